@@ -0,0 +1,74 @@
+//! Engine Benchmarks
+//!
+//! Times turn processing, rule evaluation, undo snapshots, and visibility queries on
+//! synthetic worlds of increasing size.  Run with "cargo bench --features bench".
+
+use bonaventure::bench;
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+
+/// The world sizes to benchmark: (rooms, things, rules).
+const SIZES: [(usize, usize, usize); 3] = [(10, 50, 20), (100, 1_000, 200), (500, 5_000, 1_000)];
+
+/// Returns a label for the size, e.g., "100-rooms-1000-things-200-rules".
+fn label(size: &(usize, usize, usize)) -> String {
+    format!("{}-rooms-{}-things-{}-rules", size.0, size.1, size.2)
+}
+
+fn turns(c: &mut Criterion) {
+    let console = bench::silence();
+    let mut group = c.benchmark_group("turn");
+    for size in &SIZES {
+        let mut game = bench::synthetic_game(size.0, size.1, size.2);
+        group.bench_function(BenchmarkId::from_parameter(label(size)), |b| {
+            b.iter(|| game.turn(black_box("look")))
+        });
+    }
+    group.finish();
+    bench::restore(console);
+}
+
+fn rules(c: &mut Criterion) {
+    let console = bench::silence();
+    let mut group = c.benchmark_group("rules");
+    for size in &SIZES {
+        let mut game = bench::synthetic_game(size.0, size.1, size.2);
+        group.bench_function(BenchmarkId::from_parameter(label(size)), |b| {
+            b.iter(|| bench::fire_turn_rules(&mut game))
+        });
+    }
+    group.finish();
+    bench::restore(console);
+}
+
+fn snapshots(c: &mut Criterion) {
+    let console = bench::silence();
+    let mut group = c.benchmark_group("snapshot");
+    for size in &SIZES {
+        let mut game = bench::synthetic_game(size.0, size.1, size.2);
+        group.bench_function(BenchmarkId::from_parameter(label(size)), |b| {
+            b.iter(|| bench::snapshot(&mut game))
+        });
+    }
+    group.finish();
+    bench::restore(console);
+}
+
+fn visibility(c: &mut Criterion) {
+    let console = bench::silence();
+    let mut group = c.benchmark_group("visible");
+    for size in &SIZES {
+        let game = bench::synthetic_game(size.0, size.1, size.2);
+        group.bench_function(BenchmarkId::from_parameter(label(size)), |b| {
+            b.iter(|| bench::visible_count(&game))
+        });
+    }
+    group.finish();
+    bench::restore(console);
+}
+
+criterion_group!(benches, turns, rules, snapshots, visibility);
+criterion_main!(benches);
@@ -0,0 +1,106 @@
+//! Autosave
+//!
+//! Every few turns the game saves the player's commands to one of a rotating set of
+//! autosave files, so that a long session isn't lost to a crash or an accidental quit.
+//! A save is simply the session's command history, in walkthrough format; "restore
+//! auto" replays the most recent save against a fresh copy of the scenario.  The
+//! scenario chooses how often to save; see WorldBuilder::autosave().
+//!
+//! Autosaves are only written once a file prefix has been set with set_prefix(), as
+//! the game does for interactive play; automated runs leave no files behind.
+
+use crate::walkthrough;
+use crate::Game;
+use std::cell::RefCell;
+use std::fs;
+use std::time::SystemTime;
+
+/// The default prefix for autosave files, in the current directory.
+pub const AUTOSAVE_PREFIX: &str = "bonaventure-autosave";
+
+/// The number of autosave files kept.
+pub const AUTOSAVE_SLOTS: usize = 3;
+
+/// The default number of turns between autosaves.
+pub const AUTOSAVE_TURNS: usize = 10;
+
+thread_local! {
+    /// The prefix of the autosave files, if autosaves are being written.
+    static PREFIX: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Sets the prefix of the autosave files, or stops autosaving if None.
+pub fn set_prefix(prefix: Option<&str>) {
+    PREFIX.with(|p| *p.borrow_mut() = prefix.map(|prefix| prefix.to_string()));
+}
+
+/// Returns the autosave files' prefix, if autosaves are being written.
+pub fn prefix() -> Option<String> {
+    PREFIX.with(|p| p.borrow().clone())
+}
+
+/// Returns the path of the given autosave slot.
+fn slot_path(prefix: &str, slot: usize) -> String {
+    format!("{}-{}.txt", prefix, slot + 1)
+}
+
+/// Saves the game's history to the next slot, if an autosave is due after the given
+/// number of turns.  Returns true if the game was saved.
+pub fn autosave(game: &Game, turns: usize) -> Result<bool, String> {
+    let prefix = match prefix() {
+        Some(prefix) => prefix,
+        None => return Ok(false),
+    };
+
+    let every = game.world.autosave_turns;
+
+    if every == 0 || turns == 0 || turns % every != 0 {
+        return Ok(false);
+    }
+
+    let path = slot_path(&prefix, (turns / every - 1) % AUTOSAVE_SLOTS);
+    walkthrough::save(&game.history, &path)
+        .map_err(|err| format!("Cannot write {}: {}", path, err))?;
+
+    Ok(true)
+}
+
+/// Saves the game's history at once, e.g., when the player interrupts the game,
+/// overwriting the oldest slot.  Returns true if the game was saved.
+pub fn save_now(game: &Game) -> Result<bool, String> {
+    let prefix = match prefix() {
+        Some(prefix) => prefix,
+        None => return Ok(false),
+    };
+
+    // Unwritten slots come first, as they have no modification time.
+    let path = (0..AUTOSAVE_SLOTS)
+        .map(|slot| slot_path(&prefix, slot))
+        .min_by_key(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .expect("at least one autosave slot");
+
+    walkthrough::save(&game.history, &path)
+        .map_err(|err| format!("Cannot write {}: {}", path, err))?;
+
+    Ok(true)
+}
+
+/// Returns the path of the most recent autosave, if there is one.
+pub fn latest() -> Option<String> {
+    let prefix = prefix()?;
+
+    (0..AUTOSAVE_SLOTS)
+        .map(|slot| slot_path(&prefix, slot))
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((modified, path))
+        })
+        .max_by_key(|(modified, _): &(SystemTime, String)| *modified)
+        .map(|(_, path)| path)
+}
+
+/// Loads the commands saved in the most recent autosave.
+pub fn load_latest() -> Result<Vec<String>, String> {
+    let path = latest().ok_or_else(|| "There's no autosave to restore.".to_string())?;
+    walkthrough::load(&path).map_err(|err| format!("Cannot read {}: {}", path, err))
+}
@@ -0,0 +1,113 @@
+//! Benchmark Support
+//!
+//! Tools for measuring the engine's performance on large worlds: a generator of
+//! synthetic worlds of a given size, and entry points for the operations timed by the
+//! benchmarks in benches/.  The synthetic worlds are meaningless as games, but they
+//! exercise the same code paths as real scenarios.
+
+use crate::console;
+use crate::phys;
+use crate::rule;
+use crate::types::Dir::*;
+use crate::types::Event;
+use crate::types::Flag::*;
+use crate::world::World;
+use crate::world_builder::WBEvent::*;
+use crate::world_builder::*;
+use crate::Game;
+
+/// Builds a synthetic world: a row of rooms linked east and west, with the things
+/// spread evenly among them.  Half of the rules are turn rules whose predicates are
+/// checked every turn, and half are rules for getting particular things.  The player
+/// starts in the first room.
+pub fn synthetic_world(rooms: usize, things: usize, rules: usize) -> World {
+    let rooms = rooms.max(1);
+    let mut wb = WorldBuilder::new();
+
+    wb.player().location("room-0");
+
+    // FIRST, create the rooms.
+    for i in 0..rooms {
+        let mut room = wb
+            .room(&format!("room-{}", i), &format!("Room {}", i))
+            .prose("A featureless room.");
+
+        if i > 0 {
+            room = room.link(West, &format!("room-{}", i - 1));
+        }
+
+        if i + 1 < rooms {
+            room.link(East, &format!("room-{}", i + 1));
+        }
+    }
+
+    // NEXT, create the things.
+    for i in 0..things {
+        wb.thing(&format!("thing-{}", i), &format!("thing {}", i), &format!("thing{}", i))
+            .location(&format!("room-{}", i % rooms))
+            .on_examine("A featureless thing.");
+    }
+
+    // NEXT, create the rules.
+    for i in 0..rules {
+        if i % 2 == 0 || things == 0 {
+            wb.rule(&format!("rule-{}", i))
+                .when(&|w| w.has(PLAYER, Dead))
+                .print("Never printed.");
+        } else {
+            wb.on(&GetThing(&format!("thing-{}", i % things)))
+                .print("Taken again.");
+        }
+    }
+
+    wb.world()
+}
+
+/// The console's state before silence(), for restore().
+pub struct ConsoleState {
+    interactive: bool,
+    quiet: bool,
+}
+
+/// Discards all output and skips "[more]" prompts, so that the benchmarks measure the
+/// engine rather than the terminal.  Returns the console's previous state.
+pub fn silence() -> ConsoleState {
+    let state = ConsoleState {
+        interactive: console::is_interactive(),
+        quiet: console::is_quiet(),
+    };
+
+    console::set_interactive(false);
+    console::set_quiet(true);
+    state
+}
+
+/// Restores the console's state as it was before silence().
+pub fn restore(state: ConsoleState) {
+    console::set_interactive(state.interactive);
+    console::set_quiet(state.quiet);
+}
+
+/// Creates a game whose world is a synthetic world; see synthetic_world().
+/// Restarting the game restarts the standard scenario.
+pub fn synthetic_game(rooms: usize, things: usize, rules: usize) -> Game {
+    let mut game = Game::new();
+    game.world = synthetic_world(rooms, things, rules);
+    game
+}
+
+/// Fires the rules for a turn event, as the game does at the end of each turn.
+pub fn fire_turn_rules(game: &mut Game) -> bool {
+    rule::fire_event(&mut game.world, &Event::Turn)
+}
+
+/// Saves the world state for undo, as the game does before each command.
+pub fn snapshot(game: &mut Game) {
+    let undo_info = game.world.clone();
+    game.save_for_undo(undo_info);
+}
+
+/// Returns the number of things the player can see.
+pub fn visible_count(game: &Game) -> usize {
+    phys::visible(&game.world, game.world.pid).len()
+}
@@ -1,7 +1,13 @@
 //! The Command System
 /// This system is for parsing commands and preparing them for execution,
 /// not for executing them.
+use crate::entity::ID;
+use crate::phys;
+use crate::script::Script;
+use crate::types::*;
 use crate::world::World;
+use crate::world::WorldQuery;
+use std::collections::BTreeSet;
 
 pub struct Command {
     // The original text of the command, as entered by the user
@@ -11,6 +17,9 @@ pub struct Command {
     // vector of some enum type.
     pub words: Vec<String>,
 
+    // The value of each word that's a number, e.g., "42" or "forty-two", by position.
+    pub numbers: Vec<Option<usize>>,
+
     // If true, this is a debugging command.
     pub is_debug: bool,
 }
@@ -19,46 +28,257 @@ impl Command {
     fn new(input: &str, words: Vec<String>) -> Command {
         Command {
             input: input.into(),
+            numbers: words.iter().map(|w| parse_numeral(w)).collect(),
             words,
             is_debug: input.starts_with('!'),
         }
     }
+
+    /// Returns the value of the ith word, if it's a number.
+    pub fn number(&self, i: usize) -> Option<usize> {
+        self.numbers.get(i).copied().flatten()
+    }
+
+    /// Returns the ith word as a code to enter on a dial or keypad: a number word is
+    /// written as digits, and anything else is taken as is.
+    pub fn code(&self, i: usize) -> String {
+        match self.number(i) {
+            Some(n) if !self.words[i].starts_with(|c: char| c.is_ascii_digit()) => n.to_string(),
+            _ => self.words[i].clone(),
+        }
+    }
+}
+
+/// A scenario-provided extension to the parser, for input conventions the built-in
+/// parser doesn't support.  Extensions are registered with
+/// WorldBuilder::parser_extension(), and are applied in the order registered.  Both
+/// methods have defaults that do nothing, so an extension implements only what it needs.
+pub trait ParserExtension {
+    /// Transforms the command's words as split from the input, punctuation and all,
+    /// before articles are stripped and synonyms translated, e.g., to join quoted text
+    /// into a single word.  Words it produces are kept as is; words it passes through
+    /// are cleaned of punctuation afterwards.
+    fn tokens(&self, words: Vec<String>) -> Vec<String> {
+        words
+    }
+
+    /// Handles the whole command before any of the built-in or custom commands get to
+    /// it.  It receives the input as the player entered it as well as the command's
+    /// words, and works like a CommandHook: it returns Handled::Pass to leave the
+    /// command to the rest of the parser.
+    fn sentence(&self, _world: &WorldQuery, _input: &str, _words: &[&str], _script: &mut Script) -> CommandResult {
+        Ok(Handled::Pass)
+    }
+}
+
+/// A parser extension that joins quoted text into a single word without its quotes, so
+/// that "say \"open sesame\"" has the words "say" and "open sesame".
+#[allow(dead_code)]
+pub struct Quotes;
+
+impl ParserExtension for Quotes {
+    fn tokens(&self, words: Vec<String>) -> Vec<String> {
+        let mut result: Vec<String> = Vec::new();
+        let mut quoted: Option<Vec<String>> = None;
+
+        for word in words {
+            match &mut quoted {
+                Some(text) => text.push(word),
+                None if word.starts_with('"') => quoted = Some(vec![word]),
+                None => result.push(word),
+            }
+
+            if let Some(text) = &quoted {
+                let last = text.last().unwrap();
+                if last.ends_with('"') && (text.len() > 1 || last.len() > 1) {
+                    result.push(text.join(" ").trim_matches('"').trim().into());
+                    quoted = None;
+                }
+            }
+        }
+
+        // An unterminated quote runs to the end of the input.
+        if let Some(text) = quoted {
+            result.push(text.join(" ").trim_matches('"').trim().into());
+        }
+
+        result
+    }
 }
 
 pub fn parse(world: &World, input: &str) -> Result<Command, String> {
-    // FIRST, remove extraneous characters.
+    // FIRST, split into words
     let input = input.trim();
-    let mut text = String::new();
+    let split: Vec<String> = input.split_whitespace().map(|w| w.to_string()).collect();
 
-    for c in input.chars() {
-        match c {
-            ',' | '!' => {}
-            '.' => {
-                return Err("Input contains '.'; multiple commands not yet support.".into());
-            }
-            _ => text.push(c),
+    // NEXT, let the scenario's parser extensions transform the words, punctuation and
+    // all, so that quoted text can keep its own.
+    let mut tokens = split.clone();
+
+    if !input.starts_with('!') {
+        for ext in &world.parser_extensions {
+            tokens = ext.tokens(tokens);
         }
     }
 
-    // NEXT, split into words
-    let raw_words: Vec<&str> = text.split_whitespace().collect();
+    // NEXT, remove extraneous characters from the words the extensions passed through;
+    // the words they produced are taken as is.
+    let mut raw_words: Vec<String> = Vec::new();
+
+    for token in tokens {
+        if !split.contains(&token) {
+            raw_words.push(token);
+            continue;
+        }
+
+        let mut word = String::new();
+
+        for c in token.chars() {
+            match c {
+                ',' | '!' => {}
+                '.' => {
+                    return Err("Input contains '.'; multiple commands not yet support.".into());
+                }
+                _ => word.push(c),
+            }
+        }
+
+        if !word.is_empty() {
+            raw_words.push(word);
+        }
+    }
 
-    // NEXT, strip articles and translate synonyms.
+    // NEXT, strip articles and translate synonyms.  "My" is treated as an article, so
+    // that "wash my hands" is "wash hands".
     let mut words: Vec<String> = Vec::new();
 
     for word in raw_words {
-        match word {
-            "a" | "an" | "the" => (),
+        match word.as_str() {
+            "a" | "an" | "the" | "my" => (),
             _ => {
-                if let Some(canon) = world.synonyms.get(word) {
+                if let Some(canon) = world.synonyms.get(&word) {
                     words.push(canon.to_string());
                 } else {
-                    words.push(word.to_string());
+                    words.push(word);
                 }
             }
         }
     }
 
+    // NEXT, join the words of each noun phrase into a single word.
+    if !input.starts_with('!') {
+        words = noun_phrases(world, words);
+    }
+
     // NEXT, return the result.
     Ok(Command::new(input, words))
 }
+
+/// Joins the words of each multi-word noun phrase into a single word, so that "get
+/// emotional support sword" has the words "get" and "emotional support sword".  A
+/// phrase is a run of words that could refer to some thing the player can see; the
+/// longest such run wins.  The first word is taken to be the verb, and is never part of
+/// one.
+fn noun_phrases(world: &World, words: Vec<String>) -> Vec<String> {
+    // FIRST, only things in scope can make a phrase; look them up by their head nouns
+    // in the noun index.
+    let scope = phys::visible(world, world.pid);
+    let containers: BTreeSet<ID> = scope.iter().map(|id| phys::loc(world, *id)).collect();
+    let in_scope = |phrase: &str| {
+        let head = phrase.rsplit(' ').next().unwrap_or(phrase);
+        let named = containers
+            .iter()
+            .filter_map(|c| world.noun_index.get(c)?.get(head))
+            .flatten()
+            .any(|id| scope.contains(id) && world.things[id].has_noun(phrase));
+
+        named
+            || scope
+                .iter()
+                .any(|id| world.things.get(id).map_or(false, |t| t.has_plural_noun(phrase)))
+    };
+
+    // NEXT, join each phrase's words.
+    let mut result = Vec::new();
+    let mut i = 0;
+
+    while i < words.len() {
+        let is_phrase = |j: &usize| in_scope(&words[i..*j].join(" "));
+        let end = match i {
+            0 => None,
+            _ => (i + 2..=words.len()).rev().find(is_phrase),
+        };
+
+        match end {
+            Some(j) => {
+                result.push(words[i..j].join(" "));
+                i = j;
+            }
+            None => {
+                result.push(words[i].clone());
+                i += 1;
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console;
+    use crate::Game;
+
+    fn words(text: &str) -> Vec<String> {
+        text.split_whitespace().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_quotes() {
+        assert_eq!(Quotes.tokens(words("say \"open sesame\" loudly")), vec!["say", "open sesame", "loudly"]);
+        assert_eq!(Quotes.tokens(words("say \"hello\"")), vec!["say", "hello"]);
+        assert_eq!(Quotes.tokens(words("say \" hi")), vec!["say", "hi"]);
+        assert_eq!(Quotes.tokens(words("look")), vec!["look"]);
+    }
+
+    #[test]
+    fn test_parse_quotes() {
+        console::set_interactive(false);
+
+        let mut world = Game::new().world;
+        world.parser_extensions.push(&Quotes);
+
+        assert_eq!(parse(&world, "say \"Hi.\"").unwrap().words, vec!["say", "Hi."]);
+        assert_eq!(
+            parse(&world, "say, \"Well, hello!\"").unwrap().words,
+            vec!["say", "Well, hello!"]
+        );
+        assert!(parse(&world, "say Hi.").is_err());
+    }
+
+    #[test]
+    fn test_noun_phrases() {
+        console::set_interactive(false);
+
+        let mut world = Game::new().world;
+        let sword = world.lookup("sword");
+        world.things.get_mut(&sword).unwrap().name = "emotional support sword".into();
+        let (pid, hilltop) = (world.pid, world.lookup("hilltop"));
+
+        // The sword is out of sight, so it can't join the words.
+        assert_eq!(parse(&world, "get support sword").unwrap().words, words("get support sword"));
+
+        // In sight, the phrase is one word, and the longest phrase wins.
+        phys::move_thing(&mut world, pid, hilltop);
+        assert_eq!(parse(&world, "get support sword").unwrap().words, vec!["get", "support sword"]);
+        assert_eq!(
+            parse(&world, "get emotional support sword").unwrap().words,
+            vec!["get", "emotional support sword"]
+        );
+        assert_eq!(
+            parse(&world, "get the emotional sword now").unwrap().words,
+            vec!["get", "emotional sword", "now"]
+        );
+    }
+}
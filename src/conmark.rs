@@ -3,6 +3,44 @@
 
 use textwrap::Wrapper;
 
+/// The grammatical person used for narration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum Person {
+    /// "I take the note."
+    First,
+
+    /// "You take the note."
+    Second,
+}
+
+/// The tense used for narration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum Tense {
+    /// "You take the note."
+    Present,
+
+    /// "You took the note."
+    Past,
+}
+
+/// The narration setting: person and tense.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Narration {
+    pub person: Person,
+    pub tense: Tense,
+}
+
+impl Default for Narration {
+    fn default() -> Self {
+        Self {
+            person: Person::Second,
+            tense: Tense::Present,
+        }
+    }
+}
+
 /// Wraps a text string for display to the console.  The string is wrapped to fit within
 /// the console terminal width.  The string is broken at explicit newlines.
 pub fn conwrap(text: &str) -> String {
@@ -38,6 +76,81 @@ pub fn confmt(text: &str) -> String {
     result
 }
 
+/// Expands narration placeholders in the input string.  A placeholder is a "$" followed
+/// by a word, e.g., "$You $don't see that."  The word is replaced as appropriate for the
+/// narration's person and tense, e.g., "I didn't see that."  Unknown words (and "$"
+/// followed by anything else) are left as is.
+pub fn narrate(text: &str, narration: Narration) -> String {
+    let mut result = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            result.push(c);
+            continue;
+        }
+
+        let mut word = String::new();
+        while let Some(&next) = chars.peek() {
+            if next.is_alphabetic() || next == '\'' {
+                word.push(next);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        match narrate_word(&word, narration) {
+            Some(replacement) => result.push_str(replacement),
+            None => {
+                result.push('$');
+                result.push_str(&word);
+            }
+        }
+    }
+
+    result
+}
+
+/// Returns the replacement for a narration placeholder word, or None if the word is
+/// unknown.
+fn narrate_word(word: &str, narration: Narration) -> Option<&'static str> {
+    // Forms: first person present, first person past, second person present,
+    // second person past.
+    let forms = match word {
+        "you" => ["I", "I", "you", "you"],
+        "You" => ["I", "I", "You", "You"],
+        "your" => ["my", "my", "your", "your"],
+        "Your" => ["My", "My", "Your", "Your"],
+        "yourself" => ["myself", "myself", "yourself", "yourself"],
+        "are" => ["am", "was", "are", "were"],
+        "aren't" => ["am not", "wasn't", "aren't", "weren't"],
+        "have" => ["have", "had", "have", "had"],
+        "don't" => ["don't", "didn't", "don't", "didn't"],
+        "can" => ["can", "could", "can", "could"],
+        "can't" => ["can't", "couldn't", "can't", "couldn't"],
+        "see" => ["see", "saw", "see", "saw"],
+        "hear" => ["hear", "heard", "hear", "heard"],
+        "notice" => ["notice", "noticed", "notice", "noticed"],
+        "get" => ["get", "got", "get", "got"],
+        "leave" => ["leave", "left", "leave", "left"],
+        "step" => ["step", "stepped", "step", "stepped"],
+        "feel" => ["feel", "felt", "feel", "felt"],
+        "find" => ["find", "found", "find", "found"],
+        "stumble" => ["stumble", "stumbled", "stumble", "stumbled"],
+        _ => return None,
+    };
+
+    let index = match (narration.person, narration.tense) {
+        (Person::First, Tense::Present) => 0,
+        (Person::First, Tense::Past) => 1,
+        (Person::Second, Tense::Present) => 2,
+        (Person::Second, Tense::Past) => 3,
+    };
+
+    Some(forms[index])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,4 +177,26 @@ mod tests {
     fn test_confmt_newline() {
         assert_eq!(confmt("ab|cd"), "ab\ncd");
     }
+
+    #[test]
+    fn test_narrate_second_present() {
+        let n = Narration::default();
+        assert_eq!(narrate("$You $don't see it.", n), "You don't see it.");
+    }
+
+    #[test]
+    fn test_narrate_first_past() {
+        let n = Narration {
+            person: Person::First,
+            tense: Tense::Past,
+        };
+        assert_eq!(narrate("$You $don't see it.", n), "I didn't see it.");
+        assert_eq!(narrate("$You $are here.", n), "I was here.");
+    }
+
+    #[test]
+    fn test_narrate_unknown() {
+        let n = Narration::default();
+        assert_eq!(narrate("Pay $10,000 $dollars.", n), "Pay $10,000 $dollars.");
+    }
 }
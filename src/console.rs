@@ -1,28 +1,81 @@
 //! # Console I/O
 //! Create a Console to read input in "readline" fashion.  Use para() and the para!() macro
-//! to output paragraphs of text.
+//! to output paragraphs of text.  Output is buffered until flush() is called at the end of
+//! the turn; if there's more of it than will fit on the terminal, it is paginated.
 
 use crate::conmark::*;
 
 use rustyline::error::ReadlineError;
 use rustyline::Editor;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
 
-/// A console input abstraction, wrapping the rustyline input processor.
+thread_local! {
+    /// If true, paragraph output is suppressed.
+    static SUPPRESSED: Cell<bool> = Cell::new(false);
+
+    /// If true, all output is discarded, e.g., for benchmarks.
+    static QUIET: Cell<bool> = Cell::new(false);
+
+    /// If true, a person is at the keyboard.
+    static INTERACTIVE: Cell<bool> = Cell::new(true);
+
+    /// If true, output is tailored for screen readers.
+    static ACCESSIBLE: Cell<bool> = Cell::new(false);
+
+    /// The narration setting, used to expand narration placeholders.
+    static NARRATION: Cell<Narration> = Cell::new(Narration::default());
+
+    /// Output lines waiting to be flushed.
+    static BUFFER: RefCell<Vec<String>> = RefCell::new(Vec::new());
+}
+
+/// A line of input read from the console.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    /// A non-empty command line.
+    Command(String),
+
+    /// The player pressed ^C at the prompt.
+    Interrupted,
+
+    /// The player pressed ^D at the prompt, or piped input is exhausted.
+    Eof,
+}
+
+/// A console input abstraction, wrapping the rustyline input processor.  If the input
+/// isn't a terminal (e.g., commands are piped in from a file), lines are read directly
+/// without prompting.
 pub struct Console {
     rusty: Editor<()>,
+    interactive: bool,
 }
 
 impl Console {
     /// Creates the console input abstraction.
     pub fn new() -> Console {
+        let interactive = atty::is(atty::Stream::Stdin);
+        set_interactive(interactive);
+
         Console {
             rusty: Editor::<()>::new(),
+            interactive,
         }
     }
 
     /// Read a non-empty line from the console, using the given prompt.
-    /// Ignores empty lines; halts on ^C, ^D.
-    pub fn readline(&mut self, prompt: &str) -> String {
+    /// Ignores empty lines; reports ^C and ^D (or the end of piped input) as such.
+    pub fn readline(&mut self, prompt: &str) -> Input {
+        if !self.interactive {
+            return match read_command(&mut io::stdin().lock()) {
+                Some(line) => Input::Command(line),
+                None => Input::Eof,
+            };
+        }
+
         loop {
             match self.rusty.readline(prompt) {
                 Ok(line) => {
@@ -31,26 +84,138 @@ impl Console {
                         continue;
                     } else {
                         self.rusty.add_history_entry(line);
-                        return line.to_string();
+                        return Input::Command(line.to_string());
                     }
                 }
-                Err(ReadlineError::Interrupted) => break,
-                Err(ReadlineError::Eof) => break,
+                Err(ReadlineError::Interrupted) => return Input::Interrupted,
+                Err(ReadlineError::Eof) => return Input::Eof,
                 Err(err) => {
                     println!("Input Error: {:?}", err);
                     continue;
                 }
             }
         }
+    }
+}
+
+/// Reads the next non-empty line from the reader, without prompting.  Returns None at
+/// the end of the input.
+pub fn read_command<R: BufRead>(reader: &mut R) -> Option<String> {
+    let mut line = String::new();
 
-        ::std::process::exit(0);
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) | Err(_) => return None,
+            Ok(_) => {
+                let line = line.trim();
+                if !line.is_empty() {
+                    return Some(line.to_string());
+                }
+            }
+        }
     }
 }
 
+/// Specifies whether a person is at the keyboard.  If not, output isn't paginated
+/// and "[more]" prompts are skipped, so that scripted runs don't consume commands
+/// as keypresses.
+pub fn set_interactive(flag: bool) {
+    INTERACTIVE.with(|i| i.set(flag));
+}
+
+/// Is a person at the keyboard?
+pub fn is_interactive() -> bool {
+    INTERACTIVE.with(|i| i.get())
+}
+
+/// Specifies whether all output is discarded, e.g., so that benchmarks measure the
+/// engine rather than the terminal.
+pub fn set_quiet(flag: bool) {
+    QUIET.with(|q| q.set(flag));
+}
+
+/// Is all output being discarded?
+pub fn is_quiet() -> bool {
+    QUIET.with(|q| q.get())
+}
+
 /// Outputs the text as a block paragraph, i.e., adds an extra newline.
 /// This is the normal way to output text.
 pub fn para(text: &str) {
-    println!("{}\n", conwrap(&confmt(text)));
+    if SUPPRESSED.with(|s| s.get()) || QUIET.with(|q| q.get()) {
+        return;
+    }
+
+    BUFFER.with(|b| {
+        let mut buff = b.borrow_mut();
+        let text = narrate(text, NARRATION.with(|n| n.get()));
+        buff.extend(conwrap(&confmt(&text)).lines().map(|line| line.to_string()));
+        buff.push(String::new());
+    });
+}
+
+/// Outputs a line of text as is, without wrapping or narration, e.g., debugging output.
+/// It's buffered along with the paragraphs, so that it appears in order.
+pub fn line(text: &str) {
+    BUFFER.with(|b| b.borrow_mut().push(text.into()));
+}
+
+/// Writes any buffered output to the terminal.  If the output won't fit on the terminal,
+/// it is displayed a page at a time, with a "[MORE]" prompt between pages.
+pub fn flush() {
+    let lines: Vec<String> = BUFFER.with(|b| b.borrow_mut().drain(..).collect());
+    let interactive = is_interactive();
+    let page_size = match term_size::dimensions() {
+        Some((_, height)) if interactive && height > 1 => height - 1,
+        _ => lines.len().max(1),
+    };
+
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 && i % page_size == 0 {
+            prompt("[MORE]");
+        }
+        println!("{}", line);
+    }
+}
+
+/// Sets the narration setting used to expand narration placeholders in output.
+/// See conmark::narrate().
+pub fn set_narration(narration: Narration) {
+    NARRATION.with(|n| n.set(narration));
+}
+
+/// Turns screen-reader friendly output on or off.
+pub fn set_accessible(flag: bool) {
+    ACCESSIBLE.with(|a| a.set(flag));
+}
+
+/// Is screen-reader friendly output on?
+pub fn is_accessible() -> bool {
+    ACCESSIBLE.with(|a| a.get())
+}
+
+/// Suppresses or resumes paragraph output, e.g., so that nothing follows a cutscene
+/// for the rest of the turn.
+pub fn suppress(flag: bool) {
+    SUPPRESSED.with(|s| s.set(flag));
+}
+
+/// Flushes the output, then prompts with "[more]" and waits for the user to press Enter.
+pub fn more() {
+    flush();
+    if is_interactive() {
+        prompt("[more]");
+    }
+}
+
+/// Displays the prompt, and waits for the user to press Enter.
+fn prompt(text: &str) {
+    print!("{}", text);
+    io::stdout().flush().ok();
+
+    let mut line = String::new();
+    io::stdin().read_line(&mut line).ok();
 }
 
 /// Formats its arguments using format!(), and outputs them as a wrapped
@@ -0,0 +1,72 @@
+//! The Consumable System
+//!
+//! Consumables are things with a limited number of charges: a lamp's oil, a box of
+//! matches, a potion that can be sipped a few times.  The charges left are kept in the
+//! thing's "charges" variable.  A script uses a charge with `consume()`; a consumable
+//! machine uses one automatically each turn it runs.  When the charges run low the
+//! thing's warning is displayed, and when they run out the thing is flagged Exhausted:
+//! it switches off, its description notes that it's used up, and it can no longer be
+//! used.
+
+use crate::entity::ID;
+use crate::phys;
+use crate::rule;
+use crate::types::sentence;
+use crate::types::Event;
+use crate::types::Flag;
+use crate::visual;
+use crate::world::World;
+
+/// The name of the variable that holds a consumable's charges.
+pub const CHARGES: &str = "charges";
+
+/// Is the thing a consumable?
+pub fn is_consumable(world: &World, thing: ID) -> bool {
+    world.consumables.get(&thing).is_some()
+}
+
+/// Has the thing used up all of its charges?
+pub fn is_exhausted(world: &World, thing: ID) -> bool {
+    world.has_flags(thing) && world.has_flag(thing, Flag::Exhausted)
+}
+
+/// Returns the number of charges the consumable has left.
+pub fn charges(world: &World, thing: ID) -> i64 {
+    world.var(thing, CHARGES)
+}
+
+/// Returns the prose added to the description of an exhausted consumable.
+pub fn exhausted_prose(world: &World, thing: ID) -> Option<&str> {
+    match world.consumables.get(&thing) {
+        Some(consumablec) if is_exhausted(world, thing) => Some(&consumablec.exhausted),
+        _ => None,
+    }
+}
+
+/// Uses one of the consumable's charges.  Displays the warning if the charges have run
+/// low, and exhausts the thing if they've run out.  The player only sees the messages
+/// if the thing is visible.  Does nothing if the thing is already exhausted.
+pub fn consume(world: &mut World, thing: ID) {
+    if !is_consumable(world, thing) || is_exhausted(world, thing) {
+        return;
+    }
+
+    let left = charges(world, thing) - 1;
+    world.set_var(thing, CHARGES, left);
+
+    let seen = phys::visible(world, world.pid).contains(&thing);
+
+    if left <= 0 {
+        world.set_flag(thing, Flag::Exhausted);
+        world.unset_flag(thing, Flag::On);
+        if seen {
+            let text = format!("{} is used up.", visual::the_name(world, thing));
+            visual::info(&sentence(&text));
+        }
+        rule::fire_event(world, &Event::Exhausted(thing));
+    } else if left <= world.consumables[&thing].low && seen {
+        if let Some(warning) = &world.consumables[&thing].warning {
+            visual::info(warning);
+        }
+    }
+}
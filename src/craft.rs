@@ -0,0 +1,50 @@
+//! The Crafting Module
+//!
+//! Scenarios define recipes: two things that combine to make a third, e.g., a stick and
+//! a rag that make a torch.  The player combines the things with "combine", "attach",
+//! or "mix"; the inputs are moved to LIMBO, and the output appears in the player's
+//! inventory.  Rules on the Combine event can forbid the combination or add to it.
+
+use crate::entity::ID;
+use crate::entity::PlayerID;
+use crate::entity::ThingID;
+use crate::phys;
+use crate::rule;
+use crate::types::sentence;
+use crate::types::Event::*;
+use crate::visual;
+use crate::world::World;
+use crate::world::LIMBO;
+
+/// Returns the output of the recipe that combines the two things, in either order,
+/// if there is one.
+pub fn recipe_for(world: &World, a: ID, b: ID) -> Option<ID> {
+    world
+        .recipes
+        .iter()
+        .find(|r| r.inputs == (a, b) || r.inputs == (b, a))
+        .map(|r| r.output)
+}
+
+/// The player combines the two things.  The caller is responsible for verifying that
+/// the player is carrying them.
+pub fn combine(world: &mut World, pid: PlayerID, a: ThingID, b: ThingID) -> Result<(), String> {
+    let (pid, a, b) = (pid.id(), a.id(), b.id());
+
+    let output = match recipe_for(world, a, b) {
+        Some(output) => output,
+        None => return Err("Those don't go together.".into()),
+    };
+
+    if rule::allows(world, &Combine(pid, output)) {
+        phys::move_thing(world, a, LIMBO);
+        phys::move_thing(world, b, LIMBO);
+        phys::move_thing(world, output, pid);
+
+        let text = format!("$You now $have {}", visual::a_name(world, output));
+        visual::act(&sentence(&text));
+        rule::fire_event(world, &Combine(pid, output));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! Crash Reports
+//!
+//! When the game panics during a turn, the player gets a friendly message rather than
+//! a bare backtrace, and the scenario author gets a crash file with the data needed to
+//! reproduce the problem: the panic message, the player's commands, the event log, and
+//! a JSON dump of the world.
+
+use crate::console;
+use crate::debug;
+use crate::Game;
+use std::cell::RefCell;
+use std::fs;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+
+/// The crash file, in the current directory.
+pub const CRASH_FILE: &str = "bonaventure-crash.txt";
+
+thread_local! {
+    /// The message and location of the most recent panic.
+    static LAST_PANIC: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Installs a panic hook that saves the panic's message for the crash report, instead
+/// of printing it and a backtrace.  Panics outside a turn, e.g., while the world is
+/// built, keep the usual hook; see turn().
+fn install_hook() {
+    panic::set_hook(Box::new(|info| {
+        let payload = info.payload();
+        let msg = if let Some(msg) = payload.downcast_ref::<&str>() {
+            msg.to_string()
+        } else if let Some(msg) = payload.downcast_ref::<String>() {
+            msg.clone()
+        } else {
+            "unknown panic".to_string()
+        };
+
+        let text = match info.location() {
+            Some(loc) => format!("{} at {}:{}", msg, loc.file(), loc.line()),
+            None => msg,
+        };
+
+        LAST_PANIC.with(|p| *p.borrow_mut() = Some(text));
+    }));
+}
+
+/// Executes one game turn.  If the turn panics, writes a crash report and exits.
+pub fn turn(game: &mut Game, cmd: &str) {
+    let previous = panic::take_hook();
+    install_hook();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| game.turn(cmd)));
+    panic::set_hook(previous);
+
+    if result.is_ok() {
+        return;
+    }
+
+    let msg = LAST_PANIC
+        .with(|p| p.borrow_mut().take())
+        .unwrap_or_else(|| "unknown panic".into());
+
+    // Show whatever the turn had already output before it failed.
+    console::flush();
+    println!("\nOops!  Something has gone wrong, and the game has to stop.");
+    println!("({})", msg);
+
+    match fs::write(CRASH_FILE, report(game, &msg)) {
+        Ok(()) => println!(
+            "The details are in {}; please include it in any bug report.",
+            CRASH_FILE
+        ),
+        Err(err) => println!("Cannot write {}: {}", CRASH_FILE, err),
+    }
+
+    std::process::exit(1);
+}
+
+/// Formats the crash report.
+fn report(game: &Game, msg: &str) -> String {
+    let mut text = String::new();
+
+    text.push_str(&format!("Panic: {}\n\n", msg));
+
+    text.push_str("Commands:\n");
+    for cmd in &game.history {
+        text.push_str(&format!("  {}\n", cmd));
+    }
+
+    text.push_str("\nLog:\n");
+    for (clock, entry) in game.world.log.entries() {
+        text.push_str(&format!("  [{}] {}\n", clock, entry));
+    }
+
+    text.push_str("\nWorld:\n");
+    text.push_str(&debug::world_json(&game.world));
+    text.push('\n');
+
+    text
+}
@@ -0,0 +1,83 @@
+//! Darkness
+//!
+//! A room flagged Dark is pitch dark unless something in it gives light: a thing
+//! flagged LightSource, lying there or carried, that's switched on if it's a machine.
+//! In the dark the player can't see the room or anything in it, and only some commands
+//! make sense: moving about, feeling around, dropping things, taking inventory by touch,
+//! and the commands about the game itself.  Anything else gets the scenario's response
+//! for the verb, or a general complaint about the dark.  Moving about in the dark can be
+//! risky; see WorldBuilder::dark_risk().
+
+use crate::entity::ID;
+use crate::entity::PlayerID;
+#[cfg(feature = "machines")]
+use crate::machine;
+use crate::phys;
+use crate::rule;
+use crate::types::Event::*;
+use crate::types::Flag::*;
+use crate::visual;
+use crate::world::World;
+
+/// The verbs the player can use in the dark, unless the scenario adds more.
+pub const DARK_VERBS: &[&str] = &[
+    "go", "north", "south", "east", "west", "up", "down", "in", "out", "swim", "look",
+    "feel", "touch", "drop", "inventory", "help", "chapter", "status", "time", "records", "note",
+    "journal", "erase", "accessible", "undo", "restart", "restore", "quit",
+];
+
+/// The response to a verb that makes no sense in the dark, unless the scenario has
+/// another.
+pub const DARK_RESPONSE: &str = "It's too dark to see anything.";
+
+/// Is the room dark, i.e., flagged Dark with nothing in it giving light?
+pub fn is_dark(world: &World, room: ID) -> bool {
+    world.is_room(room)
+        && world.has_flag(room, Dark)
+        && !world
+            .things
+            .keys()
+            .any(|id| gives_light(world, *id) && phys::encloses(world, room, *id))
+}
+
+/// Does the thing give light?  A light source that's a machine, e.g., a lamp, only
+/// gives light while it's running.
+pub fn gives_light(world: &World, thing: ID) -> bool {
+    #[cfg(feature = "machines")]
+    if machine::is_machine(world, thing) && !machine::is_running(world, thing) {
+        return false;
+    }
+
+    world.has_flags(thing) && world.has_flag(thing, LightSource)
+}
+
+/// The player tries to move about in the dark, and may stumble instead, as the
+/// scenario's risk decides.  If no rule responds to the stumble, the player simply gets
+/// nowhere.  Returns true if the player stumbled.
+pub fn stumbles(world: &mut World, pid: PlayerID) -> bool {
+    let here = phys::loc(world, pid.id());
+
+    if !is_dark(world, here) || world.rng.below(100) >= world.dark_risk {
+        return false;
+    }
+
+    if !rule::fire_event(world, &Stumble(pid.id(), here)) {
+        visual::act("$You $stumble around in the dark, and $get nowhere.");
+    }
+
+    true
+}
+
+/// Can the player use the verb in the dark?
+pub fn permits(world: &World, verb: &str) -> bool {
+    world.dark_verbs.contains(verb) || world.directions.contains(verb)
+}
+
+/// Returns the response to a verb the player can't use in the dark.
+pub fn response(world: &World, verb: &str) -> String {
+    world
+        .dark_responses
+        .get(verb)
+        .cloned()
+        .unwrap_or_else(|| DARK_RESPONSE.into())
+}
@@ -0,0 +1,126 @@
+//! The Deadline System
+//!
+//! Deadlines put the player under time pressure.  The scenario defines each one with
+//! WorldBuilder::deadline(), or sets a limit on the whole game with
+//! WorldBuilder::turn_limit(); rules start and stop deadlines with script actions, e.g.,
+//! lighting a fuse and cutting it.  Each turn, every running deadline counts down,
+//! warning the player at the times the scenario chose.  When one runs out, it fires its
+//! TimeUp event; unless a guard on that event intervenes, the player's time is up, and
+//! so is the player.  The player checks the time with "time".
+//!
+//! Warning texts can include "{turns}", which becomes the time left, e.g., "3 turns".
+
+use crate::entity::ID;
+use crate::phys;
+use crate::rule;
+use crate::types::list_phrase;
+use crate::types::Event;
+use crate::types::Time;
+use crate::visual;
+use crate::world::World;
+
+/// The text shown when a deadline runs out, unless the scenario has another.
+pub const TIME_UP: &str = "$You $have run out of time.";
+
+/// The Deadline system.  Counts down each running deadline, giving any warning that's
+/// due and ending the deadlines that have run out.
+pub fn system(world: &mut World) {
+    let running: Vec<ID> = world
+        .deadlines
+        .iter()
+        .filter(|(_, deadlinec)| deadlinec.left.is_some())
+        .map(|(id, _)| *id)
+        .collect();
+
+    for id in running {
+        let deadlinec = world.deadlines.get_mut(&id).unwrap();
+        let left = deadlinec.left.unwrap_or(0).saturating_sub(1);
+        deadlinec.left = Some(left);
+
+        if left == 0 {
+            expire(world, id);
+        } else if let Some(text) = warning(world, id, left) {
+            visual::info(&text.replace("{turns}", &turns_phrase(left)));
+        }
+    }
+}
+
+/// Returns the warning that's due for the deadline with the given number of turns
+/// left, if any.
+fn warning(world: &World, id: ID, left: Time) -> Option<String> {
+    let deadlinec = &world.deadlines[&id];
+
+    if let Some(text) = deadlinec.warnings.get(&left) {
+        return Some(text.clone());
+    }
+
+    match &deadlinec.every {
+        Some((every, text)) if left % every == 0 => Some(text.clone()),
+        _ => None,
+    }
+}
+
+/// The deadline has run out: fires its TimeUp event and, unless a guard intervenes,
+/// ends the game.  The deadline's tag is the cause of death, for the epitaph.
+fn expire(world: &mut World, id: ID) {
+    world.deadlines.get_mut(&id).unwrap().left = None;
+    world.log(&format!("Deadline {} ran out", world.tag(id)));
+
+    let pid = world.pid;
+    let event = Event::TimeUp(pid, id);
+
+    if rule::allows(world, &event) {
+        let text = world.deadlines[&id].expire.clone();
+        let cause = world.tag(id);
+        phys::kill(world, pid, text.as_deref().unwrap_or(TIME_UP), Some(&cause));
+        rule::fire_event(world, &event);
+    }
+}
+
+/// Starts the deadline, with its full allowance of turns; a running deadline starts
+/// over.
+pub fn start(world: &mut World, id: ID) {
+    let deadlinec = world.deadlines.get_mut(&id).unwrap();
+    deadlinec.left = Some(deadlinec.turns);
+}
+
+/// Stops the deadline, e.g., when the player has averted the threat.  Returns false
+/// if it wasn't running.
+pub fn stop(world: &mut World, id: ID) -> bool {
+    world.deadlines.get_mut(&id).unwrap().left.take().is_some()
+}
+
+/// Returns the number of turns left before the deadline runs out, or None if it isn't
+/// running.
+pub fn turns_left(world: &World, id: ID) -> Option<Time> {
+    world.deadlines[&id].left
+}
+
+/// Returns a description of the running deadlines, e.g., "3 turns left for the fuse
+/// and 120 turns left in the game", or None if there are none.
+pub fn describe(world: &World) -> Option<String> {
+    let phrases: Vec<String> = world
+        .deadlines
+        .values()
+        .filter_map(|deadlinec| {
+            let left = deadlinec.left?;
+            Some(format!("{} left {}", turns_phrase(left), deadlinec.name))
+        })
+        .collect();
+
+    if phrases.is_empty() {
+        return None;
+    }
+
+    let phrases: Vec<&str> = phrases.iter().map(|p| p.as_str()).collect();
+    Some(list_phrase(&phrases, "and"))
+}
+
+/// Returns the number of turns as a phrase, e.g., "1 turn" or "3 turns".
+pub fn turns_phrase(turns: Time) -> String {
+    if turns == 1 {
+        "1 turn".into()
+    } else {
+        format!("{} turns", turns)
+    }
+}
@@ -1,10 +1,23 @@
 //! Debugging tools module
 
+use crate::console;
+use crate::consumable;
+#[cfg(feature = "effects")]
+use crate::effect;
+use crate::entity::prose_component::Prose;
 use crate::entity::ID;
 use crate::phys;
 use crate::world::*;
 use crate::types::LinkDest::*;
 
+/// Writes a line of debugging output through the console, so that it appears in order
+/// with the game's own output.
+macro_rules! out {
+    ($($arg:tt)*) => {
+        console::line(&format!($($arg)*))
+    };
+}
+
 /// List all entities in the world
 pub fn list_world(world: &World) {
     for id in world.tags.keys() {
@@ -13,17 +26,93 @@ pub fn list_world(world: &World) {
 }
 
 /// Dump all entities in the world
-#[allow(dead_code)]
 pub fn dump_world(world: &World) {
     for id in world.tags.keys() {
         dump_entity(world, *id);
     }
+
+    dump_log(world);
+}
+
+/// Dump the log of recent commands, events, and rule firings
+pub fn dump_log(world: &World) {
+    out!("Log:");
+    for (clock, text) in world.log.entries() {
+        out!("  [{}] {}", clock, text);
+    }
+}
+
+/// Checks the world's invariants after the named system has run, panicking with a dump
+/// of the world if any are violated.  This is expensive, so it's only done in debug builds.
+pub fn check_invariants(world: &World, system: &str) {
+    if let Err(msg) = world.validate() {
+        dump_world(world);
+        console::flush();
+        panic!("Invariant violated after {} system: {}", system, msg);
+    }
+}
+
+/// Returns a JSON dump of the world's entities: each entity's ID, tag, location,
+/// flags, and contents, along with the clock and the player's ID.
+pub fn world_json(world: &World) -> String {
+    let mut entities = Vec::new();
+
+    for (id, tc) in &world.tags {
+        let mut fields = vec![
+            format!("\"id\": {}", id),
+            format!("\"tag\": {}", json_string(&tc.tag)),
+        ];
+
+        if world.has_location(*id) {
+            fields.push(format!("\"location\": {}", phys::loc(world, *id)));
+        }
+
+        if let Some(flagc) = world.flag_sets.get(id) {
+            let flags: Vec<String> = flagc
+                .iter()
+                .map(|flag| json_string(&format!("{:?}", flag)))
+                .collect();
+            fields.push(format!("\"flags\": [{}]", flags.join(", ")));
+        }
+
+        if let Some(invc) = world.inventories.get(id) {
+            let ids: Vec<String> = invc.iter().map(|id| id.to_string()).collect();
+            fields.push(format!("\"contains\": [{}]", ids.join(", ")));
+        }
+
+        entities.push(format!("    {{{}}}", fields.join(", ")));
+    }
+
+    format!(
+        "{{\n  \"clock\": {},\n  \"player\": {},\n  \"entities\": [\n{}\n  ]\n}}",
+        world.clock,
+        world.pid,
+        entities.join(",\n")
+    )
+}
+
+/// Quotes the text as a JSON string.
+fn json_string(text: &str) -> String {
+    let mut result = String::from("\"");
+
+    for c in text.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+    result
 }
 
 /// List just the given entity
 fn list_entity(world: &World, id: ID) {
     let &tc = world.tags.get(&id).as_ref().unwrap();
-    println!("[{}] {}", tc.id, tc.tag);
+    out!("[{}] {}", tc.id, tc.tag);
 }
 
 /// Dump info about the entity with the given ID
@@ -33,54 +122,195 @@ pub fn dump_entity(world: &World, id: ID) {
     // FIRST, display its location, if any.
     if world.has_location(id) {
         let here = phys::loc(world, id);
-        println!("  Location: [{}] {}", here, world.tag(here));
+        out!("  Location: [{}] {}", here, world.tag(here));
     }
 
     // FIRST, display the player info
     if world.players.get(&id).is_some() {
-        println!("  Player");
+        out!("  Player");
     }
 
     // NEXT, if it's a thing display the thing info.
     if let Some(thingc) = &world.things.get(&id) {
-        println!("  Thing name: {}", thingc.name);
-        println!("    Noun: {}", thingc.noun);
+        out!("  Thing name: {}", thingc.name);
+        out!("    Noun: {}", thingc.noun);
+        for alias in &thingc.aliases {
+            out!("    Alias: {}", alias);
+        }
     }
 
     // NEXT, if it's a room display the room info.
     if let Some(roomc) = &world.rooms.get(&id) {
-        println!("  Room name: {}", roomc.name);
+        out!("  Room name: {}", roomc.name);
+        out!("  Visits: {}", roomc.visits);
         for (dir, dest) in &roomc.links {
             match dest {
                 Room(id) => {
-                    println!("    Link: {:?} to [{}] {}", dir, id, world.tag(*id));
+                    out!("    Link: {:?} to [{}] {}", dir, id.id(), world.tag(id.id()));
                 },
                 DeadEnd(prose) => {
-                    println!("    Link: {:?} to DeadEnd: {}", dir, prose);
+                    out!("    Link: {:?} to DeadEnd: {}", dir, prose);
                 }
             }
         }
+        for (dir, req) in &roomc.requirements {
+            out!("    Requires: {:?} needs {:?}", dir, req.capability);
+        }
+        for (dir, trap) in &roomc.traps {
+            out!("    Trap: {:?} guarded by [{}] {}", dir, trap, world.tag(*trap));
+        }
+        for (dir, door) in &roomc.doors {
+            out!("    Door: {:?} guarded by [{}] {}", dir, door, world.tag(*door));
+        }
+    }
+
+    // NEXT, if it's a random table display its settings.
+    if let Some(tablec) = &world.random_tables.get(&id) {
+        out!("  Random table: {}% chance", tablec.chance);
+        if let Some(flag) = tablec.region {
+            out!("    Region: {:?}", flag);
+        }
+        out!("    Cooldown: {} turns", tablec.cooldown);
+    }
+
+    // NEXT, if it has topics display them.
+    if let Some(topicc) = &world.topics.get(&id) {
+        for topic in &topicc.topics {
+            out!("  Topic: {}", topic.name);
+        }
+    }
+
+    // NEXT, if it's lockable display its keys.
+    if let Some(lockc) = &world.locks.get(&id) {
+        for key in &lockc.keys {
+            out!("  Key: [{}] {}", key, world.tag(*key));
+        }
+        if let Some(code) = &lockc.combination {
+            out!("  Combination: {}", code);
+        }
+    }
+
+    // NEXT, if it's a machine display its power requirement.
+    #[cfg(feature = "machines")]
+    if let Some(machinec) = &world.machines.get(&id) {
+        out!("  Machine: power {:?}", machinec.power);
+    }
+
+    // NEXT, if it's a platform display its stops.
+    if let Some(platformc) = &world.platforms.get(&id) {
+        for (i, stop) in platformc.stops.iter().enumerate() {
+            let here = if i == platformc.position { " (here)" } else { "" };
+            out!("  Stop: {:?} [{}] {}{}", stop.entry, stop.room, world.tag(stop.room), here);
+        }
+    }
+
+    // NEXT, if it's a status effect display its settings.
+    #[cfg(feature = "effects")]
+    if let Some(effectc) = &world.effects.get(&id) {
+        out!("  Effect: {}, {:?}", effectc.name, effectc.stacking);
+        if let Some(tick) = effectc.tick {
+            out!("    Tick: [{}] {}", tick.id(), world.tag(tick.id()));
+        }
+    }
+
+    // NEXT, if it's a deadline display its countdown.
+    #[cfg(feature = "deadlines")]
+    if let Some(deadlinec) = &world.deadlines.get(&id) {
+        match deadlinec.left {
+            Some(left) => out!("  Deadline: {} of {} turns left", left, deadlinec.turns),
+            None => out!("  Deadline: {} turns, not running", deadlinec.turns),
+        }
+    }
+
+    // NEXT, if it's a player display the effects they're under.
+    #[cfg(feature = "effects")]
+    if let Some(effects) = world.players.get(&id).and_then(|_| effect::describe(world, id)) {
+        out!("  Effects: {}", effects);
+    }
+
+    // NEXT, if it's a pressure plate display its load.
+    if let Some(platec) = &world.plates.get(&id) {
+        let state = if platec.pressed { "pressed" } else { "released" };
+        out!("  Plate: load {} of {}, {}", phys::load(world, id), platec.threshold, state);
+    }
+
+    // NEXT, if it's a portal display where it leads.
+    if let Some(portalc) = &world.portals.get(&id) {
+        match portalc.dest {
+            Some(dest) => out!("  Portal: to [{}] {}", dest, world.tag(dest)),
+            None => out!("  Portal: one-way exit"),
+        }
+    }
+
+    // NEXT, if it's climbable display where it leads.
+    if let Some(climbc) = &world.climbables.get(&id) {
+        out!("  Climbs: {:?}", climbc.dir);
+        if let Some(req) = &climbc.requirement {
+            out!("    Requires: {:?}", req.capability);
+        }
+    }
+
+    // NEXT, if it's a button display its platform.
+    if let Some(buttonc) = &world.buttons.get(&id) {
+        out!("  Button: [{}] {}", buttonc.platform, world.tag(buttonc.platform));
+    }
+
+    // NEXT, if it's a consumable display its charges.
+    if world.consumables.get(&id).is_some() {
+        out!("  Charges: {}", consumable::charges(world, id));
+    }
+
+    // NEXT, if it handles verbs display them.
+    if let Some(verbc) = &world.verb_handlers.get(&id) {
+        let mut verbs: Vec<&String> = verbc.handlers.keys().collect();
+        verbs.sort();
+        for verb in verbs {
+            out!("  Verb: {}", verb);
+        }
+    }
+
+    // NEXT, if it's a chapter display its title and goals.
+    if let Some(chapterc) = &world.chapters.get(&id) {
+        out!("  Chapter: {}", chapterc.title);
+        for goal in &chapterc.goals {
+            out!("    Goal: {}", goal.text);
+        }
     }
 
     // NEXT, if it's a rule display its actions.
     if let Some(rulec) = &world.rules.get(&id) {
+        if let Some(chapter) = rulec.chapter {
+            out!("  In chapter: [{}] {}", chapter, world.tag(chapter));
+        }
+        if let Some(flag) = rulec.class {
+            out!("  For any subject with: {:?}", flag);
+        }
+        if rulec.cooldown > 0 {
+            out!("  Cooldown: {} turns", rulec.cooldown);
+        }
+        if rulec.once_per_visit {
+            out!("  Once per visit");
+        }
+        if let Some(room) = rulec.redirect {
+            out!("  Redirects to: [{}] {}", room, world.tag(room));
+        }
         rulec.script.dump("  ");
     }
 
     // NEXT, display its flags, if any.
     if let Some(flagc) = &world.flag_sets.get(&id) {
         for flag in flagc.iter() {
-            println!("  Flag: {:?}", flag);
+            out!("  Flag: {:?}", flag);
         }
     }
 
     // NEXT, display its inventory, if any.
     if let Some(invc) = world.inventories.get(&id) {
-        if invc.things.is_empty() {
-            println!("  Contains: nothing");
+        if invc.is_empty() {
+            out!("  Contains: nothing");
         } else {
-            for tid in &invc.things {
-                println!("  Contains: [{}] {}", tid, world.tag(*tid));
+            for tid in invc.iter() {
+                out!("  Contains: [{}] {}", tid, world.tag(*tid));
             }
         }
     }
@@ -88,7 +318,15 @@ pub fn dump_entity(world: &World, id: ID) {
     // NEXT, display any associated prose (given the entity's current state)
     if let Some(prosec) = &world.proses.get(&id) {
         for (prose_type, prose) in &prosec.types {
-            println!("  Prose [{:?}]: {}", prose_type, prose.as_string(world, id));
+            match prose {
+                // Don't count the dump as a view.
+                Prose::Variants(variants) => {
+                    for text in variants.texts() {
+                        out!("  Prose [{:?}] variant: {}", prose_type, text);
+                    }
+                }
+                _ => out!("  Prose [{:?}]: {}", prose_type, prose.as_string(world, id, *prose_type)),
+            }
         }
     }
 }
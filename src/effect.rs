@@ -0,0 +1,134 @@
+//! The Effect System
+//!
+//! Status effects are conditions the player can be under for a while: poisoned,
+//! blessed, invisible.  The scenario defines each effect with WorldBuilder::effect();
+//! rules apply and remove them with script actions, for a number of turns or until
+//! removed.  Each turn, every active effect runs its tick script, e.g., to cost the
+//! poisoned player some health, and counts down; when its time is up it wears off.
+//! The player's effects are shown by "status" and when the player examines themselves.
+//!
+//! Applying an effect that's already active follows the effect's stacking rule; see
+//! Stacking.
+
+use crate::entity::effect_component::ActiveEffect;
+use crate::entity::ID;
+use crate::types::list_phrase;
+use crate::types::Stacking;
+use crate::types::Time;
+use crate::visual;
+use crate::world::World;
+
+/// The Effect system.  Runs each active effect's tick script and counts down its
+/// duration, removing the effects that have worn off.
+pub fn system(world: &mut World) {
+    let players: Vec<ID> = world.players.keys().cloned().collect();
+
+    for pid in players {
+        let active: Vec<(ID, usize)> = world.players[&pid]
+            .effects
+            .iter()
+            .map(|(id, activec)| (*id, activec.stacks))
+            .collect();
+
+        for (effect, stacks) in active {
+            if let Some(tick) = world.effects[&effect].tick {
+                let script = world.rules[&tick.id()].script.clone();
+                for _ in 0..stacks {
+                    script.execute_with(world, Some(pid));
+                }
+            }
+
+            // The tick script might have removed it.
+            let expired = match world.players.get_mut(&pid).unwrap().effects.get_mut(&effect) {
+                Some(ActiveEffect { turns: Some(turns), .. }) => {
+                    *turns = turns.saturating_sub(1);
+                    *turns == 0
+                }
+                _ => false,
+            };
+
+            if expired {
+                world.log(&format!("Effect {} wore off", world.tag(effect)));
+                remove(world, pid, effect);
+            }
+        }
+    }
+}
+
+/// Is the effect active on the player?
+pub fn is_active(world: &World, pid: ID, effect: ID) -> bool {
+    world.players[&pid].effects.contains_key(&effect)
+}
+
+/// Applies the effect to the player for the given number of turns, or until removed.
+/// If the effect is already active, it's reapplied according to its stacking rule.
+pub fn apply(world: &mut World, pid: ID, effect: ID, turns: Option<Time>) {
+    let stacking = world.effects[&effect].stacking;
+    let effects = &mut world.players.get_mut(&pid).unwrap().effects;
+
+    match effects.get_mut(&effect) {
+        None => {
+            effects.insert(effect, ActiveEffect { turns, stacks: 1 });
+        }
+        Some(activec) => {
+            activec.turns = match (stacking, activec.turns, turns) {
+                (_, None, _) | (_, _, None) => None,
+                (Stacking::Extend, Some(left), Some(more)) => Some(left + more),
+                (_, Some(left), Some(more)) => Some(left.max(more)),
+            };
+
+            if stacking == Stacking::Stack {
+                activec.stacks += 1;
+            }
+        }
+    }
+}
+
+/// Removes the effect from the player, showing its expiry text, if any.  Returns false
+/// if the effect wasn't active.
+pub fn remove(world: &mut World, pid: ID, effect: ID) -> bool {
+    if world.players.get_mut(&pid).unwrap().effects.remove(&effect).is_none() {
+        return false;
+    }
+
+    if let Some(text) = &world.effects[&effect].expire {
+        visual::info(text);
+    }
+
+    true
+}
+
+/// Returns a description of the player's active effects, e.g., "poisoned (x2, 3 turns
+/// left) and blessed", or None if there are none.
+pub fn describe(world: &World, pid: ID) -> Option<String> {
+    let effects = &world.players[&pid].effects;
+
+    if effects.is_empty() {
+        return None;
+    }
+
+    let phrases: Vec<String> = effects
+        .iter()
+        .map(|(id, activec)| {
+            let mut notes = Vec::new();
+            if activec.stacks > 1 {
+                notes.push(format!("x{}", activec.stacks));
+            }
+            match activec.turns {
+                Some(1) => notes.push("1 turn left".to_string()),
+                Some(turns) => notes.push(format!("{} turns left", turns)),
+                None => {}
+            }
+
+            let name = &world.effects[id].name;
+            if notes.is_empty() {
+                name.clone()
+            } else {
+                format!("{} ({})", name, notes.join(", "))
+            }
+        })
+        .collect();
+
+    let phrases: Vec<&str> = phrases.iter().map(|p| p.as_str()).collect();
+    Some(list_phrase(&phrases, "and"))
+}
@@ -1,14 +1,76 @@
 //! The Entity Data Type and Builder
 
+pub mod button_component;
+pub mod chapter_component;
+pub mod climb_component;
+pub mod consumable_component;
+#[cfg(feature = "deadlines")]
+pub mod deadline_component;
+#[cfg(feature = "effects")]
+pub mod effect_component;
 pub mod flag_set_component;
 pub mod inventory_component;
 pub mod location_component;
+pub mod lock_component;
+#[cfg(feature = "machines")]
+pub mod machine_component;
+pub mod platform_component;
+pub mod plate_component;
+pub mod portal_component;
 pub mod player_component;
 pub mod prose_component;
+pub mod random_table_component;
 pub mod room_component;
 pub mod rule_component;
 pub mod tag_component;
 pub mod thing_component;
+pub mod topic_component;
+pub mod var_component;
+pub mod verb_component;
 
 /// The entity ID type: an integer.
 pub type ID = usize;
+
+/// Defines a typed wrapper around an entity ID, for a particular kind of entity.  Functions
+/// that need a particular kind of entity take the typed ID, so that they can't be given the
+/// wrong kind by mistake.  The builders return typed IDs; World::player_id(), etc., validate
+/// bare IDs in dynamic cases.
+macro_rules! typed_id {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(ID);
+
+        impl $name {
+            /// Wraps the ID, which the caller knows to be of the right kind.
+            pub(crate) fn new(id: ID) -> Self {
+                $name(id)
+            }
+
+            /// Returns the bare entity ID.
+            pub fn id(self) -> ID {
+                self.0
+            }
+        }
+    };
+}
+
+typed_id!(
+    /// The ID of a player entity.
+    PlayerID
+);
+
+typed_id!(
+    /// The ID of a room entity.
+    RoomID
+);
+
+typed_id!(
+    /// The ID of a thing entity.
+    ThingID
+);
+
+typed_id!(
+    /// The ID of a rule or named script entity.
+    RuleID
+);
@@ -0,0 +1,22 @@
+//! The button component.  Buttons are things that move a platform when pushed (or
+//! pressed): either to a particular stop, e.g., an elevator's call button or floor
+//! button, or on to the platform's next stop, e.g., a ferry's bell.
+
+use crate::entity::ID;
+
+/// Information specific to platform buttons.
+#[derive(Debug, Clone)]
+pub struct ButtonComponent {
+    /// The platform the button moves.
+    pub platform: ID,
+
+    /// The stop the button sends the platform to, or None for the next stop.
+    pub stop: Option<ID>,
+}
+
+impl ButtonComponent {
+    /// Creates a new button for the platform.
+    pub fn new(platform: ID, stop: Option<ID>) -> Self {
+        Self { platform, stop }
+    }
+}
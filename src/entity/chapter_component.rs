@@ -0,0 +1,38 @@
+//! Chapter Data
+
+use crate::types::RulePredicate;
+
+/// A goal within a chapter: something the player should accomplish.
+#[derive(Clone)]
+pub struct Goal {
+    /// The goal's description, e.g., "Find the sword."
+    pub text: String,
+
+    /// Has the goal been achieved?
+    pub predicate: RulePredicate,
+}
+
+/// A chapter (or act) in the story.  The story moves from one chapter to the next
+/// when the next chapter's entry condition is met.
+#[derive(Clone)]
+pub struct ChapterComponent {
+    /// The chapter's title, for display.
+    pub title: String,
+
+    /// The chapter's entry condition.
+    pub predicate: RulePredicate,
+
+    /// The player's goals for this chapter.
+    pub goals: Vec<Goal>,
+}
+
+impl ChapterComponent {
+    /// Creates a new chapter with the given title.
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.into(),
+            predicate: &|_| true,
+            goals: Vec::new(),
+        }
+    }
+}
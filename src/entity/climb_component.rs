@@ -0,0 +1,28 @@
+//! The climb component.  Climbable things are things the player can climb: a tree, a
+//! ladder, a rope.  Climbing a thing either follows one of the room's links, usually Up
+//! or Down, or fires a Climb event for rules to handle.  Whether a thing can be climbed
+//! at all is indicated by its Climbable flag.
+
+use crate::types::Dir;
+use crate::types::Requirement;
+
+/// Information specific to climbable things.
+#[derive(Debug, Clone, Default)]
+pub struct ClimbComponent {
+    /// The direction climbing the thing takes the player, or None if rules decide
+    /// what happens.
+    pub dir: Option<Dir>,
+
+    /// The capability needed to climb the thing, if any, e.g., a rope.
+    pub requirement: Option<Requirement>,
+}
+
+impl ClimbComponent {
+    /// Creates a new climb component that leads nowhere and requires nothing.
+    pub fn new() -> Self {
+        Self {
+            dir: None,
+            requirement: None,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! The consumable component.  Consumables are things with a limited number of charges,
+//! e.g., a lamp's oil, a box of matches, or the sips in a potion.  The charges left are
+//! stored in the thing's "charges" variable; when they run out, the thing is flagged
+//! Exhausted.
+
+/// Information specific to consumable things.
+#[derive(Debug, Clone, Default)]
+pub struct ConsumableComponent {
+    /// The number of charges at or below which the warning is displayed.
+    pub low: i64,
+
+    /// The warning to display when the charges run low, e.g., "The lamp flickers."
+    pub warning: Option<String>,
+
+    /// The prose added to the thing's description once it's exhausted.
+    pub exhausted: String,
+}
+
+impl ConsumableComponent {
+    /// Creates a new consumable component with no warning and the default
+    /// exhausted prose.
+    pub fn new() -> Self {
+        Self {
+            low: 0,
+            warning: None,
+            exhausted: "It's used up.".into(),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! The deadline component.  A deadline puts the player under time pressure: a turn
+//! limit on the whole game, or a timed threat such as a lit fuse.  While it's running it
+//! counts down, giving warnings along the way; when it runs out, the game is over unless
+//! a rule intervenes.
+
+use crate::types::Time;
+use std::collections::BTreeMap;
+
+/// The definition and state of a deadline.
+#[derive(Debug, Clone)]
+pub struct DeadlineComponent {
+    /// How the player sees the deadline, as a phrase following "3 turns left", e.g.,
+    /// "before the fuse burns down".
+    pub name: String,
+
+    /// The number of turns the deadline allows, once started.
+    pub turns: Time,
+
+    /// The number of turns left, or None if the deadline isn't running.
+    pub left: Option<Time>,
+
+    /// Warn the player every so many turns, with the given text.
+    pub every: Option<(Time, String)>,
+
+    /// Warn the player with the given text when so many turns are left.
+    pub warnings: BTreeMap<Time, String>,
+
+    /// The text shown when the deadline runs out, if any.
+    pub expire: Option<String>,
+}
+
+impl DeadlineComponent {
+    /// Creates a new deadline with the given name, which allows the given number of
+    /// turns once started.
+    pub fn new(name: &str, turns: Time) -> Self {
+        Self {
+            name: name.into(),
+            turns,
+            left: None,
+            every: None,
+            warnings: BTreeMap::new(),
+            expire: None,
+        }
+    }
+}
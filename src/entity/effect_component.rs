@@ -0,0 +1,46 @@
+//! The effect component.  A status effect is a condition the player can be under for a
+//! while, e.g., poisoned, blessed, or invisible.  Scripts apply and remove effects; each
+//! turn, each active effect runs its tick script, if any, and counts down its duration.
+
+use crate::entity::RuleID;
+use crate::types::Stacking;
+use crate::types::Time;
+
+/// The definition of a status effect.
+#[derive(Debug, Clone)]
+pub struct EffectComponent {
+    /// The effect's name as the player sees it, e.g., "poisoned".
+    pub name: String,
+
+    /// What happens when the effect is applied again while it's active.
+    pub stacking: Stacking,
+
+    /// The rule or named script to run each turn while the effect is active, once per
+    /// stack, if any.
+    pub tick: Option<RuleID>,
+
+    /// The text shown when the effect wears off, if any.
+    pub expire: Option<String>,
+}
+
+impl EffectComponent {
+    /// Creates a new effect with the given name, which refreshes when reapplied.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            stacking: Stacking::Refresh,
+            tick: None,
+            expire: None,
+        }
+    }
+}
+
+/// A status effect that is active on a player.
+#[derive(Debug, Clone)]
+pub struct ActiveEffect {
+    /// The number of turns left, or None if the effect lasts until removed.
+    pub turns: Option<Time>,
+
+    /// The number of times the effect has been stacked; see Stacking::Stack.
+    pub stacks: usize,
+}
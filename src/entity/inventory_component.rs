@@ -1,14 +1,16 @@
 //! Inventory
 
 use crate::entity::ID;
+use crate::phys::Bookkeeping;
 use std::collections::BTreeSet;
 
 /// Inventories of Things
 #[derive(Debug, Clone, Default)]
 pub struct InventoryComponent {
     /// A set of things in the inventory.  We use a BTreeSet so that we preserve the order
-    /// in which things were added.
-    pub things: BTreeSet<ID>,
+    /// in which things were added.  Inventories must be kept in sync with locations, so
+    /// only phys::move_thing() and phys::add_location() can change them.
+    things: BTreeSet<ID>,
 }
 
 impl InventoryComponent {
@@ -29,13 +31,15 @@ impl InventoryComponent {
         self.things.contains(&id)
     }
 
-    /// Puts the entity in the inventory list.  Performs no other game logic.
-    pub fn add(&mut self, id: ID) {
+    /// Puts the entity in the inventory list.  Performs no other game logic; use
+    /// phys::move_thing().
+    pub fn add(&mut self, id: ID, _: &Bookkeeping) {
         self.things.insert(id);
     }
 
-    /// Remove an entity from the inventory.
-    pub fn remove(&mut self, id: ID) {
+    /// Remove an entity from the inventory.  Performs no other game logic; use
+    /// phys::move_thing().
+    pub fn remove(&mut self, id: ID, _: &Bookkeeping) {
         self.things.remove(&id);
     }
 
@@ -1,15 +1,19 @@
 //! Entity Location Component
 //! In principle, the location could be part of the Thing component; but location varies
 //! and other ThingComponent attributes do not.
+//!
+//! Locations must be kept in sync with inventories, so only phys::move_thing() and
+//! phys::add_location() can change them.
 
 use crate::entity::ID;
+use crate::phys::Bookkeeping;
 use crate::world::LIMBO;
 
 /// Inventories of Things
 #[derive(Debug, Clone, Default)]
 pub struct LocationComponent {
     /// The location of this entity, for entities that can have a location.
-    pub id: ID,
+    id: ID,
 }
 
 impl LocationComponent {
@@ -17,4 +21,14 @@ impl LocationComponent {
     pub fn new() -> Self {
         Self { id: LIMBO }
     }
+
+    /// The ID of the entity's location.
+    pub fn id(&self) -> ID {
+        self.id
+    }
+
+    /// Sets the entity's location.  Performs no other game logic; use phys::move_thing().
+    pub fn set(&mut self, id: ID, _: &Bookkeeping) {
+        self.id = id;
+    }
 }
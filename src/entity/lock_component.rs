@@ -0,0 +1,28 @@
+//! The lock component.  It stores the keys that can lock and unlock a lockable thing,
+//! e.g., a door, a chest, or a diary, and the combination for a thing with a dial or
+//! keypad.  Whether the thing is currently locked is
+//! indicated by its Locked flag.
+
+use crate::entity::ID;
+use std::collections::BTreeSet;
+
+/// Information specific to lockable things.
+#[derive(Debug, Clone, Default)]
+pub struct LockComponent {
+    /// The things that can lock and unlock this thing.  Things flagged MasterKey work
+    /// as well.
+    pub keys: BTreeSet<ID>,
+
+    /// The combination that unlocks this thing, e.g., for a safe or a keypad.
+    pub combination: Option<String>,
+}
+
+impl LockComponent {
+    /// Creates a new lock component with no keys.
+    pub fn new() -> Self {
+        Self {
+            keys: BTreeSet::new(),
+            combination: None,
+        }
+    }
+}
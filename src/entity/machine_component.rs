@@ -0,0 +1,45 @@
+//! The machine component.  Machines are things that can be switched on and off, and
+//! that fire a Running event each turn while they are on and powered.  Whether a
+//! machine is switched on is indicated by its On flag.
+
+use crate::entity::ID;
+
+/// What a machine needs to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Power {
+    /// The machine needs nothing.
+    None,
+
+    /// Battery(thing): The machine needs the thing in its inventory.
+    Battery(ID),
+
+    /// The machine needs to be in a room flagged Powered.
+    Room,
+}
+
+/// Information specific to machines.
+#[derive(Debug, Clone)]
+pub struct MachineComponent {
+    /// The machine's power requirement
+    pub power: Power,
+
+    /// A line added to the description of the machine's room while it's running,
+    /// e.g., "The generator hums here."
+    pub ambient: Option<String>,
+}
+
+impl MachineComponent {
+    /// Creates a new machine that needs no power.
+    pub fn new() -> Self {
+        Self {
+            power: Power::None,
+            ambient: None,
+        }
+    }
+}
+
+impl Default for MachineComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
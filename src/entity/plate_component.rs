@@ -0,0 +1,24 @@
+//! The plate component.  A pressure plate is a room or container that notices how much
+//! weight is on it: the things in it, and everything they hold, including the player.
+//! When the load rises to the plate's threshold the plate is pressed, and when it falls
+//! below the threshold the plate is released; rules can respond to either.
+
+/// Information specific to pressure plates.
+#[derive(Debug, Clone)]
+pub struct PlateComponent {
+    /// The minimum load that presses the plate.
+    pub threshold: u32,
+
+    /// Is the plate currently pressed?
+    pub pressed: bool,
+}
+
+impl PlateComponent {
+    /// Creates a new, released plate with the given threshold.
+    pub fn new(threshold: u32) -> Self {
+        Self {
+            threshold,
+            pressed: false,
+        }
+    }
+}
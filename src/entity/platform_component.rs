@@ -0,0 +1,40 @@
+//! The platform component.  A platform is a room that moves between stops, e.g., an
+//! elevator or a ferry.  At each stop the platform is linked to the stop's room, and
+//! the room back to the platform; when the platform moves, the links are rebound.
+
+use crate::entity::ID;
+use crate::types::Dir;
+
+/// A place the platform can stop.
+#[derive(Debug, Clone)]
+pub struct Stop {
+    /// The room the platform docks with.
+    pub room: ID,
+
+    /// The direction from the stop's room to the platform.
+    pub entry: Dir,
+}
+
+/// Information specific to platforms.
+#[derive(Debug, Clone)]
+pub struct PlatformComponent {
+    /// The direction from the platform to the room it's docked with.
+    pub exit: Dir,
+
+    /// The platform's stops, in order.
+    pub stops: Vec<Stop>,
+
+    /// The index of the stop the platform is docked with.
+    pub position: usize,
+}
+
+impl PlatformComponent {
+    /// Creates a new platform, with no stops, that's left in the given direction.
+    pub fn new(exit: Dir) -> Self {
+        Self {
+            exit,
+            stops: Vec::new(),
+            position: 0,
+        }
+    }
+}
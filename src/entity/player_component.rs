@@ -1,15 +1,47 @@
 //! Player Data Module
 
+#[cfg(feature = "effects")]
+use crate::entity::effect_component::ActiveEffect;
+#[cfg(feature = "effects")]
+use crate::entity::ID;
+use crate::types::Time;
+#[cfg(feature = "effects")]
+use std::collections::BTreeMap;
+
 /// Information specific to Player Entities
 #[derive(Debug, Clone, Default)]
 pub struct PlayerComponent {
-    // None yet; at present, this component serves only as a marker that the entity
-// is a (the) player.  This will eventually change.
+    /// The notes the player has written with the "note" command, oldest first.  They
+    /// are part of the world state, and are kept along with the rest of it.
+    pub notes: Vec<Note>,
+
+    /// The entries in the player's quest journal, added by the scenario's rules and
+    /// read with "read journal", oldest first.
+    pub journal: Vec<Note>,
+
+    /// The status effects the player is under, by effect ID.
+    #[cfg(feature = "effects")]
+    pub effects: BTreeMap<ID, ActiveEffect>,
 }
 
 impl PlayerComponent {
     /// Create a new PlayerComponent
     pub fn new() -> Self {
-        Self {}
+        Self {
+            notes: Vec::new(),
+            journal: Vec::new(),
+            #[cfg(feature = "effects")]
+            effects: BTreeMap::new(),
+        }
     }
 }
+
+/// A free-text note written by the player, or an entry in the quest journal.
+#[derive(Debug, Clone)]
+pub struct Note {
+    /// The turn on which the note was written.
+    pub turn: Time,
+
+    /// The text of the note.
+    pub text: String,
+}
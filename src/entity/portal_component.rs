@@ -0,0 +1,21 @@
+//! The portal component.  A portal is a thing the player can enter, e.g., a magic
+//! mirror or a shimmering archway, that leads to another portal elsewhere: entering it
+//! takes the player to the room the other portal is in.  Portals are usually paired,
+//! each leading to the other; a one-way portal leads to a portal that doesn't lead back.
+
+use crate::entity::ID;
+
+/// Information specific to portals.
+#[derive(Debug, Clone)]
+pub struct PortalComponent {
+    /// The portal this one leads to, or None if it leads nowhere, i.e., it's the far
+    /// end of a one-way portal.
+    pub dest: Option<ID>,
+}
+
+impl PortalComponent {
+    /// Creates a new portal leading to the given portal, if any.
+    pub fn new(dest: Option<ID>) -> Self {
+        Self { dest }
+    }
+}
@@ -1,12 +1,18 @@
 //! The prose component.  It stores the entity's prose visuals.
+//!
+//! Prose strings are shared and immutable, so that cloning the world, e.g., for undo,
+//! doesn't copy the text.
 
 use crate::entity::ID;
+use crate::rng::Rng;
 use crate::types::EntityProseHook;
 use crate::types::ProseType;
 use crate::types::ProseBuffer;
+use crate::types::Variation;
 use crate::world::World;
 use std::collections::HashMap;
 use std::fmt;
+use std::rc::Rc;
 
 /// A hook to convert an entity into prose.
 /// We define this struct because we can't add traits to EntityStringHook.
@@ -35,26 +41,109 @@ impl fmt::Debug for ProseHook {
     }
 }
 
+/// Alternate prose strings for an entity, one of which is shown on each view.  The
+/// view count and the text last shown live with the world, in World::prose_views, so
+/// that undo restores them.
+#[derive(Clone, Debug)]
+pub struct ProseVariants {
+    texts: Rc<[Rc<str>]>,
+    variation: Variation,
+}
+
+impl ProseVariants {
+    /// Creates the variants.
+    ///
+    /// * Panics if there are no texts.
+    pub fn new(texts: Vec<Rc<str>>, variation: Variation) -> Self {
+        assert!(!texts.is_empty(), "No prose variants");
+
+        Self {
+            texts: texts.into(),
+            variation,
+        }
+    }
+
+    /// Returns the alternate strings.
+    pub fn texts(&self) -> &[Rc<str>] {
+        &self.texts
+    }
+
+    /// Chooses the string for the next view of the entity, given the number of views
+    /// so far and the string shown last.  Returns its index.
+    pub fn choose(&self, views: usize, last: usize, rng: &mut Rng) -> usize {
+        let n = self.texts.len();
+
+        match self.variation {
+            _ if views == 0 || n == 1 => 0,
+            Variation::Cycle => views % n,
+            Variation::Random => (last + 1 + rng.below(n - 1)) % n,
+        }
+    }
+}
+
 /// A Prose value: how to produce a visual string for an entity.
 #[allow(dead_code)]
 #[derive(Clone, Debug)]
 pub enum Prose {
     Default,
-    Prose(String),
+    Prose(Rc<str>),
+    Variants(ProseVariants),
     Hook(ProseHook),
 }
 
 impl Prose {
-    /// Converts the prose to an actual string.
-    pub fn as_string(&self, world: &World, id: ID) -> String {
+    /// Converts the prose to an actual string.  For variants, this is the string chosen
+    /// for the latest view; see visual::room() and visual::thing().
+    pub fn as_string(&self, world: &World, id: ID, prose_type: ProseType) -> String {
         match self {
-            Prose::Default => "You don't see anything special.".to_string(),
+            Prose::Default => "$You $don't see anything special.".to_string(),
             Prose::Prose(str) => str.to_string(),
+            Prose::Variants(variants) => {
+                let (_, i) = world.prose_views.get(&(id, prose_type)).copied().unwrap_or((0, 0));
+                variants.texts[i].to_string()
+            }
             Prose::Hook(hook) => hook.call(world, id),
         }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variants(variation: Variation) -> ProseVariants {
+        let texts: Vec<Rc<str>> = vec!["A".into(), "B".into(), "C".into()];
+        ProseVariants::new(texts, variation)
+    }
+
+    /// Returns the indices chosen for the given number of views.
+    fn views(prose: &ProseVariants, count: usize, rng: &mut Rng) -> Vec<usize> {
+        let mut last = 0;
+        (0..count)
+            .map(|views| {
+                last = prose.choose(views, last, rng);
+                last
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cycle() {
+        let prose = variants(Variation::Cycle);
+        assert_eq!(views(&prose, 4, &mut Rng::new(1)), vec![0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn test_random() {
+        let prose = variants(Variation::Random);
+        let chosen = views(&prose, 10, &mut Rng::new(1));
+
+        assert_eq!(chosen[0], 0);
+        assert!(chosen.windows(2).all(|pair| pair[0] != pair[1]));
+        assert_eq!(views(&prose, 10, &mut Rng::new(1)), chosen);
+    }
+}
+
 /// Information specific to entity prose
 #[derive(Debug, Clone, Default)]
 pub struct ProseComponent {
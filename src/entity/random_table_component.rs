@@ -0,0 +1,34 @@
+//! Random Event Tables
+
+use crate::types::Flag;
+use crate::types::Time;
+
+/// A table of random events.  The table is rolled once per turn; if it comes up, one of
+/// its events is chosen by weight.  The events themselves are rules triggered by
+/// `Event::Random(table)`.
+#[derive(Debug, Clone)]
+pub struct RandomTableComponent {
+    /// The percentage chance that the table comes up on a given turn.
+    pub chance: usize,
+
+    /// If given, the table is rolled only when the player is in a room with this flag.
+    pub region: Option<Flag>,
+
+    /// The number of turns after an event fires before the table is rolled again.
+    pub cooldown: Time,
+
+    /// The time at which one of the table's events last fired.
+    pub last_fired: Option<Time>,
+}
+
+impl RandomTableComponent {
+    /// Creates a new table with the given percentage chance of coming up each turn.
+    pub fn new(chance: usize) -> Self {
+        Self {
+            chance,
+            region: None,
+            cooldown: 0,
+            last_fired: None,
+        }
+    }
+}
@@ -1,7 +1,9 @@
 //! The Room Component
 
+use crate::entity::ID;
 use crate::types::LinkDest;
 use crate::types::Dir;
+use crate::types::Requirement;
 use std::collections::HashMap;
 
 /// Information specific to rooms.
@@ -12,6 +14,19 @@ pub struct RoomComponent {
 
     /// Links from this room to other rooms.
     pub links: HashMap<Dir, LinkDest>,
+
+    /// Capabilities required to follow particular links.
+    pub requirements: HashMap<Dir, Requirement>,
+
+    /// Traps guarding particular links.
+    pub traps: HashMap<Dir, ID>,
+
+    /// Lockable doors guarding particular links.
+    pub doors: HashMap<Dir, ID>,
+
+    /// The number of times the player has entered the room, counting the start of the
+    /// game for the player's starting room.
+    pub visits: usize,
 }
 
 impl RoomComponent {
@@ -20,6 +35,10 @@ impl RoomComponent {
         RoomComponent {
             name: name.into(),
             links: HashMap::new(),
+            requirements: HashMap::new(),
+            traps: HashMap::new(),
+            doors: HashMap::new(),
+            visits: 0,
         }
     }
 }
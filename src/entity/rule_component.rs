@@ -1,8 +1,11 @@
 //! Rule Data
 
+use crate::entity::ID;
 use crate::script::Script;
 use crate::types::Event;
+use crate::types::Flag;
 use crate::types::RulePredicate;
+use crate::types::Time;
 
 /// Game rules: actions taken when a predicate is met
 #[derive(Clone)]
@@ -11,6 +14,32 @@ pub struct RuleComponent {
     pub is_guard: bool,
     pub predicate: RulePredicate,
     pub script: Script,
+
+    /// The rule's relative weight, for rules chosen from random event tables.
+    pub weight: usize,
+
+    /// If given, the rule can only fire during this chapter.
+    pub chapter: Option<ID>,
+
+    /// If given, the rule applies to a class of entities: it matches any event of the
+    /// same kind as its own whose subject has this flag.
+    pub class: Option<Flag>,
+
+    /// The number of turns after the rule fires during which it can't fire again.
+    pub cooldown: Time,
+
+    /// The time at which the rule last fired.
+    pub last_fired: Option<Time>,
+
+    /// If true, the rule fires no more than once per visit to a room; see Once::Visit.
+    pub once_per_visit: bool,
+
+    /// The room the player was in when the rule last fired, and the visit number.
+    pub fired_visit: Option<(ID, usize)>,
+
+    /// For a guard, the room to send the player to instead of denying the event, if
+    /// any; see Outcome::Redirect.
+    pub redirect: Option<ID>,
 }
 
 impl RuleComponent {
@@ -21,6 +50,14 @@ impl RuleComponent {
             is_guard: false,
             predicate: &|_| true,
             script: Script::new(),
+            weight: 1,
+            chapter: None,
+            class: None,
+            cooldown: 0,
+            last_fired: None,
+            once_per_visit: false,
+            fired_visit: None,
+            redirect: None,
         }
     }
 
@@ -28,19 +65,16 @@ impl RuleComponent {
     pub fn newx(event: Event, predicate: RulePredicate) -> RuleComponent {
         RuleComponent {
             event,
-            is_guard: false,
             predicate,
-            script: Script::new(),
+            ..Self::new()
         }
     }
 
     /// Creates a new guard rule, to which actions can be added.
     pub fn guard(event: Event, predicate: RulePredicate) -> RuleComponent {
         RuleComponent {
-            event,
             is_guard: true,
-            predicate,
-            script: Script::new(),
+            ..Self::newx(event, predicate)
         }
     }
 }
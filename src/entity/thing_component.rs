@@ -1,5 +1,20 @@
 //! Thing Data
 
+use crate::entity::ID;
+
+/// How a thing's name takes an article.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Article {
+    /// "a" or "an", chosen by the first letter of the name.
+    Indefinite,
+
+    /// A specific article, e.g., "an" for "hour" or "some" for "water".
+    Custom(String),
+
+    /// No article: the name is a proper noun, e.g., "Excalibur".
+    Proper,
+}
+
 /// Information specific to things.
 #[derive(Debug, Clone)]
 pub struct ThingComponent {
@@ -8,6 +23,33 @@ pub struct ThingComponent {
 
     /// The thing's noun, for use in commands
     pub noun: String,
+
+    /// Additional nouns by which the thing can be referred to in commands.
+    pub aliases: Vec<String>,
+
+    /// Adjectives that set the thing apart from others with the same noun, e.g., "red"
+    /// for one of several keys.  The player can use them with the noun, e.g., "get red
+    /// key", and they're added to the name in listings that need them.
+    pub adjectives: Vec<String>,
+
+    /// The article used with the thing's name.
+    pub article: Article,
+
+    /// The thing's listing priority: things with lower priorities are listed first.
+    /// Things with the same priority are listed by name.
+    pub priority: i32,
+
+    /// The room or NPC the thing belongs to, if any.  Taking it in the owner's
+    /// presence is theft.
+    pub owner: Option<ID>,
+
+    /// The thing's weight, not counting anything it holds; see phys::weight().
+    pub weight: u32,
+
+    /// The thing's plural name and noun, e.g., "gold coins" and "coins", if there can
+    /// be several like it.  Identical things are listed together, e.g., "five gold
+    /// coins", and the player can refer to several at once, e.g., "get two coins".
+    pub plural: Option<(String, String)>,
 }
 
 impl ThingComponent {
@@ -16,6 +58,174 @@ impl ThingComponent {
         ThingComponent {
             name: name.into(),
             noun: noun.into(),
+            aliases: Vec::new(),
+            adjectives: Vec::new(),
+            article: Article::Indefinite,
+            priority: 0,
+            owner: None,
+            weight: 1,
+            plural: None,
+        }
+    }
+
+    /// Returns the name with its indefinite article, e.g., "a note", "some water".
+    pub fn a_name(&self) -> String {
+        self.with_article(&self.name)
+    }
+
+    /// Returns the name with its adjectives and indefinite article, e.g., "a red key";
+    /// adjectives already in the name aren't repeated.
+    pub fn a_full_name(&self) -> String {
+        self.with_article(&self.full_name())
+    }
+
+    /// Returns the name preceded by any adjectives it doesn't already include, e.g.,
+    /// "red key".
+    pub fn full_name(&self) -> String {
+        let mut words: Vec<&str> = self
+            .adjectives
+            .iter()
+            .filter(|adj| !describes(&self.name, adj))
+            .map(|adj| adj.as_str())
+            .collect();
+        words.push(&self.name);
+        words.join(" ")
+    }
+
+    /// Returns the given form of the name with the thing's indefinite article.
+    fn with_article(&self, name: &str) -> String {
+        match &self.article {
+            Article::Indefinite => {
+                if name.starts_with(|c: char| "aeiouAEIOU".contains(c)) {
+                    format!("an {}", name)
+                } else {
+                    format!("a {}", name)
+                }
+            }
+            Article::Custom(article) => format!("{} {}", article, name),
+            Article::Proper => name.into(),
+        }
+    }
+
+    /// Returns the name with the definite article, e.g., "the note".
+    pub fn the_name(&self) -> String {
+        if self.article == Article::Proper {
+            self.name.clone()
+        } else {
+            format!("the {}", self.name)
+        }
+    }
+
+    /// Can the thing be referred to by the given word?  The word can also be a noun
+    /// phrase: the noun preceded by words from the thing's name or its adjectives,
+    /// e.g., "support sword" for the "emotional support sword".
+    pub fn has_noun(&self, word: &str) -> bool {
+        match word.rsplit_once(' ') {
+            Some((words, noun)) => {
+                self.has_noun(noun)
+                    && words.split(' ').all(|w| {
+                        describes(&self.name, w) || self.adjectives.iter().any(|adj| adj == w)
+                    })
+            }
+            None => self.noun == word || self.aliases.iter().any(|alias| alias == word),
+        }
+    }
+
+    /// Can several things like this one be referred to by the given word or noun
+    /// phrase?
+    pub fn has_plural_noun(&self, word: &str) -> bool {
+        match (&self.plural, word.rsplit_once(' ')) {
+            (Some((name, noun)), Some((words, last))) => noun == last && describes(name, words),
+            (Some((_, noun)), None) => noun == word,
+            (None, _) => false,
         }
     }
+
+    /// Is the thing identical to the other, so that the two are listed together?
+    pub fn is_like(&self, other: &ThingComponent) -> bool {
+        self.plural.is_some()
+            && self.plural == other.plural
+            && self.name == other.name
+            && self.article == other.article
+    }
+}
+
+/// Are all of the space-separated words found in the name?
+fn describes(name: &str, words: &str) -> bool {
+    words
+        .split(' ')
+        .all(|word| name.split_whitespace().any(|w| w.eq_ignore_ascii_case(word)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn has_noun() {
+        let mut thingc = ThingComponent::new("stone", "stone");
+        assert!(thingc.has_noun("stone"));
+        assert!(!thingc.has_noun("block"));
+
+        thingc.aliases.push("block".into());
+        assert!(thingc.has_noun("stone"));
+        assert!(thingc.has_noun("block"));
+    }
+
+    #[test]
+    fn has_noun_phrase() {
+        let thingc = ThingComponent::new("emotional support sword", "sword");
+        assert!(thingc.has_noun("support sword"));
+        assert!(thingc.has_noun("emotional support sword"));
+        assert!(!thingc.has_noun("rusty sword"));
+        assert!(!thingc.has_noun("emotional support"));
+
+        let mut key = ThingComponent::new("key", "key");
+        key.adjectives.push("red".into());
+        assert!(key.has_noun("red key"));
+        assert!(!key.has_noun("rusty key"));
+        assert_eq!(key.full_name(), "red key");
+        assert_eq!(key.a_full_name(), "a red key");
+
+        let mut coin = ThingComponent::new("gold coin", "coin");
+        coin.plural = Some(("gold coins".into(), "coins".into()));
+        assert!(coin.has_plural_noun("gold coins"));
+        assert!(!coin.has_plural_noun("silver coins"));
+    }
+
+    #[test]
+    fn is_like() {
+        let mut coin = ThingComponent::new("gold coin", "coin");
+        let mut other = coin.clone();
+        assert!(!coin.is_like(&other));
+
+        coin.plural = Some(("gold coins".into(), "coins".into()));
+        other.plural = coin.plural.clone();
+        assert!(coin.is_like(&other));
+        assert!(coin.has_plural_noun("coins"));
+        assert!(!coin.has_plural_noun("coin"));
+
+        other.name = "silver coin".into();
+        assert!(!coin.is_like(&other));
+    }
+
+    #[test]
+    fn a_name() {
+        let mut thingc = ThingComponent::new("note", "note");
+        assert_eq!(thingc.a_name(), "a note");
+        assert_eq!(thingc.the_name(), "the note");
+
+        thingc.name = "apple".into();
+        assert_eq!(thingc.a_name(), "an apple");
+
+        thingc.name = "water".into();
+        thingc.article = Article::Custom("some".into());
+        assert_eq!(thingc.a_name(), "some water");
+        assert_eq!(thingc.the_name(), "the water");
+
+        thingc.name = "Excalibur".into();
+        thingc.article = Article::Proper;
+        assert_eq!(thingc.a_name(), "Excalibur");
+        assert_eq!(thingc.the_name(), "Excalibur");
+    }
 }
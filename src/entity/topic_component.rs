@@ -0,0 +1,29 @@
+//! The topic component.  It stores the topics the player can ask an entity about.
+
+use crate::types::RulePredicate;
+
+/// A conversation topic.
+#[derive(Clone)]
+pub struct Topic {
+    /// The topic's name, as the player would type it.
+    pub name: String,
+
+    /// Is the topic currently askable?  E.g., has the player learned about it yet?
+    pub predicate: RulePredicate,
+
+    /// The entity's response when asked about the topic.
+    pub prose: String,
+}
+
+/// The topics the player can ask an entity about.
+#[derive(Clone, Default)]
+pub struct TopicComponent {
+    pub topics: Vec<Topic>,
+}
+
+impl TopicComponent {
+    /// Creates a new, empty topic component.
+    pub fn new() -> Self {
+        Self { topics: Vec::new() }
+    }
+}
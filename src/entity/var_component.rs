@@ -0,0 +1,31 @@
+//! The variable component.  It stores named integer variables on an entity, e.g., the
+//! charges left in a lamp or the number of times a bell has been rung.  A variable that
+//! has never been set has the value 0.
+
+use std::collections::BTreeMap;
+
+/// An entity's variables.
+#[derive(Debug, Clone, Default)]
+pub struct VarComponent {
+    /// The variable values, by name.
+    pub vars: BTreeMap<String, i64>,
+}
+
+impl VarComponent {
+    /// Creates a new, empty variable component.
+    pub fn new() -> Self {
+        Self {
+            vars: BTreeMap::new(),
+        }
+    }
+
+    /// Gets the variable's value, or 0 if it has never been set.
+    pub fn get(&self, name: &str) -> i64 {
+        self.vars.get(name).cloned().unwrap_or(0)
+    }
+
+    /// Sets the variable's value.
+    pub fn set(&mut self, name: &str, value: i64) {
+        self.vars.insert(name.into(), value);
+    }
+}
@@ -0,0 +1,22 @@
+//! The verb component.  It stores the verb handlers attached to a particular thing, so
+//! that object-specific behavior lives with the object.
+
+use crate::types::CommandHook;
+use std::collections::HashMap;
+
+/// The verbs a thing handles itself, e.g., "wash" for a basin.  The handlers are tried
+/// before the scenario's generic command handlers when the player uses the verb on the
+/// thing.
+#[derive(Clone, Default)]
+pub struct VerbComponent {
+    pub handlers: HashMap<String, CommandHook>,
+}
+
+impl VerbComponent {
+    /// Creates a new, empty verb component.
+    pub fn new() -> Self {
+        Self {
+            handlers: HashMap::new(),
+        }
+    }
+}
@@ -0,0 +1,253 @@
+//! Help
+//!
+//! The player gets general help with "help", help on a command or topic with "help
+//! <topic>", e.g., "help get" or "help containers", and a list of the topics that
+//! mention a word with "help search <word>".  The framework provides a page for each
+//! built-in command; the scenario can add pages for its own commands and topics.  See
+//! WorldBuilder::help_command() and WorldBuilder::help_topic().
+
+use crate::types::list_phrase;
+use crate::world::World;
+
+/// A page of help on a command or topic.
+#[derive(Clone, Debug)]
+pub struct HelpPage {
+    /// For commands, the forms the command takes, e.g., "get <thing>".
+    pub usage: Option<String>,
+
+    /// The help text.
+    pub text: String,
+}
+
+/// The built-in commands: the command's verb, its usage, and its help text.
+const COMMANDS: &[(&str, &str, &str)] = &[
+    (
+        "go",
+        "go <direction>, or just <direction>: north, south, east, west, up, down, in, out; \
+         go to <place>",
+        "Moves $you in the given direction, if there's a way to go, or toward a place \
+         $you $have been before.",
+    ),
+    ("look", "look", "Describes $your surroundings."),
+    ("inventory", "inventory", "Lists what $you $are carrying."),
+    (
+        "examine",
+        "examine <thing>, examine all",
+        "Describes the thing in detail, or everything in sight.",
+    ),
+    ("search", "search, search room", "Searches the room for anything hidden."),
+    (
+        "feel",
+        "feel around, feel <thing>",
+        "Finds out what's within reach, or what a thing is, by touch; handy in the dark.",
+    ),
+    ("read", "read <thing>", "Reads the writing on the thing, if there is any."),
+    (
+        "get",
+        "get <thing>, pick up <thing>, get <thing> from <container>",
+        "Picks up the thing, from wherever it is.",
+    ),
+    ("drop", "drop <thing>", "Puts down something $you $are carrying."),
+    ("put", "put <thing> in <container>", "Puts the thing into a container."),
+    (
+        "lock",
+        "lock <thing>, lock <thing> with <key>",
+        "Locks the thing, if $you $have the key.",
+    ),
+    (
+        "unlock",
+        "unlock <thing>, unlock <thing> with <key>",
+        "Unlocks the thing, if $you $have the key.",
+    ),
+    ("disarm", "disarm <thing> with <tool>", "Disarms a trap, given the right tool."),
+    (
+        "switch",
+        "turn on <thing>, turn off <thing>, switch on <thing>, switch off <thing>",
+        "Turns a device on or off.",
+    ),
+    (
+        "combine",
+        "combine <thing> with <thing>, attach <thing> to <thing>, mix <thing> and <thing>",
+        "Makes something new from two things, if they go together.",
+    ),
+    (
+        "enter",
+        "enter <thing>, go through <thing>, dial <thing> to <code>, turn <thing> to <code>, \
+         set <thing> to <code>, enter <code> on <thing>",
+        "Goes through a doorway of some kind, e.g., a magic mirror; or enters a code on a \
+         dial or keypad.  Numbers can be written as digits or words, e.g., \"42\" or \
+         \"forty-two\".",
+    ),
+    (
+        "push",
+        "push <thing>, pull <thing>, turn <thing>",
+        "Handles the thing in the given way, to see what happens.",
+    ),
+    ("touch", "touch <thing>", "Touches the thing, to see what it's like or what happens."),
+    ("shake", "shake <thing>", "Shakes the thing, to see what happens."),
+    ("open", "open <thing>, close <thing>", "Opens or closes the thing, if it opens."),
+    ("eat", "eat <thing>, drink <thing>", "Eats or drinks the thing, if it's edible."),
+    ("smell", "smell <thing>", "Smells the thing."),
+    (
+        "climb",
+        "climb <thing>, climb up <thing>, climb down <thing>",
+        "Climbs the thing, e.g., a tree or a ladder.",
+    ),
+    ("swim", "swim <direction>", "Swims in the given direction, where there's water."),
+    ("wash", "wash <thing>", "Washes the thing, where there's water."),
+    ("talk", "talk to <person>", "Starts a conversation with the person."),
+    (
+        "ask",
+        "ask <person> about <topic>, ask about <topic>",
+        "Asks about the topic, during a conversation.",
+    ),
+    ("topics", "topics", "Lists the topics $you can ask about, during a conversation."),
+    ("use", "use <thing>", "Uses the thing, if it has an obvious use."),
+    ("chapter", "chapter", "Shows the current chapter of the story."),
+    ("status", "status, status on, status off", "Shows $your status, or the status line."),
+    ("time", "time", "Shows the turn, and how long $you $have left, if time is short."),
+    ("records", "records", "Shows $your best runs through the game."),
+    ("note", "note <text>", "Writes a note in $your journal."),
+    ("journal", "journal", "Shows $your journal."),
+    ("erase", "erase note <number>", "Erases the numbered note from $your journal."),
+    (
+        "accessible",
+        "accessible on, accessible off",
+        "Tailors the output for screen readers.",
+    ),
+    ("undo", "undo", "Takes back the last command."),
+    ("restart", "restart", "Starts the game over from the beginning."),
+    ("restore", "restore auto", "Restores the most recent autosave."),
+    ("quit", "quit", "Ends the game."),
+    (
+        "help",
+        "help, help <topic>, help search <word>",
+        "Gets help on a command or topic, or finds the topics that mention a word.",
+    ),
+];
+
+/// The built-in topics: the topic and its help text.
+const TOPICS: &[(&str, &str)] = &[(
+    "containers",
+    "Some things can hold other things: boxes, bags, and the like.  Use \"put <thing> in \
+     <container>\" to fill them, and \"get <thing> from <container>\" to empty them.",
+)];
+
+/// Adds the pages for the built-in commands and topics to the world.
+pub fn add_builtin_pages(world: &mut World) {
+    for (verb, usage, text) in COMMANDS.iter().filter(|(verb, _, _)| is_enabled(verb)) {
+        world.help.insert(
+            verb.to_string(),
+            HelpPage {
+                usage: Some(usage.to_string()),
+                text: text.to_string(),
+            },
+        );
+    }
+
+    for (topic, text) in TOPICS {
+        world.help.insert(
+            topic.to_string(),
+            HelpPage {
+                usage: None,
+                text: text.to_string(),
+            },
+        );
+    }
+}
+
+/// Returns false if the command belongs to a subsystem that's compiled out.
+fn is_enabled(verb: &str) -> bool {
+    match verb {
+        "switch" => cfg!(feature = "machines"),
+        "swim" => cfg!(feature = "water"),
+        "time" => cfg!(feature = "deadlines"),
+        _ => true,
+    }
+}
+
+/// Returns the text of the help page on the topic, if there is one.  A command's page
+/// can be found by any of the words in its usage, e.g., "pull" finds the page for
+/// "push".
+pub fn page(world: &World, topic: &str) -> Option<String> {
+    let (topic, page) = world.help.get_key_value(topic).or_else(|| {
+        world
+            .help
+            .iter()
+            .find(|(_, page)| page.usage.as_ref().map_or(false, |u| uses(u, topic)))
+    })?;
+
+    let mut text = String::new();
+
+    if let Some(usage) = &page.usage {
+        text.push_str(&format!("Usage: {}|", usage));
+
+        let mut synonyms: Vec<&str> = world
+            .synonyms
+            .iter()
+            .filter(|(syn, canon)| *canon == topic && *syn != topic)
+            .map(|(syn, _)| syn.as_str())
+            .collect();
+        synonyms.sort();
+
+        if !synonyms.is_empty() {
+            text.push_str(&format!("Also: {}.|", list_phrase(&synonyms, "or")));
+        }
+    }
+
+    text.push_str(&page.text);
+    Some(text)
+}
+
+/// Returns the topics whose pages mention the word, in alphabetical order.
+pub fn search<'a>(world: &'a World, word: &str) -> Vec<&'a str> {
+    let word = word.to_lowercase();
+
+    world
+        .help
+        .iter()
+        .filter(|(topic, page)| {
+            topic.contains(&word)
+                || page.usage.as_ref().map_or(false, |u| u.to_lowercase().contains(&word))
+                || page.text.to_lowercase().contains(&word)
+        })
+        .map(|(topic, _)| topic.as_str())
+        .collect()
+}
+
+/// Returns all of the help topics, in alphabetical order.
+pub fn topics(world: &World) -> Vec<&str> {
+    world.help.keys().map(|topic| topic.as_str()).collect()
+}
+
+/// Does the usage include the word?
+fn uses(usage: &str, word: &str) -> bool {
+    usage
+        .split(|c: char| !c.is_alphanumeric() && c != '<' && c != '>')
+        .any(|w| w == word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page() {
+        let world = World::new();
+
+        let get = page(&world, "get").unwrap();
+        assert!(get.starts_with("Usage: get <thing>"));
+        assert!(get.contains("Also: take."));
+
+        assert!(page(&world, "pull").unwrap().contains("push <thing>"));
+        assert!(page(&world, "containers").unwrap().starts_with("Some things"));
+        assert!(page(&world, "thing").is_none());
+        assert!(page(&world, "xyzzy").is_none());
+    }
+
+    #[test]
+    fn test_search() {
+        assert_eq!(search(&World::new(), "keypad"), vec!["enter"]);
+        assert!(search(&World::new(), "xyzzy").is_empty());
+    }
+}
@@ -0,0 +1,40 @@
+//! Implicit Actions
+//!
+//! Some commands have a precondition that the player could trivially satisfy: to read a
+//! note, the player must be holding it.  Rather than failing, such a command can perform
+//! the prerequisite action implicitly, announcing it in parentheses, e.g., "(first taking
+//! the note)", and then carry on.  The prerequisite is performed in the usual way, so
+//! traps and guards still apply; if it doesn't succeed, the command goes no further.
+//!
+//! Reading is governed by the scenario's read policy; other implicit actions, e.g.,
+//! taking a thing before putting it somewhere, or unlocking a door with a carried key
+//! before going through it, are performed only if the scenario enables them.
+
+use crate::entity::PlayerID;
+use crate::entity::ThingID;
+use crate::lock;
+use crate::phys;
+use crate::visual;
+use crate::world::World;
+
+/// The player takes the thing before doing something else with it.  Returns true if the
+/// player is now holding it.
+pub fn take(world: &mut World, pid: PlayerID, thing: ThingID) -> bool {
+    announce(&format!("first taking {}", visual::the_name(world, thing.id())));
+    phys::get_thing_quietly(world, pid, thing)
+}
+
+/// The player unlocks the thing with the key before going on, e.g., through a locked
+/// door.  The caller is responsible for verifying that the key fits.  Returns true if
+/// the thing is now unlocked.
+pub fn unlock(world: &mut World, pid: PlayerID, thing: ThingID, key: ThingID) -> bool {
+    let action = format!("first unlocking {} with {}",
+        visual::the_name(world, thing.id()), visual::the_name(world, key.id()));
+    announce(&action);
+    lock::unlock_quietly(world, pid, thing)
+}
+
+/// Announces an implicit action.
+fn announce(action: &str) {
+    visual::act(&format!("({})", action));
+}
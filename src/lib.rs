@@ -2,35 +2,98 @@
 /// Bonaventure is a simple text adventure framework.  At present, it is used to
 /// implement a single game; see src/scenario.rs.  Eventually it might support
 /// multiple games.
+#[cfg(any(test, feature = "bench"))]
+pub mod bench;
+mod autosave;
 mod command;
 mod conmark;
+mod consumable;
 #[macro_use]
 mod console;
+mod craft;
+mod crash;
+mod dark;
+#[cfg(feature = "deadlines")]
+mod deadline;
 mod debug;
+#[cfg(feature = "effects")]
+mod effect;
 mod entity;
+mod help;
+mod implicit;
+mod lock;
+#[cfg(feature = "machines")]
+mod machine;
 mod phys;
+mod platform;
+mod portal;
 mod player_control;
+mod records;
+mod rng;
 mod rule;
 mod scenario;
 mod script;
+mod story;
+#[cfg(test)]
+mod testing;
 mod types;
 mod visual;
+mod walkthrough;
+mod wash;
+#[cfg(feature = "water")]
+mod water;
 mod world;
 #[allow(dead_code)] // Games won't use all features.
 mod world_builder;
 
+pub use crate::console::Input;
 use crate::types::Event;
+use crate::types::Flag;
+use crate::types::TurnSystem;
+use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
 use crate::world::*;
 
+/// A function that handles ^C or ^D at the prompt in place of the default behavior;
+/// see Game::set_interrupt_hook().  It receives Input::Interrupted or Input::Eof.
+pub type InterruptHook = fn(&mut Game, &Input);
+
 /// The main game object.  It owns the world as it currently is, and supports restart
 /// and undo, etc.
 /// TODO: Possibly, this should live elsewhere.
 pub struct Game {
+    // The scenario's world-building function, used on restart
+    build: fn() -> World,
+
     // THe current world
     world: World,
 
     // Undo information
     undo_info: Option<World>,
+
+    // The commands the player has successfully entered since the game began,
+    // for recording walkthroughs.
+    history: Vec<String>,
+
+    // The error message from the last turn, if the command failed.
+    error: Option<String>,
+
+    // The last error message given, and the number of times in a row the player has
+    // gotten it.
+    repeated_error: Option<(String, usize)>,
+
+    // The length of the history when the game was last saved.
+    saved: usize,
+
+    // The command awaiting the player's confirmation, if any.
+    confirming: Option<player_control::Confirmation>,
+
+    // Has the player quit?
+    over: bool,
+
+    // The embedder's handler for ^C and ^D, if any.
+    interrupt_hook: Option<InterruptHook>,
 }
 
 impl Default for Game {
@@ -42,43 +105,367 @@ impl Default for Game {
 impl Game {
     /// Create the game object
     pub fn new() -> Game {
+        Game::with_scenario(scenario::build)
+    }
+
+    /// Create the game object for the scenario built by the given function.
+    pub fn with_scenario(build: fn() -> World) -> Game {
         Game {
-            world: scenario::build(),
+            build,
+            world: build(),
             undo_info: None,
+            history: Vec::new(),
+            error: None,
+            repeated_error: None,
+            saved: 0,
+            confirming: None,
+            over: false,
+            interrupt_hook: None,
         }
     }
 
     /// Introduce the game: print a welcome message, and visualize the initial location
     pub fn introduce(&mut self) {
         println!("Welcome to Bonaventure!\n");
+        self.begin();
+    }
 
+    /// Begins the game in the initial location.
+    fn begin(&mut self) {
         // The first turn is always an implicit "look at the current setting".
         // This will also give everything else a chance to move.
         self.turn("look");
+
+        // The implicit look isn't part of the player's history, or of the metrics.
+        self.history.clear();
+        self.saved = 0;
+        self.world.metrics.turns = 0;
+    }
+
+    /// Executes one game turn, returning the command's error message, if any.  This is
+    /// for automated drivers, e.g., walkthroughs and fuzzers.
+    pub fn step(&mut self, cmd: &str) -> Result<(), String> {
+        self.turn(cmd);
+
+        match &self.error {
+            Some(msg) => Err(msg.clone()),
+            None => Ok(()),
+        }
     }
 
     /// Execute one game turn.
     pub fn turn(&mut self, cmd: &str) {
-        // FIRST, let the player do what he does.
-        player_control::system(self, &cmd);
+        // FIRST, resume normal output, in case a cutscene suppressed it last turn.
+        console::suppress(false);
+        console::set_narration(self.world.narration);
+        visual::set_output_hook(self.world.output_hook);
+        self.error = None;
+        let was_dead = self.world.has_flag(self.world.pid, Flag::Dead);
+        let deaths = self.world.metrics.deaths;
+        self.world.log(&format!("Command: {}", cmd.trim()));
+
+        // NEXT, let the player do what he does.
+        let elapsed = player_control::system(self, &cmd);
+        self.fire_change_events();
+        self.check_invariants("player control");
 
-        // NEXT, handle rules
+        if let Some(msg) = &self.error {
+            let text = format!("Error: {}", msg);
+            self.world.log(&text);
+        }
+
+        // NEXT, if the command took no time, nothing else happens.
+        if elapsed {
+            self.run_systems();
+
+            // Increment the clock
+            self.world.clock += 1;
+            self.world.metrics.turns += 1;
+
+            // Save the session, if it's time.
+            match autosave::autosave(self, self.world.metrics.turns) {
+                Ok(true) => self.saved = self.history.len(),
+                Ok(false) => (),
+                Err(msg) => visual::error(&msg),
+            }
+        }
+
+        // NEXT, walk the rest of any "go to" route, a turn per step, until the player
+        // arrives, dies, or is asked a question.
+        while self.is_walking() && player_control::walk(&mut self.world) {
+            self.fire_change_events();
+            self.check_invariants("player control");
+            self.run_systems();
+            self.world.clock += 1;
+            self.world.metrics.turns += 1;
+            self.track_metrics(was_dead, deaths);
+        }
+        self.world.walking.clear();
+
+        // NEXT, skip any turns a script has elapsed.
+        if self.world.elapsing > 0 {
+            self.fast_forward();
+        }
+
+        // NEXT, pose any question a script has asked, even after a cutscene.
+        if let Some(prompt) = self.world.prompt.as_mut().filter(|p| !p.posed) {
+            prompt.posed = true;
+            console::suppress(false);
+            visual::info(&prompt.text());
+        }
+
+        // NEXT, update the session metrics.
+        self.track_metrics(was_dead, deaths);
+
+        // NEXT, display the turn's output.
+        console::flush();
+    }
+
+    /// Runs the rules and the turn systems at the end of a turn in which time passes.
+    fn run_systems(&mut self) {
+        // Handle rules
         rule::fire_event(&mut self.world, &Event::Turn);
+        if self.runs(TurnSystem::RandomEvents) {
+            rule::roll_random_tables(&mut self.world);
+        }
+        self.fire_change_events();
+        self.check_invariants("rule");
+
+        // Run the machines
+        #[cfg(feature = "machines")]
+        if self.runs(TurnSystem::Machines) {
+            machine::system(&mut self.world);
+            self.check_invariants("machine");
+        }
 
-        // NEXT, Increment the clock
-        // TODO: Probably don't want to do this here.  Some commands should
-        // take time, and some shouldn't.  This should probably be in the
-        // player_control system.
-        self.world.clock += 1;
+        // Keep the player afloat, or not
+        #[cfg(feature = "water")]
+        if self.runs(TurnSystem::Water) {
+            water::system(&mut self.world);
+            self.check_invariants("water");
+        }
+
+        // Tick the player's status effects
+        #[cfg(feature = "effects")]
+        if self.runs(TurnSystem::Effects) {
+            effect::system(&mut self.world);
+            self.check_invariants("effect");
+        }
+
+        // Count down the deadlines
+        #[cfg(feature = "deadlines")]
+        if self.runs(TurnSystem::Deadlines) {
+            deadline::system(&mut self.world);
+            self.check_invariants("deadline");
+        }
+
+        // React to anything the systems and rules have given or taken away.
+        self.fire_change_events();
+
+        // Move the story along
+        if self.runs(TurnSystem::Story) {
+            story::system(&mut self.world);
+            self.check_invariants("story");
+        }
+    }
+
+    /// Fires the events for the inventories, and the loads on pressure plates, that
+    /// have changed.
+    fn fire_change_events(&mut self) {
+        rule::fire_inventory_events(&mut self.world);
+        if self.runs(TurnSystem::Plates) {
+            rule::fire_plate_events(&mut self.world);
+        }
+    }
+
+    /// Fast-forwards over the turns skipped by a script's elapse action.  Unless the
+    /// scenario says otherwise, the rules and turn systems run for each skipped turn,
+    /// but their output is discarded; the script has already described the passage of
+    /// time.  Skipped turns don't count as the player's turns.
+    fn fast_forward(&mut self) {
+        let turns = self.world.elapsing;
+        self.world.log(&format!("Elapse: {} turns", turns));
+
+        if self.world.fast_forward {
+            let quiet = console::is_quiet();
+            console::set_quiet(true);
+
+            for _ in 0..turns {
+                self.run_systems();
+                self.world.clock += 1;
+            }
+
+            console::set_quiet(quiet);
+        } else {
+            self.world.clock += turns;
+        }
+
+        // Elapse actions taken while fast-forwarding are ignored.
+        self.world.elapsing = 0;
+    }
+
+    /// Can the player take the next step of a "go to" route?  Not if there's none, or
+    /// if the player is dead, has a question to answer, or is in a cutscene.
+    fn is_walking(&self) -> bool {
+        !self.world.walking.is_empty()
+            && !self.world.has_flag(self.world.pid, Flag::Dead)
+            && self.world.prompt.is_none()
+            && self.world.elapsing == 0
+    }
+
+    /// Is the turn system registered for this scenario?
+    fn runs(&self, system: TurnSystem) -> bool {
+        self.world.systems.contains(&system)
+    }
+
+    /// Updates the session metrics at the end of a turn: the rooms visited, the things
+    /// handled, and whether the player has died this turn.  Deaths by phys::kill() are
+    /// already counted, even if the player has been revived since.
+    fn track_metrics(&mut self, was_dead: bool, deaths: usize) {
+        let world = &mut self.world;
+        let pid = world.pid;
+        let here = phys::loc(world, pid);
+
+        if world.is_room(here) {
+            world.metrics.rooms.insert(here);
+        }
+
+        let things = phys::droppable(world, pid);
+        world.metrics.things.extend(things);
+
+        if !was_dead && world.has_flag(pid, Flag::Dead) && world.metrics.deaths == deaths {
+            world.metrics.deaths += 1;
+        }
+    }
+
+    /// Checks the world's invariants after the named system has run; see
+    /// debug::check_invariants().  Does nothing in release builds.
+    fn check_invariants(&self, system: &str) {
+        if cfg!(debug_assertions) {
+            debug::check_invariants(&self.world, system);
+        }
     }
 
     /// Restart the game: recreate the initial scenario.
     pub fn restart(&mut self) {
-        self.world = scenario::build();
+        self.world = (self.build)();
         self.undo_info = None;
         self.introduce();
     }
 
+    /// Has the player made progress since the game was last saved?
+    pub fn has_unsaved_progress(&self) -> bool {
+        self.history.len() > self.saved
+    }
+
+    /// The player wants to quit: quits at once if everything is saved, and otherwise
+    /// asks the player to confirm.
+    pub fn request_quit(&mut self) {
+        if self.has_unsaved_progress() {
+            player_control::confirm(self, "quit", player_control::QUIT_QUESTION);
+        } else {
+            self.quit();
+        }
+    }
+
+    /// Quits the game, summarizing the session.  The game is over; see is_over().
+    pub fn quit(&mut self) {
+        visual::metrics(&self.world);
+        visual::act("Bye, then.");
+        self.over = true;
+    }
+
+    /// Has the player quit?
+    pub fn is_over(&self) -> bool {
+        self.over
+    }
+
+    /// Sets the function that handles ^C and ^D at the prompt, replacing the default
+    /// behavior; see handle_interrupt().
+    pub fn set_interrupt_hook(&mut self, hook: Option<InterruptHook>) {
+        self.interrupt_hook = hook;
+    }
+
+    /// Responds to ^C or ^D at the prompt, using the interrupt hook if there is one.
+    pub fn interrupt(&mut self, input: &Input) {
+        match self.interrupt_hook {
+            Some(hook) => hook(self, input),
+            None => self.handle_interrupt(input),
+        }
+
+        console::flush();
+    }
+
+    /// The default response to ^C or ^D: saves the session, if autosaves are being
+    /// written, and then quits.  ^C asks for confirmation if there's still unsaved
+    /// progress, and a second ^C quits regardless; ^D quits at once, as there's no more
+    /// input to confirm with.
+    pub fn handle_interrupt(&mut self, input: &Input) {
+        let quitting = self
+            .confirming
+            .as_ref()
+            .map_or(false, |c| c.question == player_control::QUIT_QUESTION);
+
+        if *input == Input::Interrupted && quitting {
+            self.confirming = None;
+            self.quit();
+            return;
+        }
+
+        if self.has_unsaved_progress() {
+            match autosave::save_now(self) {
+                Ok(true) => self.saved = self.history.len(),
+                Ok(false) => (),
+                Err(msg) => visual::error(&msg),
+            }
+        }
+
+        match input {
+            Input::Interrupted => self.request_quit(),
+            _ => self.quit(),
+        }
+    }
+
+    /// Restores the most recent autosave: replays its commands against a fresh copy of
+    /// the scenario, discarding their output, and then describes the player's
+    /// surroundings.
+    pub fn restore_autosave(&mut self) {
+        let commands = match autosave::load_latest() {
+            Ok(commands) => commands,
+            Err(msg) => {
+                visual::error(&msg);
+                return;
+            }
+        };
+
+        // FIRST, replay the commands quietly, without autosaving or keeping records; a
+        // win replayed is not a new run.
+        let prefix = autosave::prefix();
+        let records = records::path();
+        let quiet = console::is_quiet();
+        autosave::set_prefix(None);
+        records::set_path(None);
+        console::set_quiet(true);
+
+        self.world = (self.build)();
+        self.undo_info = None;
+        self.begin();
+
+        for cmd in &commands {
+            self.turn(cmd);
+        }
+
+        console::set_quiet(quiet);
+        autosave::set_prefix(prefix.as_ref().map(|p| p.as_str()));
+        records::set_path(records.as_ref().map(|p| p.as_str()));
+        self.saved = self.history.len();
+
+        // NEXT, show the player where they are.
+        visual::act("Restored.");
+        let here = phys::loc(&self.world, self.world.pid);
+        visual::room(&mut self.world, here);
+    }
+
     /// Saves the world state for later undo.
     pub fn save_for_undo(&mut self, undo_info: World) {
         // At present, we save only one turn.
@@ -92,13 +479,73 @@ impl Game {
 
     pub fn undo(&mut self) {
         assert!(self.has_undo(), "Cannot undo; no undo info");
+
+        // The metrics and the log belong to the session, and survive the undo.
+        let mut metrics = self.world.metrics.clone();
+        metrics.undos += 1;
+        let log = self.world.log.clone();
+
         self.world = self.undo_info.take().unwrap();
+        self.world.metrics = metrics;
+        self.world.log = log;
+        self.world.log("Undo");
+        self.history.pop();
+    }
+
+    /// Replays the walkthrough in the given file against a new game, and verifies
+    /// that the player wins.  See the walkthrough module.
+    pub fn verify_walkthrough(path: &str) -> Result<(), String> {
+        walkthrough::verify(path)
     }
 }
 
-/// Runs the program.
+/// Runs the program.  Options:
+///
+/// * "--accessible": Output is tailored for screen readers.
+/// * "--commands <file>": Commands are read from the file rather than the console.
+/// * "--record <file>": The player's successful commands are saved to the file as a
+///   walkthrough.
+/// * "--report": A summary of the scenario's world is printed; see
+///   WorldBuilder::report().
+/// * "--verify <file>": The walkthrough in the file is replayed and checked.
 pub fn run() {
-    // FIRST, create the game world.
+    // FIRST, handle options.
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.iter().any(|arg| arg == "--accessible") {
+        console::set_accessible(true);
+    }
+
+    if let Some(path) = option_value(&args, "--commands") {
+        let file = File::open(path).unwrap_or_else(|err| panic!("Cannot open {}: {}", path, err));
+        run_from(BufReader::new(file));
+        return;
+    }
+
+    if args.iter().any(|arg| arg == "--report") {
+        print!("{}", scenario::builder().report());
+        return;
+    }
+
+    if let Some(path) = option_value(&args, "--verify") {
+        match Game::verify_walkthrough(path) {
+            Ok(()) => println!("Walkthrough verified: {}", path),
+            Err(msg) => {
+                println!("Walkthrough failed: {}", msg);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let record = option_value(&args, "--record");
+
+    // NEXT, keep records of the player's best runs and autosave.  Crashes during a turn
+    // are reported by crash::turn().
+    records::set_path(Some(records::RECORDS_FILE));
+    autosave::set_prefix(Some(autosave::AUTOSAVE_PREFIX));
+
+    // NEXT, create the game world.
     let mut game = Game::new();
     game.introduce();
 
@@ -106,6 +553,74 @@ pub fn run() {
     let mut con = console::Console::new();
 
     loop {
-        game.turn(&con.readline("> "));
+        if game.world.status_line && !console::is_accessible() {
+            console::line(&visual::status_line(&game.world));
+            console::flush();
+        }
+
+        match con.readline("> ") {
+            Input::Command(cmd) => crash::turn(&mut game, &cmd),
+            input => game.interrupt(&input),
+        }
+
+        if game.is_over() {
+            break;
+        }
+
+        if let Some(path) = record {
+            if let Err(err) = walkthrough::save(&game.history, path) {
+                println!("Cannot record walkthrough to {}: {}", path, err);
+            }
+        }
+    }
+}
+
+/// Returns the value of the named command-line option, if it's present.
+fn option_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    let i = args.iter().position(|arg| arg == name)?;
+    let value = args.get(i + 1).unwrap_or_else(|| panic!("Missing file name for {}", name));
+    Some(value)
+}
+
+/// Runs the game using commands read from the reader, e.g., a walkthrough file, until
+/// the input is exhausted.  No prompts are displayed and output isn't paginated.
+pub fn run_from<R: BufRead>(mut reader: R) {
+    console::set_interactive(false);
+
+    let mut game = Game::new();
+    game.introduce();
+
+    while let Some(cmd) = console::read_command(&mut reader) {
+        game.turn(&cmd);
+
+        if game.is_over() {
+            break;
+        }
+    }
+}
+
+#[cfg(all(test, feature = "deadlines"))]
+mod tests {
+    use super::*;
+    use crate::entity::deadline_component::DeadlineComponent;
+
+    #[test]
+    fn test_unregistered_system() {
+        console::set_interactive(false);
+
+        let mut game = Game::new();
+        game.introduce();
+        let fuse = game.world.alloc("fuse");
+        game.world.deadlines.insert(fuse, DeadlineComponent::new("for the fuse", 5));
+        deadline::start(&mut game.world, fuse);
+
+        // The scenario has no deadlines, so the Deadlines system isn't registered.
+        assert!(!game.world.systems.contains(&TurnSystem::Deadlines));
+        game.step("look").unwrap();
+        assert_eq!(deadline::turns_left(&game.world, fuse), Some(5));
+
+        game.world.systems.insert(TurnSystem::Deadlines);
+        game.step("look").unwrap();
+        assert_eq!(deadline::turns_left(&game.world, fuse), Some(4));
     }
 }
@@ -0,0 +1,117 @@
+//! The Locking Module
+//!
+//! Lockable things (doors, chests, diaries) have a LockComponent listing the keys that
+//! fit them, and are locked while flagged Locked.  A thing flagged MasterKey fits every
+//! lock.  A room's link can be guarded by a lockable door, and a locked thing can't be
+//! read.  Scripts can lock and unlock things directly, without a key.
+//!
+//! A lockable thing can also have a combination, entered with "dial" or "enter"; the
+//! right code unlocks it.
+
+use crate::entity::ID;
+use crate::entity::PlayerID;
+use crate::entity::ThingID;
+use crate::phys;
+use crate::rule;
+use crate::types::Dir;
+use crate::types::Event::*;
+use crate::types::Flag::*;
+use crate::visual;
+use crate::world::World;
+
+/// Is the thing lockable?
+pub fn is_lockable(world: &World, thing: ID) -> bool {
+    world.locks.get(&thing).is_some()
+}
+
+/// Is the thing lockable and currently locked?
+pub fn is_locked(world: &World, thing: ID) -> bool {
+    is_lockable(world, thing) && world.has_flag(thing, Locked)
+}
+
+/// Does the key fit the lockable thing?
+pub fn fits(world: &World, key: ID, thing: ID) -> bool {
+    match world.locks.get(&thing) {
+        Some(lockc) => lockc.keys.contains(&key) || world.has_flag(key, MasterKey),
+        None => false,
+    }
+}
+
+/// Returns a key carried by the actor that fits the lockable thing, if any.
+pub fn key_for(world: &World, actor: ID, thing: ID) -> Option<ID> {
+    phys::contents(world, actor)
+        .into_iter()
+        .find(|key| world.has_flags(*key) && fits(world, *key, thing))
+}
+
+/// Returns the door, if any, guarding the link in the given direction.
+pub fn link_door(world: &World, loc: ID, dir: Dir) -> Option<ID> {
+    world.rooms[&loc].doors.get(&dir).cloned()
+}
+
+/// The player enters the code on the thing's dial or keypad.  If it's the thing's
+/// combination, the thing is unlocked and the CorrectCode event fires; otherwise
+/// the WrongCode event fires.
+pub fn enter_code(world: &mut World, pid: PlayerID, thing: ThingID, code: &str) {
+    let correct = world.locks[&thing.id()].combination.as_ref().map(|c| c.as_str()) == Some(code);
+    let (pid, thing) = (pid.id(), thing.id());
+
+    if correct {
+        if rule::allows(world, &CorrectCode(pid, thing)) {
+            world.unset_flag(thing, Locked);
+            visual::act("Click.");
+            rule::fire_event(world, &CorrectCode(pid, thing));
+        }
+    } else if !rule::fire_event(world, &WrongCode(pid, thing)) {
+        visual::act("Nothing happens.");
+    }
+}
+
+/// Does the thing have a combination?
+pub fn has_combination(world: &World, thing: ID) -> bool {
+    match world.locks.get(&thing) {
+        Some(lockc) => lockc.combination.is_some(),
+        None => false,
+    }
+}
+
+/// The player unlocks the thing with the key.  The caller is responsible for
+/// verifying that the player has the key, and that it fits.
+pub fn unlock(world: &mut World, pid: PlayerID, thing: ThingID) {
+    unlock_thing(world, pid, thing, true);
+}
+
+/// Like unlock(), but without announcing "Unlocked.", e.g., when the player unlocks the
+/// thing as an implicit action.  Returns true if the thing was unlocked.
+pub fn unlock_quietly(world: &mut World, pid: PlayerID, thing: ThingID) -> bool {
+    unlock_thing(world, pid, thing, false)
+}
+
+/// The player unlocks the thing, announcing it if desired.  Returns true if the thing
+/// was unlocked.
+fn unlock_thing(world: &mut World, pid: PlayerID, thing: ThingID, announce: bool) -> bool {
+    let (pid, thing) = (pid.id(), thing.id());
+
+    if !rule::allows(world, &Unlock(pid, thing)) {
+        return false;
+    }
+
+    world.unset_flag(thing, Locked);
+    if announce {
+        visual::act("Unlocked.");
+    }
+    rule::fire_event(world, &Unlock(pid, thing));
+    true
+}
+
+/// The player locks the thing with the key.  The caller is responsible for
+/// verifying that the player has the key, and that it fits.
+pub fn lock(world: &mut World, pid: PlayerID, thing: ThingID) {
+    let (pid, thing) = (pid.id(), thing.id());
+
+    if rule::allows(world, &Lock(pid, thing)) {
+        world.set_flag(thing, Locked);
+        visual::act("Locked.");
+        rule::fire_event(world, &Lock(pid, thing));
+    }
+}
@@ -0,0 +1,116 @@
+//! The Machine System
+//!
+//! Machines are things that can be switched on and off.  A machine may need power: a
+//! battery in its inventory, or a room flagged Powered.  Each turn, every machine that
+//! is switched on and powered fires its Running event, so that rules can give it
+//! behavior; a machine that has lost its power switches off.  A running machine's
+//! ambient line, if any, is added to its room's description.  A consumable machine, e.g.,
+//! a lamp, uses a charge each turn it runs, and can't be switched on once exhausted.
+
+use crate::consumable;
+use crate::entity::machine_component::Power;
+use crate::entity::ID;
+use crate::entity::PlayerID;
+use crate::entity::ThingID;
+use crate::phys;
+use crate::rule;
+use crate::types::Audience;
+use crate::types::Event::*;
+use crate::types::Flag::*;
+use crate::types::sentence;
+use crate::visual;
+use crate::world::World;
+
+/// The Machine system.  Runs the machines that are switched on.
+pub fn system(world: &mut World) {
+    let machines: Vec<ID> = world.machines.keys().cloned().collect();
+
+    for id in machines {
+        if !world.has_flag(id, On) {
+            continue;
+        }
+
+        if is_powered(world, id) {
+            rule::fire_event(world, &Running(id));
+            consumable::consume(world, id);
+        } else {
+            world.unset_flag(id, On);
+            let room = world.tag(room_of(world, id));
+            let text = format!("{} stops.", visual::the_name(world, id));
+            visual::observe(world, &Audience::Room(room), &sentence(&text));
+        }
+    }
+}
+
+/// Is the thing a machine?
+pub fn is_machine(world: &World, thing: ID) -> bool {
+    world.machines.get(&thing).is_some()
+}
+
+/// Is the machine switched on and powered?
+pub fn is_running(world: &World, machine: ID) -> bool {
+    world.has_flag(machine, On) && is_powered(world, machine)
+}
+
+/// Returns the thing's ambient line, if it's a machine that's running and has one.
+pub fn ambient(world: &World, thing: ID) -> Option<&str> {
+    match world.machines.get(&thing) {
+        Some(machinec) if is_running(world, thing) => machinec.ambient.as_ref().map(|a| a.as_str()),
+        _ => None,
+    }
+}
+
+/// Does the machine have the power it needs?
+pub fn is_powered(world: &World, machine: ID) -> bool {
+    match world.machines[&machine].power {
+        Power::None => true,
+        Power::Battery(battery) => phys::owns(world, machine, battery),
+        Power::Room => {
+            let room = room_of(world, machine);
+            world.has_flags(room) && world.has_flag(room, Powered)
+        }
+    }
+}
+
+/// Returns the room that contains the thing, however deeply it's nested.
+fn room_of(world: &World, thing: ID) -> ID {
+    let mut id = phys::loc(world, thing);
+
+    while !world.is_room(id) && world.has_location(id) && phys::loc(world, id) != id {
+        id = phys::loc(world, id);
+    }
+
+    id
+}
+
+/// The player switches the machine on, provided it's powered.
+pub fn switch_on(world: &mut World, pid: PlayerID, machine: ThingID) -> Result<(), String> {
+    let (pid, machine) = (pid.id(), machine.id());
+
+    if consumable::is_exhausted(world, machine) {
+        return Err("It's used up.".into());
+    }
+
+    if !is_powered(world, machine) {
+        return Err("Nothing happens.  It must need power.".into());
+    }
+
+    if rule::allows(world, &SwitchOn(pid, machine)) {
+        world.set_flag(machine, On);
+        visual::act("Switched on.");
+        rule::fire_event(world, &SwitchOn(pid, machine));
+    }
+
+    Ok(())
+}
+
+/// The player switches the machine off.
+pub fn switch_off(world: &mut World, pid: PlayerID, machine: ThingID) {
+    let (pid, machine) = (pid.id(), machine.id());
+
+    if rule::allows(world, &SwitchOff(pid, machine)) {
+        world.unset_flag(machine, On);
+        visual::act("Switched off.");
+        rule::fire_event(world, &SwitchOff(pid, machine));
+    }
+}
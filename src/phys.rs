@@ -4,19 +4,61 @@
 //! where they are, and making them available.  As such, it is concerned with the
 //! location and inventory components.
 
+use crate::entity::location_component::LocationComponent;
 use crate::entity::ID;
+use crate::entity::PlayerID;
+use crate::entity::RoomID;
+use crate::entity::ThingID;
 use crate::rule;
+use crate::types::Capability;
 use crate::types::Dir;
 use crate::types::Event::*;
+use crate::types::Forfeit;
+use crate::types::Outcome;
 use crate::types::LinkDest;
+use crate::types::Manipulation;
+use crate::types::Requirement;
 use crate::types::Flag::*;
 use crate::visual;
+use crate::wash;
+#[cfg(feature = "water")]
+use crate::water;
 use crate::world::World;
 use crate::world::LIMBO;
 use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::VecDeque;
 
 type PhysResult = Result<(), String>;
 
+/// The usual announcement of the player's death.
+pub const DEATH_MESSAGE: &str = "$You $have died.";
+
+/// An index of the things in each container by noun, for fast noun lookups: container
+/// ID to noun to the things with that noun or alias.  It's kept up to date as things
+/// move; see named().
+pub type NounIndex = HashMap<ID, HashMap<String, BTreeSet<ID>>>;
+
+/// A cache of scope queries, i.e., of what each viewer can see and get, so that a
+/// command that resolves several nouns needn't recompute them.  Moving a thing or
+/// changing a flag clears it.
+#[derive(Clone, Debug, Default)]
+pub struct ScopeCache {
+    /// The things visible to each viewer; see visible().
+    visible: HashMap<ID, BTreeSet<ID>>,
+
+    /// The things each viewer could get; see gettable().
+    gettable: HashMap<ID, BTreeSet<ID>>,
+}
+
+impl ScopeCache {
+    /// Clears the cache, because the world has changed.
+    pub fn clear(&mut self) {
+        self.visible.clear();
+        self.gettable.clear();
+    }
+}
+
 //--------------------------------------------------------------------------------
 // Queries
 
@@ -26,7 +68,49 @@ type PhysResult = Result<(), String>;
 pub fn loc(world: &World, thing: ID) -> ID {
     assert_has_location(world, thing);
 
-    world.locations[&thing].id
+    world.locations[&thing].id()
+}
+
+/// Determines whether the actor has the capability, either directly or by virtue of
+/// something it's carrying.
+pub fn can(world: &World, actor: ID, capability: Capability) -> bool {
+    let flag = Can(capability);
+
+    if world.has_flags(actor) && world.has_flag(actor, flag) {
+        return true;
+    }
+
+    world.has_inventory(actor)
+        && contents(world, actor)
+            .iter()
+            .any(|id| world.has_flags(*id) && world.has_flag(*id, flag))
+}
+
+/// Returns the requirement, if any, for following the link in the given direction.
+pub fn link_requirement(world: &World, loc: ID, dir: Dir) -> Option<Requirement> {
+    assert_is_room(world, loc);
+
+    world.rooms[&loc].requirements.get(&dir).cloned()
+}
+
+/// Returns the armed trap, if any, guarding the link in the given direction.
+pub fn link_trap(world: &World, loc: ID, dir: Dir) -> Option<ThingID> {
+    assert_is_room(world, loc);
+
+    match world.rooms[&loc].traps.get(&dir) {
+        Some(trap) if world.has_flag(*trap, Trapped) => world.thing_id(*trap),
+        _ => None,
+    }
+}
+
+/// Returns the requirement, if any, for climbing the thing.
+pub fn climb_requirement(world: &World, thing: ID) -> Option<Requirement> {
+    world.climbables.get(&thing).and_then(|climbc| climbc.requirement.clone())
+}
+
+/// Returns the direction climbing the thing takes the climber, if it leads anywhere.
+pub fn climb_dir(world: &World, thing: ID) -> Option<Dir> {
+    world.climbables.get(&thing).and_then(|climbc| climbc.dir)
 }
 
 /// Tries to follow a link in the given direction; returns the linked
@@ -56,7 +140,7 @@ pub fn owns(world: &World, container: ID, thing: ID) -> bool {
 pub fn contents(world: &World, container: ID) -> BTreeSet<ID> {
     assert_has_inventory(world, container);
 
-    world.inventories[&container].things.clone()
+    world.inventories[&container].iter().cloned().collect()
 }
 
 pub fn scenery(world: &World, owner: ID) -> BTreeSet<ID> {
@@ -79,9 +163,9 @@ pub fn non_scenery(world: &World, owner: ID) -> BTreeSet<ID> {
 
     let mut result: BTreeSet<ID> = BTreeSet::new();
 
-    // FIRST, get everything that's flagged as scenery.
+    // FIRST, get everything that's not flagged as scenery, or hidden.
     for id in contents(world, owner) {
-        if !world.has_flag(id, Scenery) {
+        if !world.has_flag(id, Scenery) && !is_concealed(world, id) {
             result.insert(id);
         }
     }
@@ -94,9 +178,9 @@ pub fn immovable(world: &World, owner: ID) -> BTreeSet<ID> {
 
     let mut result: BTreeSet<ID> = BTreeSet::new();
 
-    // FIRST, get everything that's flagged as immovable.
+    // FIRST, get everything that's flagged as immovable, and isn't hidden.
     for id in contents(world, owner) {
-        if world.has_flag(id, Immovable) {
+        if world.has_flag(id, Immovable) && !is_concealed(world, id) {
             result.insert(id);
         }
     }
@@ -104,11 +188,89 @@ pub fn immovable(world: &World, owner: ID) -> BTreeSet<ID> {
     result
 }
 
+/// Returns the number of links between two rooms, provided that it is no more than
+/// max_links; returns None otherwise.  Dead ends are ignored.
+pub fn distance(world: &World, from: ID, to: ID, max_links: usize) -> Option<usize> {
+    find_path(world, from, to, max_links, |_| true).map(|steps| steps.len())
+}
+
+/// Returns the shortest route from one room to another through rooms the player has
+/// seen, as the direction of each step and the room it leads to, or None if there's no
+/// such route.  Dead ends are ignored, as are doors and other obstacles; they stop the
+/// player on the way.
+pub fn route(world: &World, pid: ID, from: ID, to: ID) -> Option<Vec<(Dir, ID)>> {
+    find_path(world, from, to, usize::MAX, |room| world.has_flag(pid, Seen(room)))
+}
+
+/// Finds the shortest path of no more than max_links links from one room to another,
+/// passing only through rooms that satisfy the predicate, as the direction of each step
+/// and the room it leads to.  Returns None if there's no such path.  Dead ends are
+/// ignored.
+fn find_path<F>(
+    world: &World,
+    from: ID,
+    to: ID,
+    max_links: usize,
+    passable: F,
+) -> Option<Vec<(Dir, ID)>>
+where
+    F: Fn(ID) -> bool,
+{
+    let mut came_from: HashMap<ID, (Dir, ID)> = HashMap::new();
+    let mut frontier: VecDeque<(ID, usize)> = VecDeque::new();
+
+    frontier.push_back((from, 0));
+
+    while let Some((room, dist)) = frontier.pop_front() {
+        if room == to {
+            let mut steps = Vec::new();
+            let mut here = to;
+            while here != from {
+                let (dir, prev) = came_from[&here];
+                steps.push((dir, here));
+                here = prev;
+            }
+            steps.reverse();
+            return Some(steps);
+        }
+
+        if dist == max_links || !world.is_room(room) {
+            continue;
+        }
+
+        // Sort the links, so that the path doesn't depend on hash order.
+        let mut links: Vec<(&Dir, &LinkDest)> = world.rooms[&room].links.iter().collect();
+        links.sort_by_key(|(dir, _)| **dir);
+
+        for (dir, dest) in links {
+            if let LinkDest::Room(next) = dest {
+                let next = next.id();
+                if next != from && !came_from.contains_key(&next) && passable(next) {
+                    came_from.insert(next, (*dir, room));
+                    frontier.push_back((next, dist + 1));
+                }
+            }
+        }
+    }
+
+    None
+}
 
 /// Finds all things in the viewer's location that are visible to
 /// the viewer.  This includes things owned by the viewer, present
 /// in the viewer's location, or (ultimately) visible in open containers.
 pub fn visible(world: &World, viewer: ID) -> BTreeSet<ID> {
+    if let Some(ids) = world.scope_cache.borrow().visible.get(&viewer) {
+        return ids.clone();
+    }
+
+    let result = compute_visible(world, viewer);
+    world.scope_cache.borrow_mut().visible.insert(viewer, result.clone());
+    result
+}
+
+/// Computes visible(), bypassing the cache.
+fn compute_visible(world: &World, viewer: ID) -> BTreeSet<ID> {
     let mut result: BTreeSet<ID> = BTreeSet::new();
 
     // FIRST, get anything owned by the viewer
@@ -116,9 +278,35 @@ pub fn visible(world: &World, viewer: ID) -> BTreeSet<ID> {
         result.append(&mut contents(world, viewer));
     }
 
-    // NEXT, get anything in the viewer's location.
+    // NEXT, get anything in the viewer's location that isn't concealed.
     if world.has_location(viewer) {
-        result.append(&mut contents(world, loc(world, viewer)));
+        let here = contents(world, loc(world, viewer));
+        result.extend(here.into_iter().filter(|id| !is_concealed(world, *id)));
+    }
+
+    result
+}
+
+/// Finds the things flagged Distant in the rooms linked to the viewer's location,
+/// with the direction in which each can be seen.
+pub fn distant(world: &World, viewer: ID) -> Vec<(Dir, ID)> {
+    let here = loc(world, viewer);
+    let mut result = Vec::new();
+
+    if !world.is_room(here) {
+        return result;
+    }
+
+    let custom = world.directions.iter().map(|name| Dir::Custom(*name));
+
+    for dir in Dir::ALL.iter().cloned().chain(custom) {
+        if let Some(LinkDest::Room(room)) = follow_link(world, here, dir) {
+            for id in in_order(world, &contents(world, room.id())) {
+                if world.has_flag(id, Distant) && !result.iter().any(|(_, t)| *t == id) {
+                    result.push((dir, id));
+                }
+            }
+        }
     }
 
     result
@@ -151,16 +339,27 @@ pub fn droppable(world: &World, viewer: ID) -> BTreeSet<ID> {
 
 /// Finds all things in the viewer's location that he could, in theory,
 /// move to his own inventory, i.e., all things that aren't flagged
-/// as immovable.
+/// as immovable.  The viewer can't get itself.
 pub fn gettable(world: &World, viewer: ID) -> BTreeSet<ID> {
     assert_has_location(world, viewer);
 
+    if let Some(ids) = world.scope_cache.borrow().gettable.get(&viewer) {
+        return ids.clone();
+    }
+
+    let result = compute_gettable(world, viewer);
+    world.scope_cache.borrow_mut().gettable.insert(viewer, result.clone());
+    result
+}
+
+/// Computes gettable(), bypassing the cache.
+fn compute_gettable(world: &World, viewer: ID) -> BTreeSet<ID> {
     let mut result: BTreeSet<ID> = BTreeSet::new();
 
     // FIRST, get everything in the current location that isn't
     // flagged as Immovable.
     for id in contents(world, loc(world, viewer)) {
-        if !world.has_flag(id, Immovable) {
+        if id != viewer && !world.has_flag(id, Immovable) && !is_concealed(world, id) {
             result.insert(id);
         }
     }
@@ -168,70 +367,408 @@ pub fn gettable(world: &World, viewer: ID) -> BTreeSet<ID> {
     result
 }
 
+/// Returns the things directly in the container that can be referred to by the noun
+/// or noun phrase, e.g., "support sword", using the noun index.  Concealed things are
+/// left out.
+pub fn named(world: &World, container: ID, noun: &str) -> BTreeSet<ID> {
+    let head = noun.rsplit(' ').next().unwrap_or(noun);
+    let mut result = world
+        .noun_index
+        .get(&container)
+        .and_then(|nouns| nouns.get(head))
+        .cloned()
+        .unwrap_or_default();
+
+    result.retain(|id| !is_concealed(world, *id) && world.things[id].has_noun(noun));
+    result
+}
+
+/// Is the thing concealed, i.e., hidden until the player searches for it?
+pub fn is_concealed(world: &World, thing: ID) -> bool {
+    world.has_flags(thing) && world.has_flag(thing, Concealed)
+}
+
+/// Returns the concealed things in the room.
+pub fn concealed(world: &World, room: ID) -> BTreeSet<ID> {
+    contents(world, room)
+        .into_iter()
+        .filter(|id| is_concealed(world, *id))
+        .collect()
+}
+
+/// Verifies that the noun index agrees with the world.
+pub fn check_noun_index(world: &World) -> Result<(), String> {
+    if world.noun_index != compute_noun_index(world) {
+        return Err("The noun index is stale".into());
+    }
+
+    Ok(())
+}
+
+/// Computes the noun index from scratch.
+fn compute_noun_index(world: &World) -> NounIndex {
+    let mut index = NounIndex::new();
+
+    for (id, locc) in &world.locations {
+        if let Some(thingc) = world.things.get(id) {
+            let nouns = index.entry(locc.id()).or_default();
+            for noun in std::iter::once(&thingc.noun).chain(thingc.aliases.iter()) {
+                nouns.entry(noun.clone()).or_default().insert(*id);
+            }
+        }
+    }
+
+    index
+}
+
+/// Verifies that the scope cache agrees with the world, i.e., that nothing has changed
+/// the world without clearing it.
+pub fn check_scope_cache(world: &World) -> Result<(), String> {
+    let cache = world.scope_cache.borrow();
+
+    for (viewer, ids) in &cache.visible {
+        if *ids != compute_visible(world, *viewer) {
+            return Err(format!("Stale visible scope for {}", idtag(world, *viewer)));
+        }
+    }
+
+    for (viewer, ids) in &cache.gettable {
+        if *ids != compute_gettable(world, *viewer) {
+            return Err(format!("Stale gettable scope for {}", idtag(world, *viewer)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the entities in listing order: things by priority and then by name, and
+/// anything else after them, by ID.  Listings and noun lookups use this order, so
+/// that output doesn't depend on the order in which the scenario created things.
+pub fn in_order(world: &World, ids: &BTreeSet<ID>) -> Vec<ID> {
+    let mut result: Vec<ID> = ids.iter().cloned().collect();
+
+    result.sort_by_key(|id| match world.things.get(id) {
+        Some(thingc) => (0, thingc.priority, thingc.name.clone(), *id),
+        None => (1, 0, String::new(), *id),
+    });
+
+    result
+}
+
 //--------------------------------------------------------------------------------
 // Low-level operations
 //
 // These operations move things about and do the bookkeeping; but they contain no
-// game logic.
+// game logic.  They are the only code that can change locations and inventories,
+// which must be kept in sync.
+
+/// A token that only this module can create, required by the mutators of the location
+/// and inventory components, so that nothing else can change them.
+pub struct Bookkeeping(());
+
+/// Gives the thing a location component, if it doesn't have one.  The thing will
+/// initially be in LIMBO.
+pub fn add_location(world: &mut World, thing: ID) {
+    if !world.has_location(thing) {
+        world.locations.insert(thing, LocationComponent::new());
+        world.inventories.get_mut(&LIMBO).unwrap().add(thing, &Bookkeeping(()));
+        index_nouns(world, thing, LIMBO, true);
+        world.scope_cache.get_mut().clear();
+    }
+}
 
+/// Moves the thing from its current location into the container's inventory.  Moving a
+/// thing to LIMBO takes it out of play.  Panics if the move would put the thing inside
+/// itself.
+pub fn move_thing(world: &mut World, thing: ID, container: ID) {
+    assert_has_location(world, thing);
+    assert_has_inventory(world, container);
+    assert!(!encloses(world, thing, container),
+        "Cannot put {} inside itself", idtag(world, thing));
 
-/// Removes the thing from its current location and puts it in LIMBO.
-pub fn take_out(world: &mut World, thing: ID) {
-    let container = loc(world, thing);
+    // FIRST, remove it from wherever.
+    let there = loc(world, thing);
+    world.inventories.get_mut(&there).unwrap().remove(thing, &Bookkeeping(()));
+    index_nouns(world, thing, there, false);
 
-    // FIRST, remove it from wherever
-    world.inventories.get_mut(&container).unwrap().remove(thing);
+    // NEXT, put it where it goes.
+    world.locations.get_mut(&thing).unwrap().set(container, &Bookkeeping(()));
+    world.inventories.get_mut(&container).unwrap().add(thing, &Bookkeeping(()));
+    index_nouns(world, thing, container, true);
+    world.scope_cache.get_mut().clear();
+
+    // NEXT, it's no longer where it started.
+    if world.has_flags(thing) {
+        world.set_flag(thing, Moved);
+    }
 
-    // NEXT, put it in LIMBO
-    world.locations.get_mut(&thing).unwrap().id = LIMBO;
-    world.inventories.get_mut(&LIMBO).unwrap().add(thing);
+    // NEXT, note whose inventories have changed; see rule::fire_inventory_events().
+    let carriers: Vec<ID> = world
+        .players
+        .keys()
+        .cloned()
+        .filter(|pid| *pid != thing && (encloses(world, *pid, there) || encloses(world, *pid, container)))
+        .collect();
+    world.inventory_changes.extend(carriers);
 }
 
-pub fn put_in(world: &mut World, thing: ID, container: ID) {
-    // FIRST, remove it from wherever.
-    let there = loc(world, thing);
-    world.inventories.get_mut(&there).unwrap().remove(thing);
+/// Takes the thing out of play, i.e., moves it to LIMBO.  An Essential thing can't be
+/// lost, and is moved to its recovery place instead.
+pub fn destroy(world: &mut World, thing: ID) {
+    if world.has_flags(thing) && world.has_flag(thing, Essential) {
+        let place = recovery_place(world, thing);
+        world.log(&format!("Recovered {} to {}", idtag(world, thing), idtag(world, place)));
+        move_thing(world, thing, place);
+    } else {
+        move_thing(world, thing, LIMBO);
+    }
+}
 
-    // NEXT, put it where it goes.
-    world.locations.get_mut(&thing).unwrap().id = container;
-    world.inventories.get_mut(&container).unwrap().add(thing);
+/// Returns the place where the essential thing turns up if it would otherwise be lost:
+/// the place chosen by the scenario's recovery hook, if any; or else where the thing
+/// started out; or else the player's location.  A hook that names no room or
+/// container is logged as an error and ignored.
+pub fn recovery_place(world: &mut World, thing: ID) -> ID {
+    if let Some(hook) = world.recovery_hook {
+        let tag = hook(world, &world.tag(thing));
+        match world.lookup_id(&tag) {
+            Some(place) if world.has_inventory(place) => return place,
+            _ => {
+                let text = format!("Error: recovery hook gave a bad place for {}: {}", world.tag(thing), tag);
+                world.log(&text);
+            }
+        }
+    }
+
+    match world.origins.get(&thing) {
+        Some(origin) if *origin != LIMBO => *origin,
+        _ => loc(world, world.pid),
+    }
+}
+
+/// Gives the thing a new noun and aliases, keeping the noun index up to date.
+pub fn set_nouns(world: &mut World, thing: ID, noun: &str, aliases: &[String]) {
+    assert_has_location(world, thing);
+    let here = loc(world, thing);
+
+    index_nouns(world, thing, here, false);
+    let thingc = world.things.get_mut(&thing).unwrap();
+    thingc.noun = noun.into();
+    thingc.aliases = aliases.to_vec();
+    index_nouns(world, thing, here, true);
+}
+
+/// Rebuilds the noun index from scratch, e.g., after the world builder has changed
+/// things' nouns directly.
+pub fn rebuild_noun_index(world: &mut World) {
+    world.noun_index = compute_noun_index(world);
+}
+
+/// Adds the thing's nouns to, or removes them from, the container's entry in the noun
+/// index.
+fn index_nouns(world: &mut World, thing: ID, container: ID, add: bool) {
+    let thingc = match world.things.get(&thing) {
+        Some(thingc) => thingc,
+        None => return,
+    };
+
+    let entry = world.noun_index.entry(container).or_default();
+
+    for noun in std::iter::once(&thingc.noun).chain(thingc.aliases.iter()) {
+        if add {
+            entry.entry(noun.clone()).or_default().insert(thing);
+        } else if let Some(ids) = entry.get_mut(noun) {
+            ids.remove(&thing);
+            if ids.is_empty() {
+                entry.remove(noun);
+            }
+        }
+    }
+
+    if entry.is_empty() {
+        world.noun_index.remove(&container);
+    }
+}
+
+/// Returns the thing's total weight: its own weight plus the weight of everything it
+/// holds.
+pub fn weight(world: &World, thing: ID) -> u32 {
+    world.things.get(&thing).map_or(0, |thingc| thingc.weight) + load(world, thing)
+}
+
+/// Returns the total weight of everything in the container, e.g., the load on a
+/// pressure plate.
+pub fn load(world: &World, container: ID) -> u32 {
+    world
+        .inventories
+        .get(&container)
+        .map_or(0, |inv| inv.iter().map(|id| weight(world, *id)).sum())
+}
+
+/// Is the other entity the thing itself, or somewhere inside it?
+pub fn encloses(world: &World, thing: ID, other: ID) -> bool {
+    let mut id = other;
+
+    loop {
+        if id == thing {
+            return true;
+        }
+
+        match world.locations.get(&id) {
+            Some(locc) if locc.id() != id => id = locc.id(),
+            _ => return false,
+        }
+    }
 }
 
 //---------------------------------------------------------------------------------
 // High-level operations
 
-/// The player tries to enter the room.
-pub fn enter_room(world: &mut World, pid: ID, room: ID) -> PhysResult {
-    if rule::allows(world, &EnterRoom(pid, room)) {
-        put_in(world, pid, room);
+/// The player tries to enter the room.  A guard can send the player to another room
+/// instead, e.g., through a trapdoor; see Outcome::Redirect.
+pub fn enter_room(world: &mut World, player: PlayerID, room: RoomID) -> PhysResult {
+    enter_room_via(world, player, room, &mut Vec::new())
+}
 
-        if !world.has_flag(pid, Seen(room)) {
-            visual::room(world, room);
-        } else {
-            visual::room_brief(world, room);
+/// Enters the room, following redirects.  A redirect back to a room already tried
+/// is a cycle in the scenario, and is treated as a denial.
+fn enter_room_via(
+    world: &mut World,
+    player: PlayerID,
+    room: RoomID,
+    tried: &mut Vec<ID>,
+) -> PhysResult {
+    let (pid, room) = (player.id(), room.id());
+    tried.push(room);
+
+    match rule::outcome(world, &EnterRoom(pid, room)) {
+        Outcome::Allow => (),
+        Outcome::Deny => return Ok(()),
+        Outcome::Redirect(other) => {
+            if tried.contains(&other) {
+                world.log(&format!("Error: redirect cycle entering {}", world.tag(other)));
+                return Ok(());
+            }
+            let other = match world.room_id(other) {
+                Some(other) => other,
+                None => return Err(format!("Redirect to a non-room: {}", world.tag(other))),
+            };
+            return enter_room_via(world, player, other, tried);
         }
+    }
+
+    // Heavy things stay behind when the player sets out into deep water.
+    #[cfg(feature = "water")]
+    if water::is_deep(world, room) && !water::is_deep(world, loc(world, pid)) {
+        water::set_out(world, player);
+    }
 
-        world.set_flag(pid, Seen(room));
+    move_thing(world, pid, room);
+    world.rooms.get_mut(&room).unwrap().visits += 1;
+    visual::arrive(world, room);
 
-        rule::fire_event(world, &EnterRoom(pid, room));
+    if !world.has_flag(pid, Seen(room)) {
+        visual::room(world, room);
+    } else {
+        visual::room_brief(world, room);
     }
 
+    world.set_flag(pid, Seen(room));
+
+    rule::fire_event(world, &EnterRoom(pid, room));
+
     Ok(())
 }
 
-/// The player gets the thing.
-pub fn get_thing(world: &mut World, pid: ID, thing: ID) -> PhysResult {
-    if rule::allows(world, &GetThing(pid, thing)) {
-        put_in(world, thing, pid);
+/// The player gets the thing.  If the thing is trapped, the trap is sprung instead.
+pub fn get_thing(world: &mut World, pid: PlayerID, thing: ThingID) -> PhysResult {
+    take_thing(world, pid, thing, true);
+    Ok(())
+}
+
+/// Like get_thing(), but without announcing "Taken.", e.g., when the player takes the
+/// thing as an implicit action.  Returns true if the player got the thing.
+pub fn get_thing_quietly(world: &mut World, pid: PlayerID, thing: ThingID) -> bool {
+    take_thing(world, pid, thing, false)
+}
+
+/// The player takes the thing, announcing it if desired.  Returns true if the player
+/// got the thing.
+fn take_thing(world: &mut World, pid: PlayerID, thing: ThingID, announce: bool) -> bool {
+    take_into(world, pid, thing, pid.id(), announce)
+}
+
+/// The player puts the thing into the container, e.g., a sack.  If the player doesn't
+/// have the thing already, the player gets it straight into the container, and the
+/// GetThing guards apply as well.  Returns true if the thing was put away.
+pub fn put_thing(world: &mut World, pid: PlayerID, thing: ThingID, container: ThingID) -> bool {
+    let (player, tid, cid) = (pid.id(), thing.id(), container.id());
+
+    if !rule::allows(world, &PutThing(player, tid, cid)) {
+        return false;
+    }
+
+    if owns(world, player, tid) {
+        move_thing(world, tid, cid);
+    } else if !take_into(world, pid, thing, cid, false) {
+        return false;
+    }
+
+    visual::act("Done.");
+    rule::fire_event(world, &PutThing(player, tid, cid));
+    true
+}
+
+/// The player takes the thing into the destination: the player's own hands, or a
+/// container.  Returns true if the player got the thing.
+fn take_into(world: &mut World, pid: PlayerID, thing: ThingID, dest: ID, announce: bool) -> bool {
+    if world.has_flag(thing.id(), Trapped) {
+        spring_trap(world, pid, thing);
+        return false;
+    }
+
+    let (pid, thing) = (pid.id(), thing.id());
+    let theft = witness(world, pid, thing).map(|owner| Steal(pid, owner, thing));
+
+    if !rule::allows(world, &GetThing(pid, thing, dest)) {
+        return false;
+    }
+
+    if let Some(theft) = &theft {
+        if !rule::allows(world, theft) {
+            return false;
+        }
+    }
+
+    move_thing(world, thing, dest);
+    if announce {
         visual::act("Taken.");
-        rule::fire_event(world, &GetThing(pid, thing));
     }
+    wash::contaminate(world, pid, thing);
+    rule::fire_event(world, &GetThing(pid, thing, dest));
 
-    Ok(())
+    if let Some(theft) = &theft {
+        rule::fire_event(world, theft);
+    }
+    true
+}
+
+/// Returns the thing's owner, if it would see the player take the thing: an owning
+/// room sees it if the player is in the room, and an owning NPC if it's in view.
+fn witness(world: &World, pid: ID, thing: ID) -> Option<ID> {
+    let owner = world.things[&thing].owner?;
+
+    if owner == loc(world, pid) || (owner != pid && visible(world, pid).contains(&owner)) {
+        Some(owner)
+    } else {
+        None
+    }
 }
 
 /// The player reads the thing's Book prose.
-pub fn read_thing(world: &mut World, pid: ID, thing: ID) -> PhysResult {
+pub fn read_thing(world: &mut World, pid: PlayerID, thing: ThingID) -> PhysResult {
+    let (pid, thing) = (pid.id(), thing.id());
+
     if rule::allows(world, &ReadThing(pid, thing)) {
         visual::read(world, thing);
         rule::fire_event(world, &ReadThing(pid, thing));
@@ -240,6 +777,146 @@ pub fn read_thing(world: &mut World, pid: ID, thing: ID) -> PhysResult {
     Ok(())
 }
 
+/// The player manipulates the thing, e.g., pushes it.  If no rule responds, the verb's
+/// default response is displayed.
+pub fn manipulate(world: &mut World, pid: PlayerID, thing: ThingID, how: Manipulation) -> PhysResult {
+    let event = Manipulate(pid.id(), thing.id(), how);
+
+    if rule::allows(world, &event) && !rule::fire_event(world, &event) {
+        match world.default_responses.get(how.verb()) {
+            Some(text) => visual::info(text),
+            None => visual::info("Nothing happens."),
+        }
+    }
+
+    Ok(())
+}
+
+/// The player climbs a thing that doesn't lead anywhere.  If no rule responds, the
+/// player gets nowhere.
+pub fn climb(world: &mut World, pid: PlayerID, thing: ThingID) -> PhysResult {
+    let event = Climb(pid.id(), thing.id());
+
+    if rule::allows(world, &event) && !rule::fire_event(world, &event) {
+        visual::info("$You $get nowhere.");
+    }
+
+    Ok(())
+}
+
+/// The player springs the trap, which is then no longer armed.
+pub fn spring_trap(world: &mut World, pid: PlayerID, trap: ThingID) {
+    let (pid, trap) = (pid.id(), trap.id());
+
+    if rule::allows(world, &SpringTrap(pid, trap)) {
+        world.unset_flag(trap, Trapped);
+        visual::act("It's a trap!");
+        rule::fire_event(world, &SpringTrap(pid, trap));
+    }
+}
+
+/// The player detects the trap, if it hasn't been detected already.
+pub fn detect_trap(world: &mut World, pid: PlayerID, trap: ThingID) {
+    let (pid, trap) = (pid.id(), trap.id());
+
+    if world.has_flag(trap, Detected) {
+        return;
+    }
+
+    if rule::allows(world, &DetectTrap(pid, trap)) {
+        world.set_flag(trap, Detected);
+        visual::act("$You $notice that it's trapped.");
+        rule::fire_event(world, &DetectTrap(pid, trap));
+    }
+}
+
+/// Kills the player, announcing the death with the message, and shows the epitaph for
+/// the cause of death, if the scenario has one.  Both can use "{deaths}"; see
+/// visual::expand().
+pub fn kill(world: &mut World, pid: ID, message: &str, cause: Option<&str>) {
+    world.set_flag(pid, Dead);
+    world.metrics.deaths += 1;
+    world.death_room = Some(loc(world, pid));
+    visual::banner(&visual::expand(world, message));
+
+    if let Some(epitaph) = cause.and_then(|cause| world.epitaphs.get(cause)) {
+        visual::info(&visual::expand(world, epitaph));
+    }
+}
+
+/// Revives the player after a death, and exacts the scenario's price for it: the
+/// player's things and points, as configured.  Fires the Revive event, whatever
+/// revived the player, so that rules can add consequences of their own.
+pub fn revive(world: &mut World, pid: ID) {
+    world.unset_flag(pid, Dead);
+    visual::banner("$You $are alive!");
+
+    // FIRST, the player forfeits the things the player was carrying, if need be.
+    let things = removable(world, pid);
+
+    match world.revive_forfeit {
+        Forfeit::Nothing => (),
+        Forfeit::Drop => {
+            let room = world.death_room.unwrap_or_else(|| loc(world, pid));
+            for id in things {
+                move_thing(world, id, room);
+                #[cfg(feature = "water")]
+                water::sink(world, id);
+            }
+        }
+        Forfeit::Lose => {
+            for id in things {
+                destroy(world, id);
+            }
+        }
+    }
+
+    // NEXT, the player loses points, if need be.
+    if world.revive_penalty != 0 {
+        world.score -= world.revive_penalty;
+        visual::score(-world.revive_penalty);
+    }
+
+    rule::fire_event(world, &Revive(pid));
+}
+
+/// The player searches the room, revealing anything concealed there, unless a rule
+/// forbids it.
+pub fn search(world: &mut World, pid: PlayerID, room: RoomID) {
+    let (pid, room) = (pid.id(), room.id());
+
+    if !rule::allows(world, &Search(pid, room)) {
+        return;
+    }
+
+    let found = concealed(world, room);
+
+    for id in &found {
+        world.unset_flag(*id, Concealed);
+    }
+
+    if found.is_empty() {
+        visual::act("$You $find nothing of interest.");
+    } else {
+        visual::act(&format!("$You $find {}.", visual::invent_list(world, &found)));
+    }
+
+    rule::fire_event(world, &Search(pid, room));
+}
+
+/// The player disarms the trap.
+pub fn disarm_trap(world: &mut World, pid: PlayerID, trap: ThingID) -> PhysResult {
+    let (pid, trap) = (pid.id(), trap.id());
+
+    if rule::allows(world, &DisarmTrap(pid, trap)) {
+        world.unset_flag(trap, Trapped);
+        visual::act("Disarmed.");
+        rule::fire_event(world, &DisarmTrap(pid, trap));
+    }
+
+    Ok(())
+}
+
 //--------------------------------------------------------------------------------
 // Standard Assertions
 
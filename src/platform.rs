@@ -0,0 +1,93 @@
+//! The Platform System
+//!
+//! A platform is a room that moves between stops: an elevator, a ferry, a cable car.
+//! While docked at a stop, the platform's exit leads to the stop's room, and the room's
+//! entry leads back to the platform; other stops have no link to it.  Buttons move the
+//! platform, either to a particular stop or on to the next one.  Rules on the
+//! PlatformMove event can forbid a move, e.g., while the power is off, or add to it.
+
+use crate::entity::ID;
+use crate::entity::PlayerID;
+use crate::entity::RoomID;
+use crate::entity::ThingID;
+use crate::phys;
+use crate::rule;
+use crate::types::Event::*;
+use crate::types::LinkDest;
+use crate::visual;
+use crate::world::World;
+
+/// Is the thing a platform button?
+pub fn is_button(world: &World, thing: ID) -> bool {
+    world.buttons.get(&thing).is_some()
+}
+
+/// Returns the room the platform is currently docked with.
+pub fn current_stop(world: &World, platform: ID) -> ID {
+    let platformc = &world.platforms[&platform];
+    platformc.stops[platformc.position].room
+}
+
+/// Binds the links between the platform and its current stop.
+pub fn dock(world: &mut World, platform: ID) {
+    let platformc = world.platforms[&platform].clone();
+    let stop = &platformc.stops[platformc.position];
+
+    world.rooms.get_mut(&platform).unwrap()
+        .links
+        .insert(platformc.exit, LinkDest::Room(RoomID::new(stop.room)));
+    world.rooms.get_mut(&stop.room).unwrap()
+        .links
+        .insert(stop.entry, LinkDest::Room(RoomID::new(platform)));
+}
+
+/// Removes the links between the platform and its current stop.
+fn undock(world: &mut World, platform: ID) {
+    let platformc = world.platforms[&platform].clone();
+    let stop = &platformc.stops[platformc.position];
+
+    world.rooms.get_mut(&platform).unwrap().links.remove(&platformc.exit);
+    world.rooms.get_mut(&stop.room).unwrap().links.remove(&stop.entry);
+}
+
+/// Moves the platform to the stop with the given index, rebinding its links.  The
+/// player perceives the move if aboard or waiting at the destination.
+pub fn move_to(world: &mut World, platform: ID, index: usize) {
+    undock(world, platform);
+    world.platforms.get_mut(&platform).unwrap().position = index;
+    dock(world, platform);
+
+    let here = phys::loc(world, world.pid);
+
+    if here == platform {
+        visual::info("$You feel a lurch as it moves.");
+    } else if here == current_stop(world, platform) {
+        visual::info("Something arrives with a clunk.");
+    }
+}
+
+/// The player presses the button, sending its platform to the button's stop, or on to
+/// the next stop.
+pub fn press(world: &mut World, pid: PlayerID, button: ThingID) {
+    let (pid, button) = (pid.id(), button.id());
+    let buttonc = world.buttons[&button].clone();
+    let platformc = &world.platforms[&buttonc.platform];
+
+    let index = match buttonc.stop {
+        Some(room) => platformc.stops.iter().position(|s| s.room == room).unwrap(),
+        None => (platformc.position + 1) % platformc.stops.len(),
+    };
+
+    if index == platformc.position {
+        visual::act("Click.  Nothing happens.");
+        return;
+    }
+
+    let event = PlatformMove(pid, buttonc.platform, platformc.stops[index].room);
+
+    if rule::allows(world, &event) {
+        visual::act("Click.");
+        move_to(world, buttonc.platform, index);
+        rule::fire_event(world, &event);
+    }
+}
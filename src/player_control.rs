@@ -2,30 +2,67 @@
 
 use crate::script::Script;
 use self::Status::*;
+use crate::autosave;
 use crate::command;
 use crate::command::Command;
+use crate::consumable;
+use crate::craft;
+use crate::dark;
+use crate::console;
 use crate::debug;
+#[cfg(feature = "deadlines")]
+use crate::deadline;
+#[cfg(feature = "effects")]
+use crate::effect;
+use crate::help;
+use crate::implicit;
+use crate::entity::player_component::Note;
 use crate::entity::ID;
+use crate::entity::PlayerID;
+use crate::entity::ThingID;
+use crate::lock;
+#[cfg(feature = "machines")]
+use crate::machine;
 use crate::phys;
+use crate::platform;
+use crate::portal;
+use crate::records;
 use crate::types::Dir::*;
 use crate::types::Flag::*;
 use crate::types::*;
 use crate::visual;
+use crate::wash;
+#[cfg(feature = "water")]
+use crate::water;
 use crate::world::*;
 use crate::Game;
 use std::collections::BTreeSet;
+use std::collections::VecDeque;
 
 /// A status result.  Indicates the general category of the change.
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 enum Status {
     /// Normal response: the world has been updated, and the change can be undone.
     Normal,
 
+    /// Like Normal, but no game time passes.
+    NoTurn,
+
     /// Restart response; the game should be restarted from scratch.
     Restart,
 
     /// Undo the last command (plus anything that happened after, e.g., rule firings)
     Undo,
+
+    /// Restore the most recent autosave.
+    Restore,
+
+    /// Quit the game, after confirmation if there's unsaved progress.
+    Quit,
+
+    /// Ask the player the question, and execute the command again if the answer is
+    /// "yes"; see confirm().
+    Confirm(String),
 }
 
 /// A status result, used for special commands
@@ -34,34 +71,166 @@ type StatusResult = Result<Status, String>;
 /// Player Context: ID and initial location.
 struct Player {
     pub id: ID,
+    pub pid: PlayerID,
     pub loc: ID,
 }
 
-/// The Player Control system.  Processes player commands.
-pub fn system(game: &mut Game, input: &str) {
+/// The question asked before quitting with unsaved progress.
+pub const QUIT_QUESTION: &str =
+    "Any progress since the last autosave will be lost.  Are $you sure $you want to quit?";
+
+/// A command awaiting the player's confirmation.
+pub struct Confirmation {
+    /// The command, as the player entered it.
+    pub command: String,
+
+    /// The question the player was asked.
+    pub question: String,
+}
+
+/// Asks the player to confirm the command before it's executed.  If the next input is
+/// "yes", the command is executed again, and this time WorldQuery::confirmed() is true;
+/// "no" cancels it, and anything else cancels it and is handled as a command in its own
+/// right.  Built-in commands ask by returning Status::Confirm, and command hooks by
+/// returning Handled::Confirm.
+pub fn confirm(game: &mut Game, command: &str, question: &str) {
+    visual::info(&format!("{} (y/n)", question));
+    game.confirming = Some(Confirmation {
+        command: command.trim().into(),
+        question: question.into(),
+    });
+}
+
+/// Interprets the input as the answer to a yes/no question: Some(true) for yes,
+/// Some(false) for no, and None for anything else.
+fn answer(input: &str) -> Option<bool> {
+    match input.trim().to_lowercase().as_str() {
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// The Player Control system.  Processes player commands.  Returns true if game time
+/// passes, and false otherwise.
+pub fn system(game: &mut Game, input: &str) -> bool {
     // FIRST, get the current game state, for later undo.
     let undo_info = game.world.clone();
 
     // NEXT, get the player's context
     let player = Player {
         id: game.world.pid,
+        pid: game.world.player_id(game.world.pid).expect("player entity"),
         loc: phys::loc(&game.world, game.world.pid),
     };
 
-    // NEXT, handle the input
-    let result = handle_input(game, &player, input);
-    match result {
-        Err(msg) => visual::error(&msg),
-        Ok(Normal) => {
+    // NEXT, if a command is awaiting confirmation, the input is the answer.
+    let confirmed = match game.confirming.take().map(|c| (c, answer(input))) {
+        Some((confirmation, Some(true))) => Some(confirmation.command),
+        Some((_, Some(false))) => {
+            visual::act("Okay.");
+            return false;
+        }
+        _ => None,
+    };
+
+    // NEXT, handle the input, or the command it confirms.
+    let result = match &confirmed {
+        Some(command) => {
+            game.world.confirmed = true;
+            let result = handle_input(game, &player, command);
+            game.world.confirmed = false;
+            result
+        }
+        None => handle_input(game, &player, input),
+    };
+
+    match &result {
+        Err(msg) => {
+            let count = match &game.repeated_error {
+                Some((last, count)) if last == msg => count + 1,
+                _ => 1,
+            };
+            visual::error(&repeat_error(&game.world, msg, count));
+            game.repeated_error = Some((msg.clone(), count));
+            game.error = Some(msg.clone());
+        }
+        Ok(Normal) | Ok(NoTurn) => {
             game.save_for_undo(undo_info);
+
+            // A confirmed command is recorded with its answer, so that it replays.
+            if let Some(command) = &confirmed {
+                game.history.push(command.clone());
+            }
+
+            if !input.trim().starts_with('!') {
+                game.history.push(input.trim().into());
+            }
         }
         Ok(Restart) => game.restart(),
         Ok(Undo) => game.undo(),
+        Ok(Restore) => game.restore_autosave(),
+        Ok(Quit) if confirmed.is_none() => game.request_quit(),
+        Ok(Quit) => game.quit(),
+        Ok(Confirm(question)) => confirm(game, input, question),
+    }
+
+    if result.is_ok() {
+        game.repeated_error = None;
+    }
+
+    match result {
+        Ok(NoTurn) | Ok(Restore) | Ok(Quit) | Ok(Confirm(_)) => false,
+        _ => true,
+    }
+}
+
+/// Returns the error message to show the count'th time in a row the player gets it:
+/// the message itself the first time, and after that the scenario's escalation prose for
+/// it, if any, or else the message again with a reminder that it's been said before.
+fn repeat_error(world: &World, msg: &str, count: usize) -> String {
+    if count < 2 {
+        return msg.into();
+    }
+
+    if let Some(texts) = world.repeat_errors.get(msg) {
+        return texts[(count - 2).min(texts.len() - 1)].clone();
+    }
+
+    // Lower-case the message's first letter, after any "$", unless it's "I".
+    let mut text = String::from("Again, ");
+    let first_word = msg.trim_start_matches('$').split(|c: char| !c.is_alphabetic()).next();
+
+    if first_word == Some("I") {
+        text.push_str(msg);
+    } else {
+        let mut chars = msg.chars();
+        for c in &mut chars {
+            if c.is_alphabetic() {
+                text.extend(c.to_lowercase());
+                break;
+            }
+            text.push(c);
+        }
+        text.extend(chars);
+    }
+
+    if count > 2 {
+        text.push_str("  (\"help\" lists what $you can do.)");
     }
+
+    text
 }
 
 fn handle_input(game: &mut Game, player: &Player, input: &str) -> StatusResult {
-    // FIRST, parse the input.
+    // FIRST, the input might answer a script's question.
+    if let Some(prompt) = game.world.prompt.take() {
+        if let Some(script) = prompt.script_for(input) {
+            return answer_prompt(&mut game.world, &prompt, script, input);
+        }
+    }
+
+    // NEXT, parse the input.
     let cmd = command::parse(&game.world, input)?;
 
     if cmd.is_debug {
@@ -71,18 +240,76 @@ fn handle_input(game: &mut Game, player: &Player, input: &str) -> StatusResult {
     }
 }
 
+/// Runs the script for the player's answer to a script's question.  The answer is
+/// saved, so that the script's prose can show it as "{answer}".
+fn answer_prompt(world: &mut World, prompt: &Prompt, script: ID, input: &str) -> StatusResult {
+    let script = world.rules[&script].script.clone();
+
+    world.answer = Some(input.trim().into());
+    script.execute_with(world, prompt.subject);
+    Ok(Normal)
+}
+
 fn handle_normal_command(game: &mut Game, player: &Player, cmd: &Command) -> StatusResult {
     let world = &mut game.world;
     let the_words: Vec<&str> = cmd.words.iter().map(|s| s.as_ref()).collect();
     let words = the_words.as_slice();
 
-    // FIRST, handle custom commands.
-    // TODO: Possible better way to handle this: write function that matches the words
-    // and clones the specific handler.
-    for handler in world.command_handlers.clone() {
-        if handler.matches(words) {
-            handler.execute(&mut game.world, player, words)?;
-            return Ok(Normal);
+    // FIRST, make sure the player is capable of the command.
+    if let Some(verb) = words.first() {
+        if let Some(req) = world.verb_requirements.get(*verb) {
+            if !phys::can(world, player.id, req.capability) {
+                return Err(req.prose.clone());
+            }
+        }
+    }
+
+    // NEXT, in the dark only some commands make sense.
+    if let Some(verb) = words.first() {
+        if dark::is_dark(world, player.loc) && !dark::permits(world, verb) {
+            return Err(dark::response(world, verb));
+        }
+    }
+
+    // NEXT, let the scenario's parser extensions handle the command.
+    for i in 0..world.parser_extensions.len() {
+        let ext = world.parser_extensions[i];
+        let hook = |query: &WorldQuery, words: &[&str], script: &mut Script| {
+            ext.sentence(query, &cmd.input, words, script)
+        };
+
+        match run_hook(world, &hook, words)? {
+            Handled::Pass => continue,
+            Handled::NoTurn => return Ok(NoTurn),
+            Handled::EndTurn => return Ok(Normal),
+            Handled::Confirm(question) => return Ok(Confirm(question)),
+        }
+    }
+
+    // NEXT, handle verbs attached to the thing the command refers to.
+    if let Some((thing, hook)) = thing_handler(world, player, words) {
+        if consumable::is_exhausted(world, thing.id()) {
+            return Err("It's used up.".into());
+        }
+
+        match run_hook(world, hook, words)? {
+            Handled::Pass => (),
+            Handled::NoTurn => return Ok(NoTurn),
+            Handled::EndTurn => return Ok(Normal),
+            Handled::Confirm(question) => return Ok(Confirm(question)),
+        }
+    }
+
+    // NEXT, handle custom commands.  Handlers are dispatched by index, so that the
+    // handler list needn't be cloned while the hooks update the world.
+    for i in 0..world.command_handlers.len() {
+        if world.command_handlers[i].matches(words) {
+            match CommandHandler::execute(world, player, i, words)? {
+                Handled::Pass => continue,
+                Handled::NoTurn => return Ok(NoTurn),
+                Handled::EndTurn => return Ok(Normal),
+                Handled::Confirm(question) => return Ok(Confirm(question)),
+            }
         }
     }
 
@@ -96,28 +323,161 @@ fn handle_normal_command(game: &mut Game, player: &Player, cmd: &Command) -> Sta
         ["east"] => cmd_go(world, player, East),
         ["go", "west"] => cmd_go(world, player, West),
         ["west"] => cmd_go(world, player, West),
-        ["help"] => cmd_help(),
+        ["go", "up"] => cmd_go(world, player, Up),
+        ["up"] => cmd_go(world, player, Up),
+        ["go", "down"] => cmd_go(world, player, Down),
+        ["down"] => cmd_go(world, player, Down),
+        ["go", "in"] => cmd_go(world, player, In),
+        ["in"] => cmd_go(world, player, In),
+        ["go", "out"] => cmd_go(world, player, Out),
+        ["out"] => cmd_go(world, player, Out),
+        [word] | ["go", word] if custom_dir(world, word).is_some() => {
+            cmd_go(world, player, custom_dir(world, word).unwrap())
+        }
+        ["climb", "up"] => cmd_go(world, player, Up),
+        ["climb", "down"] => cmd_go(world, player, Down),
+        ["climb", "up", name] => cmd_climb(world, player, name),
+        ["climb", "down", name] => cmd_climb(world, player, name),
+        ["climb", name] => cmd_climb(world, player, name),
+        #[cfg(feature = "water")]
+        ["swim"] => Err("Which way do $you want to swim?".into()),
+        #[cfg(feature = "water")]
+        ["swim", "north"] => cmd_swim(world, player, North),
+        #[cfg(feature = "water")]
+        ["swim", "south"] => cmd_swim(world, player, South),
+        #[cfg(feature = "water")]
+        ["swim", "east"] => cmd_swim(world, player, East),
+        #[cfg(feature = "water")]
+        ["swim", "west"] => cmd_swim(world, player, West),
+        #[cfg(feature = "water")]
+        ["swim", "up"] => cmd_swim(world, player, Up),
+        #[cfg(feature = "water")]
+        ["swim", "down"] => cmd_swim(world, player, Down),
+        #[cfg(feature = "water")]
+        ["swim", "in"] => cmd_swim(world, player, In),
+        #[cfg(feature = "water")]
+        ["swim", "out"] => cmd_swim(world, player, Out),
+        #[cfg(feature = "water")]
+        ["swim", word] if custom_dir(world, word).is_some() => {
+            cmd_swim(world, player, custom_dir(world, word).unwrap())
+        }
+        ["feel"] => cmd_feel_around(world, player),
+        ["feel", "around"] => cmd_feel_around(world, player),
+        ["feel", name] => cmd_feel(world, player, name),
+        ["wash"] => Err("What do $you want to wash?".into()),
+        ["wash", name] => cmd_wash(world, player, name),
+        ["help"] => cmd_help(world),
+        ["help", "search", word] => cmd_help_search(world, word),
+        ["help", topic] => cmd_help_topic(world, topic),
         ["look"] => cmd_look(world, player),
         ["inventory"] => cmd_inventory(world, player),
+        ["examine", "all"] => cmd_examine_all(world, player),
         ["examine", name] => cmd_examine(world, player, name),
+        ["search"] | ["search", "room"] => cmd_search(world, player),
         ["read", name] => cmd_read(world, player, name),
         ["get", name] => cmd_get(world, player, name),
+        ["get", count, name] if parse_number(count).is_some() => {
+            cmd_get_some(world, player, parse_number(count), name)
+        }
         ["pick", "up", name] => cmd_get(world, player, name),
         ["drop", name] => cmd_drop(world, player, name),
+        ["drop", count, name] if parse_number(count).is_some() => {
+            cmd_drop_some(world, player, parse_number(count), name)
+        }
+        ["disarm", _] => Err("What do $you want to disarm it with?".into()),
+        ["disarm", name, "with", tool] => cmd_disarm(world, player, name, tool),
+        ["unlock", name] => cmd_lock(world, player, name, None, false),
+        ["unlock", name, "with", key] => cmd_lock(world, player, name, Some(key), false),
+        ["lock", name] => cmd_lock(world, player, name, None, true),
+        ["lock", name, "with", key] => cmd_lock(world, player, name, Some(key), true),
+        #[cfg(feature = "machines")]
+        ["turn", "on", name] => cmd_switch(world, player, name, true),
+        #[cfg(feature = "machines")]
+        ["turn", name, "on"] => cmd_switch(world, player, name, true),
+        #[cfg(feature = "machines")]
+        ["switch", "on", name] => cmd_switch(world, player, name, true),
+        #[cfg(feature = "machines")]
+        ["switch", name, "on"] => cmd_switch(world, player, name, true),
+        #[cfg(feature = "machines")]
+        ["turn", "off", name] => cmd_switch(world, player, name, false),
+        #[cfg(feature = "machines")]
+        ["turn", name, "off"] => cmd_switch(world, player, name, false),
+        #[cfg(feature = "machines")]
+        ["switch", "off", name] => cmd_switch(world, player, name, false),
+        #[cfg(feature = "machines")]
+        ["switch", name, "off"] => cmd_switch(world, player, name, false),
+        ["put", name, "in", container] => cmd_put_in(world, player, name, container),
+        ["get", name, "from", container] => cmd_get_from(world, player, name, container),
+        ["combine", a, "with", b] => cmd_combine(world, player, a, b),
+        ["combine", a, "and", b] => cmd_combine(world, player, a, b),
+        ["attach", a, "to", b] => cmd_combine(world, player, a, b),
+        ["mix", a, "with", b] => cmd_combine(world, player, a, b),
+        ["mix", a, "and", b] => cmd_combine(world, player, a, b),
+        ["dial", name, "to", _] => cmd_enter_code(world, player, name, &cmd.code(3)),
+        ["turn", name, "to", _] => cmd_enter_code(world, player, name, &cmd.code(3)),
+        ["set", name, "to", _] => cmd_enter_code(world, player, name, &cmd.code(3)),
+        ["enter", _, "on", name] => cmd_enter_code(world, player, name, &cmd.code(1)),
+        ["enter", _, "into", name] => cmd_enter_code(world, player, name, &cmd.code(1)),
+        ["enter", name] => cmd_enter(world, player, name),
+        ["go", "through", name] => cmd_enter(world, player, name),
+        ["go", "to"] => Err("Where do $you want to go?".into()),
+        ["go", "to", name @ ..] => cmd_go_to(world, player, name),
+        ["push", name] => cmd_manipulate(world, player, name, Manipulation::Push),
+        ["pull", name] => cmd_manipulate(world, player, name, Manipulation::Pull),
+        ["turn", name] => cmd_manipulate(world, player, name, Manipulation::Turn),
+        ["touch", name] => cmd_manipulate(world, player, name, Manipulation::Touch),
+        ["shake", name] => cmd_manipulate(world, player, name, Manipulation::Shake),
+        ["talk", "to", name] => cmd_talk(world, player, name),
+        ["talk", name] => cmd_talk(world, player, name),
+        ["ask", "about", topic] => cmd_ask_about(world, player, topic),
+        ["ask", name, "about", topic] => cmd_ask(world, player, name, topic),
+        ["topics"] => cmd_topics(world, player),
+        ["use"] => Err("What do $you want to use?".into()),
+        ["use", _] => cmd_default_response(world, player, words),
+        ["chapter"] => cmd_chapter(world),
+        ["status"] => cmd_status(world),
+        #[cfg(feature = "deadlines")]
+        ["time"] => cmd_time(world),
+        ["records"] => cmd_records(world),
+        ["note"] => Err("What do $you want to note?".into()),
+        ["note", _, ..] => cmd_note(world, player, &cmd.input),
+        ["journal"] => cmd_journal(world, player),
+        ["erase", "note", num] => cmd_erase_note(world, player, num),
+        ["status", "on"] => cmd_status_line(world, true),
+        ["status", "off"] => cmd_status_line(world, false),
+        ["accessible", "on"] => cmd_accessible(true),
+        ["accessible", "off"] => cmd_accessible(false),
         ["undo"] => cmd_undo(game),
-        ["restart"] => cmd_restart(),
+        ["restart"] => cmd_restart(world),
+        ["restore", "auto"] => cmd_restore_auto(),
         ["quit"] => cmd_quit(),
 
-        // Error
-        _ => Err("I don't understand.".into()),
+        // Default responses, or error
+        _ => cmd_default_response(world, player, words),
     }
 }
 
 // User Commands
 
 
+/// Respond to a verb applied to a thing that doesn't handle it, if the verb has a
+/// default response.
+fn cmd_default_response(world: &World, player: &Player, words: &[&str]) -> StatusResult {
+    match words {
+        [verb, name] if world.default_responses.contains_key(*verb) => {
+            if find_visible(world, player, verb, name).is_some() {
+                visual::info(&world.default_responses[*verb]);
+                Ok(Normal)
+            } else {
+                Err("$You $don't see any such thing.".into())
+            }
+        }
+        _ => Err("I don't understand.".into()),
+    }
+}
+
 /// Display basic help, i.e., what commands are available.
-fn cmd_help() -> StatusResult {
+fn cmd_help(world: &World) -> StatusResult {
     visual::info(
         "\
 You've got the usual commands: n, s, e, w, look, get, drop, quit.
@@ -125,14 +485,84 @@ You know.  Like that.
     ",
     );
 
+    let topics = help::topics(world);
+    visual::info(&format!(
+        "For more, try \"help <topic>\" or \"help search <word>\".  The topics are {}.",
+        list_phrase(&topics, "and")
+    ));
+
+    Ok(Normal)
+}
+
+/// Display the help page on the topic, e.g., a command.
+fn cmd_help_topic(world: &World, topic: &str) -> StatusResult {
+    match help::page(world, topic) {
+        Some(text) => {
+            visual::info(&text);
+            Ok(Normal)
+        }
+        None => Err(format!(
+            "There's no help on \"{}\".  Try \"help search {}\".",
+            topic, topic
+        )),
+    }
+}
+
+/// List the help topics that mention the word.
+fn cmd_help_search(world: &World, word: &str) -> StatusResult {
+    let topics = help::search(world, word);
+
+    if topics.is_empty() {
+        return Err(format!("No help topics mention \"{}\".", word));
+    }
+
+    visual::info(&format!(
+        "Try \"help <topic>\" for {}.",
+        list_phrase(&topics, "or")
+    ));
     Ok(Normal)
 }
 
+/// Returns the scenario's custom direction with the given name, if any.
+fn custom_dir(world: &World, word: &str) -> Option<Dir> {
+    world.directions.get(word).map(|name| Custom(name))
+}
+
 /// Move the player in the given direction
 fn cmd_go(world: &mut World, player: &Player, dir: Dir) -> StatusResult {
+    if let Some(req) = phys::link_requirement(world, player.loc, dir) {
+        if !phys::can(world, player.id, req.capability) {
+            return Err(req.prose);
+        }
+    }
+
+    if let Some(door) = lock::link_door(world, player.loc, dir) {
+        if lock::is_locked(world, door) {
+            match lock::key_for(world, player.id, door) {
+                Some(key) if world.implicit_actions => {
+                    let door = world.thing_id(door).expect("door is a thing");
+                    let key = world.thing_id(key).expect("key is a thing");
+                    if !implicit::unlock(world, player.pid, door, key) {
+                        return Ok(Normal);
+                    }
+                }
+                _ => return Err(sentence(&format!("{} is locked", visual::the_name(world, door)))),
+            }
+        }
+    }
+
+    if dark::stumbles(world, player.pid) {
+        return Ok(Normal);
+    }
+
+    if let Some(trap) = phys::link_trap(world, player.loc, dir) {
+        phys::spring_trap(world, player.pid, trap);
+        return Ok(Normal);
+    }
+
     match phys::follow_link(world, player.loc, dir) {
         Some(LinkDest::Room(dest)) => {
-            phys::enter_room(world, player.id, dest)?;
+            phys::enter_room(world, player.pid, dest)?;
             Ok(Normal)
         },
         Some(LinkDest::DeadEnd(prose)) => {
@@ -140,92 +570,752 @@ fn cmd_go(world: &mut World, player: &Player, dir: Dir) -> StatusResult {
             Ok(Normal)
         }
         None => {
-            Err("You can't go that way.".into())
+            Err("$You $can't go that way.".into())
+        }
+    }
+}
+
+/// Head for a room the player has seen, by name, by the shortest route through rooms
+/// the player has seen.  The player takes the first step of the way or, if the scenario
+/// says so, goes all the way, a turn per step, stopping if anything gets in the way.
+fn cmd_go_to(world: &mut World, player: &Player, name: &[&str]) -> StatusResult {
+    let dest = find_seen_room(world, player, name)?;
+
+    if dest == player.loc {
+        return Err("$You $are already there.".into());
+    }
+
+    let mut route: VecDeque<(Dir, ID)> = phys::route(world, player.id, player.loc, dest)
+        .ok_or_else(|| "$You $don't know the way there.".to_string())?
+        .into();
+
+    // The first step is this turn's; Game::turn() walks the rest, a turn at a time.
+    let (dir, next) = route.pop_front().expect("route to another room");
+    cmd_go(world, player, dir)?;
+
+    if world.go_to_all_the_way && phys::loc(world, player.id) == next {
+        world.walking = route;
+    }
+
+    Ok(Normal)
+}
+
+/// Takes the next step of the route being walked by "go to <room>", if any.  Returns
+/// true if the step took a turn.  A step that fails or goes astray ends the walk.
+pub fn walk(world: &mut World) -> bool {
+    let (dir, next) = match world.walking.pop_front() {
+        Some(step) => step,
+        None => return false,
+    };
+
+    let player = Player {
+        id: world.pid,
+        pid: world.player_id(world.pid).expect("player entity"),
+        loc: phys::loc(world, world.pid),
+    };
+
+    if let Err(msg) = cmd_go(world, &player, dir) {
+        visual::error(&msg);
+        world.walking.clear();
+        return false;
+    }
+
+    if phys::loc(world, player.id) != next {
+        world.walking.clear();
+    }
+
+    true
+}
+
+/// Finds the room the player has seen whose name matches the words, e.g., "clearing"
+/// for "A Dreary Clearing".
+fn find_seen_room(world: &World, player: &Player, name: &[&str]) -> Result<ID, String> {
+    let name_words = |name: &str| -> Vec<String> {
+        name.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty() && !["a", "an", "the"].contains(w))
+            .map(|w| w.to_string())
+            .collect()
+    };
+    let words = name_words(&name.join(" "));
+
+    let mut rooms: Vec<ID> = world
+        .rooms
+        .keys()
+        .cloned()
+        .filter(|id| world.has_flag(player.id, Seen(*id)))
+        .filter(|id| {
+            let room_words = name_words(&world.rooms[id].name);
+            words.iter().all(|w| room_words.contains(w))
+        })
+        .collect();
+    rooms.sort();
+
+    // An exact match beats partial ones.
+    if let Some(id) = rooms.iter().find(|id| name_words(&world.rooms[id].name) == words) {
+        return Ok(*id);
+    }
+
+    match rooms.as_slice() {
+        [] => Err("$You $don't know any such place.".into()),
+        [id] => Ok(*id),
+        _ => {
+            let names: Vec<&str> = rooms.iter().map(|id| world.rooms[id].name.as_str()).collect();
+            Err(format!("Which do $you mean: {}?", list_phrase(&names, "or")))
+        }
+    }
+}
+
+/// Swim in the given direction.  Swimming is just like going, except that there must be
+/// deep water here or there.
+#[cfg(feature = "water")]
+fn cmd_swim(world: &mut World, player: &Player, dir: Dir) -> StatusResult {
+    let dest_is_deep = match phys::follow_link(world, player.loc, dir) {
+        Some(LinkDest::Room(dest)) => water::is_deep(world, dest.id()),
+        _ => false,
+    };
+
+    if !dest_is_deep && !water::is_deep(world, player.loc) {
+        return Err("There's no water to swim in.".into());
+    }
+
+    cmd_go(world, player, dir)
+}
+
+/// Climb a thing.  A climbable thing that leads somewhere takes the player there, just
+/// as following the link would; otherwise, rules decide what happens.
+fn cmd_climb(world: &mut World, player: &Player, name: &str) -> StatusResult {
+    let thing = match find_visible(world, player, "climb", name) {
+        Some(thing) => thing,
+        None => return Err("$You $don't see any such thing.".into()),
+    };
+
+    if !world.has_flag(thing.id(), Climbable) {
+        return Err("$You $can't climb that.".into());
+    }
+
+    if let Some(req) = phys::climb_requirement(world, thing.id()) {
+        if !phys::can(world, player.id, req.capability) {
+            return Err(req.prose);
+        }
+    }
+
+    match phys::climb_dir(world, thing.id()) {
+        Some(dir) => cmd_go(world, player, dir),
+        None => {
+            phys::climb(world, player.pid, thing)?;
+            Ok(Normal)
         }
     }
 }
 
+/// Enter a thing.  Entering a portal takes the player wherever the portal leads.
+fn cmd_enter(world: &mut World, player: &Player, name: &str) -> StatusResult {
+    let thing = match find_visible(world, player, "enter", name) {
+        Some(thing) => thing,
+        None => return Err("$You $don't see any such thing.".into()),
+    };
+
+    if !portal::is_portal(world, thing.id()) {
+        return Err("$You $can't enter that.".into());
+    }
+
+    portal::enter(world, player.pid, thing)?;
+    Ok(Normal)
+}
+
 /// Re-describe the current location.
-fn cmd_look(world: &World, player: &Player) -> StatusResult {
+fn cmd_look(world: &mut World, player: &Player) -> StatusResult {
     visual::room(world, player.loc);
     Ok(Normal)
 }
 
+/// Wash a thing, if there's water to hand.
+fn cmd_wash(world: &mut World, player: &Player, name: &str) -> StatusResult {
+    match find_visible(world, player, "wash", name) {
+        Some(thing) => {
+            wash::wash(world, player.pid, thing)?;
+            Ok(Normal)
+        }
+        None => Err(out_of_reach(world, find_scope(world, player, name))),
+    }
+}
+
+/// Feel around for the things here, e.g., in the dark.
+fn cmd_feel_around(world: &World, player: &Player) -> StatusResult {
+    let ids = phys::non_scenery(world, player.loc);
+
+    if ids.is_empty() {
+        visual::info("$You $feel around, but $find nothing.");
+    } else {
+        visual::info(&format!("$You $feel around, and $find {}.", visual::invent_list(world, &ids)));
+    }
+    Ok(Normal)
+}
+
+/// Feel a thing, to tell what it is, e.g., in the dark.
+fn cmd_feel(world: &World, player: &Player, name: &str) -> StatusResult {
+    match find_visible(world, player, "feel", name) {
+        Some(thing) => {
+            visual::info(&format!("It feels like {}.", visual::a_name(world, thing.id())));
+            Ok(Normal)
+        }
+        None => Err("$You $can't feel any such thing.".into()),
+    }
+}
+
 /// Display the player's inventory.
 fn cmd_inventory(world: &World, player: &Player) -> StatusResult {
     visual::player_inventory(world, player.id);
     Ok(Normal)
 }
 
-/// Describe a thing in the current location.
-fn cmd_examine(world: &World, player: &Player, name: &str) -> StatusResult {
-    if let Some(thing) = find_noun(world, phys::visible(world, player.id), name) {
-        if thing == player.id {
+/// Describe a thing in the current location.  Examining a trapped thing reveals the trap.
+fn cmd_examine(world: &mut World, player: &Player, name: &str) -> StatusResult {
+    if let Some(thing) = find_visible(world, player, "examine", name) {
+        if thing.id() == player.id {
             visual::player(world, player.id);
         } else {
-            visual::thing(world, thing);
+            visual::thing(world, thing.id());
+            if world.has_flag(thing.id(), Trapped) {
+                phys::detect_trap(world, player.pid, thing);
+            }
         }
         Ok(Normal)
+    } else if let Some(thing) = find_distant(world, player, name) {
+        visual::distant_thing(world, thing.id());
+        Ok(Normal)
     } else {
-        Err("You don't see any such thing.".into())
+        Err("$You $don't see any such thing.".into())
     }
 }
 
+/// Examine everything in sight, other than scenery: the things the player is carrying
+/// and the things here.
+fn cmd_examine_all(world: &mut World, player: &Player) -> StatusResult {
+    let mut ids = phys::non_scenery(world, player.id);
+    ids.append(&mut phys::non_scenery(world, player.loc));
+    ids.remove(&player.id);
+
+    if ids.is_empty() {
+        return Err("There's nothing here to examine.".into());
+    }
+
+    let things = phys::in_order(world, &ids);
+    visual::things(world, &things);
+
+    for id in things {
+        if world.has_flag(id, Trapped) {
+            let thing = world.thing_id(id).unwrap();
+            phys::detect_trap(world, player.pid, thing);
+        }
+    }
+
+    Ok(Normal)
+}
+
+/// Search the current location for anything concealed.
+fn cmd_search(world: &mut World, player: &Player) -> StatusResult {
+    let room = match world.room_id(player.loc) {
+        Some(room) => room,
+        None => return Err("$You $can't search here.".into()),
+    };
+
+    phys::search(world, player.pid, room);
+    Ok(Normal)
+}
+
 /// Read a thing in the current location.
 fn cmd_read(world: &mut World, player: &Player, name: &str) -> StatusResult {
-    if let Some(thing) = find_noun(world, phys::visible(world, player.id), name) {
+    if let Some(thing) = find_visible(world, player, "read", name) {
         // If it has no prose, it can't be read
-        if !visual::can_read(world, thing) {
-            return Err("You can't read that.".into());
+        if !visual::can_read(world, thing.id()) {
+            return Err("$You $can't read that.".into());
         }
 
-        // If he's holding it, or it's immovable, then he can read it.
-        if phys::owns(world, player.id, thing) || world.has_flag(thing, Immovable) {
-            phys::read_thing(world, player.id, thing)?;
-            Ok(Normal)
-        } else {
-            Err("You don't have it.".into())
+        // If it's locked, it can't be opened to be read.
+        if lock::is_locked(world, thing.id()) {
+            return Err("It's locked.".into());
+        }
+
+        // If he's holding it, or it's immovable, then he can read it.  Otherwise, he
+        // picks it up first, if the scenario is lenient.
+        if !phys::owns(world, player.id, thing.id()) && !world.has_flag(thing.id(), Immovable) {
+            match world.read_policy {
+                HoldPolicy::Strict => return Err("$You $don't have it.".into()),
+                HoldPolicy::Lenient => {
+                    if !implicit::take(world, player.pid, thing) {
+                        return Ok(Normal);
+                    }
+                }
+            }
         }
+
+        phys::read_thing(world, player.pid, thing)?;
+        Ok(Normal)
+    } else if name == "journal" {
+        // There's no journal here, so he reads his quest journal.
+        visual::journal(world, player.id);
+        Ok(NoTurn)
     } else {
         // It isn't here.
-        Err("You don't see any such thing.".into())
+        Err("$You $don't see any such thing.".into())
     }
 }
 
 /// Gets a thing from the location's inventory.
 fn cmd_get(world: &mut World, player: &Player, noun: &str) -> StatusResult {
-    // Does he already have it?
-    if find_noun(world, phys::contents(world, player.id), noun).is_some() {
-        return Err("You already have that.".into());
-    }
-
-    if find_noun(world, phys::immovable(world, player.loc), noun).is_some() {
-        return Err("You can't take that!".into());
-    }
-
+    // Is there one here he can take?  If he's already holding a thing with the same
+    // noun, this is a different one.
     if let Some(thing) = find_noun(world, phys::gettable(world, player.id), noun) {
         // Get the thing.
-        phys::get_thing(world, player.id, thing)?;
+        phys::get_thing(world, player.pid, thing)?;
         return Ok(Normal);
     }
 
-    Err("You don't see any such thing.".into())
+    // Is the noun a plural, e.g., "coins"?  Then get all of them.
+    if !find_plural(world, phys::gettable(world, player.id), noun).is_empty() {
+        return cmd_get_some(world, player, None, noun);
+    }
+
+    // Otherwise, say what's in the way.
+    match find_scope(world, player, noun) {
+        Scope::Held(_) => Err("$You already $have that.".into()),
+        Scope::Fixed(_) => Err("$You $can't take that!".into()),
+        scope => Err(out_of_reach(world, scope)),
+    }
 }
 
 /// Drops a thing you're carrying
 fn cmd_drop(world: &mut World, player: &Player, noun: &str) -> StatusResult {
-    if let Some(thing) = find_noun(world, phys::droppable(world, player.id), noun) {
-        // Drop the thing
-        phys::put_in(world, thing, player.loc);
-        visual::act("Dropped.");
+    match find_scope(world, player, noun) {
+        Scope::Held(thing) => {
+            // Drop the thing
+            phys::move_thing(world, thing.id(), player.loc);
+            visual::act("Dropped.");
+            #[cfg(feature = "water")]
+            water::sink(world, thing.id());
+            Ok(Normal)
+        }
+        Scope::Fixed(thing) if phys::owns(world, player.id, thing.id()) => {
+            Err("$You $can't drop that!".into())
+        }
+        Scope::Here(_) | Scope::Fixed(_) => Err("$You $aren't carrying that.".into()),
+        Scope::Nowhere if !find_plural(world, phys::droppable(world, player.id), noun).is_empty() => {
+            cmd_drop_some(world, player, None, noun)
+        }
+        scope => Err(out_of_reach(world, scope)),
+    }
+}
+
+/// Gets a number of identical things, e.g., "get two coins", or all of them if the
+/// count is None.
+fn cmd_get_some(world: &mut World, player: &Player, count: Option<usize>, noun: &str) -> StatusResult {
+    let things = find_plural(world, phys::gettable(world, player.id), noun);
+    let things = take_count(world, things, count)?;
+
+    // Stop at the first one the player can't take, e.g., because it's trapped.
+    let mut taken = false;
+    for thing in things {
+        if !phys::get_thing_quietly(world, player.pid, thing) {
+            break;
+        }
+        taken = true;
+    }
+
+    if taken {
+        visual::act("Taken.");
+    }
+
+    Ok(Normal)
+}
+
+/// Drops a number of identical things, e.g., "drop two coins", or all of them if the
+/// count is None.
+fn cmd_drop_some(world: &mut World, player: &Player, count: Option<usize>, noun: &str) -> StatusResult {
+    let things = find_plural(world, phys::droppable(world, player.id), noun);
+
+    for thing in take_count(world, things, count)? {
+        phys::move_thing(world, thing.id(), player.loc);
+        #[cfg(feature = "water")]
+        water::sink(world, thing.id());
+    }
+
+    visual::act("Dropped.");
+    Ok(Normal)
+}
+
+/// Disarm a detected trap using a tool.
+fn cmd_disarm(world: &mut World, player: &Player, name: &str, tool: &str) -> StatusResult {
+    let trap = match find_visible(world, player, "disarm", name) {
+        Some(trap) => trap,
+        None => return Err("$You $don't see any such thing.".into()),
+    };
+
+    if !world.has_flag(trap.id(), Trapped) || !world.has_flag(trap.id(), Detected) {
+        return Err("$You $don't see a trap there.".into());
+    }
+
+    let carried = find_noun(world, phys::contents(world, player.id), tool);
+    let tool = match carried.or_else(|| find_holdable(world, player, tool, None)) {
+        Some(tool) => tool,
+        None => return Err("$You $aren't carrying that.".into()),
+    };
+
+    if !world.has_flag(tool.id(), Can(Capability::Disarm)) {
+        return Err("That won't help $you disarm it.".into());
+    }
+
+    if !hold(world, player, tool) {
+        return Ok(Normal);
+    }
+
+    phys::disarm_trap(world, player.pid, trap)?;
+    Ok(Normal)
+}
+
+/// Push, pull, etc., a thing in the current location.
+fn cmd_manipulate(world: &mut World, player: &Player, name: &str, how: Manipulation) -> StatusResult {
+    if let Some(thing) = find_visible(world, player, how.verb(), name) {
+        if how == Manipulation::Push && platform::is_button(world, thing.id()) {
+            platform::press(world, player.pid, thing);
+        } else {
+            phys::manipulate(world, player.pid, thing, how)?;
+        }
+        Ok(Normal)
+    } else {
+        Err("$You $don't see any such thing.".into())
+    }
+}
+
+/// Lock or unlock a thing, using the given key or any carried key that fits.
+fn cmd_lock(world: &mut World, player: &Player, name: &str, key: Option<&str>, locking: bool) -> StatusResult {
+    let thing = match find_visible(world, player, if locking { "lock" } else { "unlock" }, name) {
+        Some(thing) => thing,
+        None => return Err("$You $don't see any such thing.".into()),
+    };
+
+    if !lock::is_lockable(world, thing.id()) {
+        return Err("It doesn't have a lock.".into());
+    }
+
+    if locking && lock::is_locked(world, thing.id()) {
+        return Err("It's already locked.".into());
+    } else if !locking && !lock::is_locked(world, thing.id()) {
+        return Err("It isn't locked.".into());
+    }
+
+    match key {
+        Some(key) => {
+            let carried = find_noun(world, phys::contents(world, player.id), key);
+            let key = match carried.or_else(|| find_holdable(world, player, key, None)) {
+                Some(key) => key,
+                None => return Err("$You $aren't carrying that.".into()),
+            };
+
+            if !lock::fits(world, key.id(), thing.id()) {
+                return Err("It doesn't fit.".into());
+            }
+
+            if !hold(world, player, key) {
+                return Ok(Normal);
+            }
+        }
+        None => {
+            if lock::key_for(world, player.id, thing.id()).is_none() {
+                return Err("$You $don't have a key that fits.".into());
+            }
+        }
+    }
+
+    if locking {
+        lock::lock(world, player.pid, thing);
+    } else {
+        lock::unlock(world, player.pid, thing);
+    }
+
+    Ok(Normal)
+}
+
+/// Switch a machine on or off.
+#[cfg(feature = "machines")]
+fn cmd_switch(world: &mut World, player: &Player, name: &str, on: bool) -> StatusResult {
+    let thing = match find_visible(world, player, "switch", name) {
+        Some(thing) => thing,
+        None => return Err("$You $don't see any such thing.".into()),
+    };
+
+    if !machine::is_machine(world, thing.id()) {
+        return Err("It doesn't have a switch.".into());
+    }
+
+    if on {
+        if world.has_flag(thing.id(), On) {
+            return Err("It's already on.".into());
+        }
+        machine::switch_on(world, player.pid, thing)?;
+    } else {
+        if !world.has_flag(thing.id(), On) {
+            return Err("It's already off.".into());
+        }
+        machine::switch_off(world, player.pid, thing);
+    }
+
+    Ok(Normal)
+}
+
+/// Put a thing the player is carrying into another thing, e.g., a battery into a machine.
+fn cmd_put_in(world: &mut World, player: &Player, name: &str, container: &str) -> StatusResult {
+    let thing = match find_holdable(world, player, name, None) {
+        Some(thing) => thing,
+        None => return Err(not_carried(world, player, name)),
+    };
+
+    let container = match find_visible(world, player, "put", container) {
+        Some(container) => container,
+        None => return Err(out_of_reach(world, find_scope(world, player, container))),
+    };
+
+    if container.id() == player.id || !world.has_inventory(container.id()) {
+        return Err("$You $can't put things in that.".into());
+    }
+
+    if lock::is_locked(world, container.id()) {
+        return Err(sentence(&format!("{} is locked", visual::the_name(world, container.id()))));
+    }
+
+    if container == thing {
+        return Err("That would be a neat trick.".into());
+    }
+
+    phys::put_thing(world, player.pid, thing, container);
+    Ok(Normal)
+}
+
+/// Get a thing from inside another thing, e.g., a battery from a machine.
+fn cmd_get_from(world: &mut World, player: &Player, name: &str, container: &str) -> StatusResult {
+    let container = match find_visible(world, player, "get", container) {
+        Some(container) => container,
+        None => return Err(out_of_reach(world, find_scope(world, player, container))),
+    };
+
+    if container.id() == player.id || !world.has_inventory(container.id()) {
+        return Err("There's nothing in that.".into());
+    }
+
+    if lock::is_locked(world, container.id()) {
+        return Err(sentence(&format!("{} is locked", visual::the_name(world, container.id()))));
+    }
+
+    match find_noun(world, phys::contents(world, container.id()), name) {
+        Some(thing) => {
+            phys::get_thing(world, player.pid, thing)?;
+            Ok(Normal)
+        }
+        None => Err("It isn't in there.".into()),
+    }
+}
+
+/// Combine two things the player is carrying, e.g., a stick and a rag to make a torch.
+fn cmd_combine(world: &mut World, player: &Player, a: &str, b: &str) -> StatusResult {
+    let a = match find_holdable(world, player, a, None) {
+        Some(thing) => thing,
+        None => return Err(not_carried(world, player, a)),
+    };
+
+    // If the nouns are the same, e.g., "combine stick with stick", look for another one.
+    let b = match find_holdable(world, player, b, Some(a)) {
+        Some(thing) => thing,
+        None => return Err(not_carried(world, player, b)),
+    };
+
+    if a == b {
+        return Err("That would be a neat trick.".into());
+    }
+
+    if !hold(world, player, a) || !hold(world, player, b) {
+        return Ok(Normal);
+    }
+
+    craft::combine(world, player.pid, a, b)?;
+    Ok(Normal)
+}
+
+/// Enter a code on a thing's dial or keypad.
+fn cmd_enter_code(world: &mut World, player: &Player, name: &str, code: &str) -> StatusResult {
+    let thing = match find_visible(world, player, "enter", name) {
+        Some(thing) => thing,
+        None => return Err("$You $don't see any such thing.".into()),
+    };
+
+    if !lock::has_combination(world, thing.id()) {
+        return Err("There's nowhere to enter a code.".into());
+    }
+
+    lock::enter_code(world, player.pid, thing, code);
+    Ok(Normal)
+}
+
+/// Start talking to a thing (or person), and list the topics the player can ask about.
+fn cmd_talk(world: &mut World, player: &Player, name: &str) -> StatusResult {
+    if let Some(thing) = find_visible(world, player, "talk", name) {
+        let id = thing.id();
+        if !world.is_talker(id) {
+            return Err("It doesn't have much to say.".into());
+        }
+
+        world.interlocutor = Some(id);
+        visual::topics(world, id);
         Ok(Normal)
-    } else if find_noun(world, phys::scenery(world, player.id), noun).is_some() {
-        Err("You can't drop that!".into())
-    } else if find_noun(world, phys::visible(world, player.id), noun).is_some() {
-        Err("You aren't carrying that.".into())
     } else {
-        Err("You don't see any such thing.".into())
+        Err("$You $don't see any such thing.".into())
+    }
+}
+
+/// Ask the named thing (or person) about a topic.
+fn cmd_ask(world: &mut World, player: &Player, name: &str, topic: &str) -> StatusResult {
+    if let Some(thing) = find_visible(world, player, "ask", name) {
+        let id = thing.id();
+        if !world.is_talker(id) {
+            return Err("It doesn't have much to say.".into());
+        }
+
+        world.interlocutor = Some(id);
+        ask_about(world, id, topic)
+    } else {
+        Err("$You $don't see any such thing.".into())
+    }
+}
+
+/// Ask the current interlocutor about a topic.
+fn cmd_ask_about(world: &mut World, player: &Player, topic: &str) -> StatusResult {
+    let id = interlocutor(world, player)?;
+    ask_about(world, id, topic)
+}
+
+/// List the topics the current interlocutor can be asked about.
+fn cmd_topics(world: &World, player: &Player) -> StatusResult {
+    let id = interlocutor(world, player)?;
+    visual::topics(world, id);
+    Ok(Normal)
+}
+
+/// Returns the entity the player is talking to, provided that it's still here.
+fn interlocutor(world: &World, player: &Player) -> Result<ID, String> {
+    match world.interlocutor {
+        Some(id) if phys::visible(world, player.id).contains(&id) => Ok(id),
+        _ => Err("$You $aren't talking to anyone.".into()),
+    }
+}
+
+/// Outputs the entity's response to the topic, if it's currently askable.
+fn ask_about(world: &World, id: ID, topic: &str) -> StatusResult {
+    let topicc = &world.topics[&id];
+
+    for t in &topicc.topics {
+        if t.name == topic && (t.predicate)(world) {
+            visual::info(&t.prose);
+            return Ok(Normal);
+        }
+    }
+
+    Err("$You $get no answer.".into())
+}
+
+/// Display the current chapter and its goals.
+fn cmd_chapter(world: &World) -> StatusResult {
+    if let Some(chapter) = world.chapter {
+        visual::chapter(world, chapter);
+        Ok(Normal)
+    } else {
+        Err("This story has no chapters.".into())
+    }
+}
+
+/// Display the status line on demand.
+fn cmd_status(world: &World) -> StatusResult {
+    visual::info(&visual::status_line(world));
+    #[cfg(feature = "effects")]
+    if let Some(effects) = effect::describe(world, world.pid) {
+        visual::info(&format!("$You $are {}.", effects));
+    }
+    Ok(Normal)
+}
+
+/// Display the time: the turn, and how long the player has before any running
+/// deadlines run out.
+#[cfg(feature = "deadlines")]
+fn cmd_time(world: &World) -> StatusResult {
+    let taken = deadline::turns_phrase(world.metrics.turns);
+    visual::info(&format!("$You $have taken {} so far.", taken));
+    if let Some(deadlines) = deadline::describe(world) {
+        visual::info(&format!("$You $have {}.", deadlines));
+    }
+    Ok(NoTurn)
+}
+
+/// Write a note in the player's notes.  The note is the rest of the input, as the player
+/// typed it.
+fn cmd_note(world: &mut World, player: &Player, input: &str) -> StatusResult {
+    let text = input.trim().splitn(2, char::is_whitespace).nth(1).unwrap_or("").trim();
+    let note = Note {
+        turn: world.clock,
+        text: text.into(),
+    };
+
+    world.players.get_mut(&player.id).unwrap().notes.push(note);
+    visual::act("Noted.");
+    Ok(NoTurn)
+}
+
+/// Display the player's notes.
+fn cmd_journal(world: &World, player: &Player) -> StatusResult {
+    visual::notes(world, player.id);
+    Ok(NoTurn)
+}
+
+/// Erase one of the player's notes, by its number in the journal.
+fn cmd_erase_note(world: &mut World, player: &Player, num: &str) -> StatusResult {
+    let notes = &mut world.players.get_mut(&player.id).unwrap().notes;
+
+    match num.parse::<usize>() {
+        Ok(n) if n >= 1 && n <= notes.len() => {
+            notes.remove(n - 1);
+            visual::act("Erased.");
+            Ok(NoTurn)
+        }
+        _ => Err("There's no such note.".into()),
+    }
+}
+
+/// Display the best runs of the scenario.
+fn cmd_records(world: &World) -> StatusResult {
+    visual::records(&records::best(world)?);
+    Ok(NoTurn)
+}
+
+/// Turn the status line on or off.
+fn cmd_status_line(world: &mut World, flag: bool) -> StatusResult {
+    world.status_line = flag;
+
+    if flag {
+        visual::act("The status line is on.");
+    } else {
+        visual::act("The status line is off.");
+    }
+    Ok(Normal)
+}
+
+/// Turn screen-reader friendly output on or off.
+fn cmd_accessible(flag: bool) -> StatusResult {
+    console::set_accessible(flag);
+
+    if flag {
+        visual::act("Accessible output is on.  Use the status command for the status line.");
+    } else {
+        visual::act("Accessible output is off.");
     }
+    Ok(Normal)
 }
 
 /// Undo the last command the game
@@ -238,16 +1328,28 @@ fn cmd_undo(game: &mut Game) -> StatusResult {
     }
 }
 
-/// Restart the game
-fn cmd_restart() -> StatusResult {
+/// Restart the game, once the player confirms.
+fn cmd_restart(world: &World) -> StatusResult {
+    if !world.confirmed {
+        return Ok(Confirm("Are $you sure $you want to start over?".into()));
+    }
+
     visual::act("Restarting...");
     Ok(Restart)
 }
 
+/// Restore the most recent autosave.
+fn cmd_restore_auto() -> StatusResult {
+    if autosave::latest().is_some() {
+        Ok(Restore)
+    } else {
+        Err("There's no autosave to restore.".into())
+    }
+}
+
 /// Quit the game.
 fn cmd_quit() -> StatusResult {
-    visual::act("Bye, then.");
-    ::std::process::exit(0);
+    Ok(Quit)
 }
 
 //------------------------------------------------------------------------------
@@ -264,6 +1366,8 @@ fn handle_debug_command(game: &mut Game, player: &Player, cmd: &Command) -> Stat
         ["look", id_arg] => cmd_debug_look(world, id_arg),
         ["examine", id_arg] => cmd_debug_examine(world, id_arg),
         ["go", id_arg] => cmd_debug_go(world, player, id_arg),
+        ["omniscient"] => cmd_debug_omniscient(world),
+        ["log"] => cmd_debug_log(world),
 
         // Error
         _ => Err("I don't understand.".into()),
@@ -276,6 +1380,12 @@ fn cmd_debug_list(world: &World) -> StatusResult {
     Ok(Normal)
 }
 
+/// Dump the log of recent commands, events, and rule firings.
+fn cmd_debug_log(world: &World) -> StatusResult {
+    debug::dump_log(world);
+    Ok(Normal)
+}
+
 /// Dump information about the given entity, provided the ID string is valid.
 fn cmd_debug_dump(world: &World, id_arg: &str) -> StatusResult {
     let id = parse_id(world, id_arg)?;
@@ -284,7 +1394,7 @@ fn cmd_debug_dump(world: &World, id_arg: &str) -> StatusResult {
 }
 
 /// Describe the room as though the player were in it.
-fn cmd_debug_look(world: &World, id_arg: &str) -> StatusResult {
+fn cmd_debug_look(world: &mut World, id_arg: &str) -> StatusResult {
     let id = parse_id(world, id_arg)?;
     if world.is_room(id) {
         visual::room(world, id);
@@ -295,7 +1405,7 @@ fn cmd_debug_look(world: &World, id_arg: &str) -> StatusResult {
 }
 
 /// Examine the thing fully, as though the player could see it.
-fn cmd_debug_examine(world: &World, id_arg: &str) -> StatusResult {
+fn cmd_debug_examine(world: &mut World, id_arg: &str) -> StatusResult {
     let id = parse_id(world, id_arg)?;
     if world.is_thing(id) {
         visual::thing(world, id);
@@ -308,13 +1418,26 @@ fn cmd_debug_examine(world: &World, id_arg: &str) -> StatusResult {
 /// Take the player to the room.
 fn cmd_debug_go(world: &mut World, player: &Player, id_arg: &str) -> StatusResult {
     let loc = parse_id(world, id_arg)?;
-    if world.is_room(loc) {
-        phys::put_in(world, player.id, loc);
-        visual::room(world, loc);
-        Ok(Normal)
+    match world.room_id(loc) {
+        Some(room) => {
+            phys::move_thing(world, player.id, room.id());
+            visual::room(world, room.id());
+            Ok(Normal)
+        }
+        None => Err(format!("Entity {} is not a room.", loc)),
+    }
+}
+
+/// Toggle omniscient mode, in which the player sees script output from everywhere.
+fn cmd_debug_omniscient(world: &mut World) -> StatusResult {
+    world.omniscient = !world.omniscient;
+
+    if world.omniscient {
+        visual::info("Omniscient mode is on.");
     } else {
-        Err(format!("Entity {} is not a room.", loc))
+        visual::info("Omniscient mode is off.");
     }
+    Ok(Normal)
 }
 
 /// Parse a token as an entity tag or ID, return an ID on success and
@@ -343,18 +1466,50 @@ fn parse_id(world: &World, token: &str) -> Result<ID, String> {
 //-------------------------------------------------------------------------
 // Parsing Tools
 
-/// Finds a noun in the list of things.
-fn find_noun(world: &World, ids: BTreeSet<ID>, noun: &str) -> Option<ID> {
-    for id in ids {
+/// Finds a noun in the list of things.  If several things have the noun, the first
+/// in listing order wins.
+fn find_noun(world: &World, ids: BTreeSet<ID>, noun: &str) -> Option<ThingID> {
+    for id in phys::in_order(world, &ids) {
         let thingc = &world.things[&id];
-        if thingc.noun == noun {
-            return Some(id);
+        if thingc.has_noun(noun) {
+            return world.thing_id(id);
         }
     }
 
     None
 }
 
+/// Finds the things in the list that can be referred to together by the plural noun,
+/// e.g., "coins", in listing order.
+fn find_plural(world: &World, ids: BTreeSet<ID>, noun: &str) -> Vec<ThingID> {
+    phys::in_order(world, &ids)
+        .into_iter()
+        .filter(|id| world.things[id].has_plural_noun(noun))
+        .filter_map(|id| world.thing_id(id))
+        .collect()
+}
+
+/// Takes the given number of things from those found by find_plural(), or all of them
+/// if the count is None.
+fn take_count(world: &World, mut things: Vec<ThingID>, count: Option<usize>) -> Result<Vec<ThingID>, String> {
+    let count = count.unwrap_or(things.len());
+
+    if things.is_empty() {
+        return Err("$You $don't see any such thing.".into());
+    }
+
+    if count > things.len() {
+        let (plural, _) = world.things[&things[0].id()].plural.clone().unwrap_or_default();
+        return Err(match things.len() {
+            1 => format!("There's only {}.", visual::a_name(world, things[0].id())),
+            n => format!("There are only {} {}.", number_word(n), plural),
+        });
+    }
+
+    things.truncate(count);
+    Ok(things)
+}
+
 //-------------------------------------------------------------------------
 // Command Handler
 
@@ -407,25 +1562,306 @@ impl CommandHandler {
         }
     }
 
-    /// Executes the command
-    fn execute(&self, world: &mut World, player: &Player, words: &[&str]) -> CommandResult {
+    /// Executes the world's ith command handler, which matches the words.
+    fn execute(world: &mut World, player: &Player, i: usize, words: &[&str]) -> CommandResult {
+        let handler = &world.command_handlers[i];
+        let hook = handler.hook;
+
         // FIRST, do special checks
-        match &self.pattern {
+        match &handler.pattern {
             CommandPattern::VerbVisible(_) => {
-                if find_noun(world, phys::visible(world, player.id), words[1]).is_none() {
-                    return Err("You don't see any such thing.".into());
+                if find_visible(world, player, words[0], words[1]).is_none() {
+                    return Err("$You $don't see any such thing.".into());
                 }
             }
             _ => ()
         }
 
-        // NEXT, compute the script, returning any error message
-        let script = &mut Script::new();
-        (self.hook)(world, words, script)?;
+        // NEXT, run the hook.
+        run_hook(world, hook, words)
+    }
+}
+
+/// Finds the visible thing with the given noun.  If a thing the player is carrying and a
+/// thing here have the same noun, the verb's preference decides which; most verbs
+/// prefer the carried thing.
+fn find_visible(world: &World, player: &Player, verb: &str, noun: &str) -> Option<ThingID> {
+    let held = phys::named(world, player.id, noun);
+    let here = phys::named(world, player.loc, noun);
+
+    let (first, second) = match world.verb_preferences.get(verb) {
+        Some(Preference::Here) => (here, held),
+        _ => (held, here),
+    };
+
+    find_noun(world, first, noun).or_else(|| find_noun(world, second, noun))
+}
+
+/// Finds the thing with the given noun that the player is carrying, other than the
+/// excepted thing.  If implicit actions are enabled, a thing lying here that the player
+/// could pick up will do as well; see hold().
+fn find_holdable(world: &World, player: &Player, noun: &str, except: Option<ThingID>) -> Option<ThingID> {
+    let without = |mut ids: BTreeSet<ID>| {
+        if let Some(thing) = except {
+            ids.remove(&thing.id());
+        }
+        ids
+    };
+
+    find_noun(world, without(phys::droppable(world, player.id)), noun).or_else(|| {
+        if world.implicit_actions {
+            find_noun(world, without(phys::gettable(world, player.id)), noun)
+        } else {
+            None
+        }
+    })
+}
+
+/// Makes sure that the player is holding a thing found by find_holdable(), taking it
+/// first if need be.  Returns false if the player couldn't take it, in which case the
+/// command should go no further.
+fn hold(world: &mut World, player: &Player, thing: ThingID) -> bool {
+    phys::owns(world, player.id, thing.id()) || implicit::take(world, player.pid, thing)
+}
+
+/// Where a thing the player names is, relative to the player, and so what stands in the
+/// way of handling it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    /// The player is carrying it, and could drop it.
+    Held(ThingID),
+
+    /// It's here, and the player could take it.
+    Here(ThingID),
 
-        // NEXT, execute the script
-        script.execute(world);
+    /// It's here or carried, but it's immovable, e.g., scenery.
+    Fixed(ThingID),
+
+    /// Inside(thing, container): It's inside a container that's here or carried.
+    Inside(ThingID, ThingID),
+
+    /// It's visible from an adjacent room, but out of reach.
+    Distant(ThingID),
+
+    /// There's no such thing in sight.
+    Nowhere,
+}
 
-        Ok(())
+/// Finds the thing with the given noun, and its scope.  Things the player is carrying
+/// come first, then things here, then things inside containers, then distant things.
+fn find_scope(world: &World, player: &Player, noun: &str) -> Scope {
+    if let Some(thing) = find_noun(world, phys::droppable(world, player.id), noun) {
+        return Scope::Held(thing);
+    }
+
+    if let Some(thing) = find_noun(world, phys::gettable(world, player.id), noun) {
+        return Scope::Here(thing);
+    }
+
+    let mut fixed = phys::immovable(world, player.id);
+    fixed.append(&mut phys::immovable(world, player.loc));
+
+    if let Some(thing) = find_noun(world, fixed, noun) {
+        return Scope::Fixed(thing);
+    }
+
+    for id in phys::visible(world, player.id) {
+        if id != player.id && world.has_inventory(id) {
+            if let Some(thing) = find_noun(world, phys::contents(world, id), noun) {
+                return Scope::Inside(thing, world.thing_id(id).unwrap());
+            }
+        }
+    }
+
+    match find_distant(world, player, noun) {
+        Some(thing) => Scope::Distant(thing),
+        None => Scope::Nowhere,
+    }
+}
+
+/// Explains why the player can't reach a thing that isn't carried or here.
+fn out_of_reach(world: &World, scope: Scope) -> String {
+    match scope {
+        Scope::Inside(_, container) if lock::is_locked(world, container.id()) => {
+            format!("It's in {}, which is locked.", visual::the_name(world, container.id()))
+        }
+        Scope::Inside(_, container) => format!("It's in {}.", visual::the_name(world, container.id())),
+        Scope::Distant(_) => "It's too far away.".into(),
+        _ => "$You $don't see any such thing.".into(),
+    }
+}
+
+/// Explains why the player can't use a thing find_holdable() didn't find.
+fn not_carried(world: &World, player: &Player, noun: &str) -> String {
+    match find_scope(world, player, noun) {
+        Scope::Held(_) | Scope::Here(_) | Scope::Fixed(_) => "$You $aren't carrying that.".into(),
+        scope => out_of_reach(world, scope),
+    }
+}
+
+/// Looks for a distant thing the player can see from here with the given noun.
+fn find_distant(world: &World, player: &Player, noun: &str) -> Option<ThingID> {
+    let ids = phys::distant(world, player.id).into_iter().map(|(_, id)| id).collect();
+    find_noun(world, ids, noun)
+}
+
+/// Returns the hook the thing named by the command's second word has for the command's
+/// verb, along with the thing, if the player can see the thing and it has one.
+fn thing_handler(world: &World, player: &Player, words: &[&str]) -> Option<(ThingID, CommandHook)> {
+    if words.len() < 2 {
+        return None;
+    }
+
+    let thing = find_visible(world, player, words[0], words[1])?;
+    let verbc = world.verb_handlers.get(&thing.id())?;
+    verbc.handlers.get(words[0]).map(|hook| (thing, *hook))
+}
+
+/// Runs the command hook, executing its script unless it passed or asked for
+/// confirmation.
+fn run_hook(world: &mut World, hook: &dyn Fn(&WorldQuery, &[&str], &mut Script) -> CommandResult, words: &[&str]) -> CommandResult {
+    // FIRST, compute the script, returning any error message
+    let script = &mut Script::new();
+    let handled = hook(world, words, script)?;
+
+    // NEXT, execute the script, unless the hook passed or asked for confirmation.
+    match handled {
+        Handled::Pass | Handled::Confirm(_) => (),
+        _ => script.execute(world),
+    }
+
+    Ok(handled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeat_error_escalation() {
+        let mut world = Game::new().world;
+        world.repeat_errors.insert(
+            "It's locked.".into(),
+            vec!["It's still locked.".into(), "Seriously, it's locked.".into()],
+        );
+
+        assert_eq!(repeat_error(&world, "It's locked.", 1), "It's locked.");
+        assert_eq!(repeat_error(&world, "It's locked.", 2), "It's still locked.");
+        assert_eq!(repeat_error(&world, "It's locked.", 3), "Seriously, it's locked.");
+
+        // The last text is repeated from then on.
+        assert_eq!(repeat_error(&world, "It's locked.", 9), "Seriously, it's locked.");
+    }
+
+    #[test]
+    fn test_repeat_error_again() {
+        let world = Game::new().world;
+        let msg = "$You $don't see any such thing.";
+
+        assert_eq!(repeat_error(&world, msg, 1), msg);
+        assert_eq!(repeat_error(&world, msg, 2), "Again, $you $don't see any such thing.");
+        assert_eq!(
+            repeat_error(&world, msg, 3),
+            "Again, $you $don't see any such thing.  (\"help\" lists what $you can do.)"
+        );
+    }
+
+    #[test]
+    fn test_repeat_error_i() {
+        let world = Game::new().world;
+
+        assert_eq!(repeat_error(&world, "I don't understand.", 2), "Again, I don't understand.");
+    }
+
+    #[test]
+    #[cfg(feature = "deadlines")]
+    fn test_go_to_takes_a_turn_per_step() {
+        use crate::console;
+        use crate::deadline;
+        use crate::entity::deadline_component::DeadlineComponent;
+
+        console::set_interactive(false);
+
+        let mut game = Game::new();
+        game.world.go_to_all_the_way = true;
+        game.world.systems.insert(TurnSystem::Deadlines);
+        game.introduce();
+        for cmd in ["s", "s", "w", "n"] {
+            game.step(cmd).unwrap();
+        }
+
+        let fuse = game.world.alloc("fuse");
+        game.world.deadlines.insert(fuse, DeadlineComponent::new("for the fuse", 5));
+        deadline::start(&mut game.world, fuse);
+        let clock = game.world.clock;
+
+        // Two rooms away: the fuse burns for each step.
+        game.step("go to cave mouth").unwrap();
+        assert_eq!(game.world.tag(phys::loc(&game.world, game.world.pid)), "cave-mouth");
+        assert_eq!(game.world.clock, clock + 2);
+        assert_eq!(deadline::turns_left(&game.world, fuse), Some(3));
+
+        // The walk stops when the fuse runs out on the way.
+        game.world.deadlines.get_mut(&fuse).unwrap().left = Some(1);
+        game.step("go to clearing").unwrap();
+        assert_eq!(game.world.tag(phys::loc(&game.world, game.world.pid)), "hilltop");
+        assert!(game.world.has_flag(game.world.pid, Dead));
+        assert!(game.world.walking.is_empty());
+    }
+
+    #[test]
+    fn test_help_pages_match_commands() {
+        use crate::console;
+
+        console::set_interactive(false);
+
+        // FIRST, every built-in verb has a help page, except for the debugging commands.
+        let world = World::new();
+        for verb in world.verbs.iter().filter(|verb| world.synonyms[*verb] == **verb) {
+            if !["list", "dump", "omniscient", "log"].contains(&verb.as_str()) {
+                assert!(help::page(&world, verb).is_some(), "No help page for: {}", verb);
+            }
+        }
+
+        // NEXT, every form of every page is a command the dispatcher handles.
+        let mut game = Game::new();
+        game.introduce();
+        let world = &game.world;
+        let mut forms: Vec<String> = Vec::new();
+        for page in world.help.values() {
+            let usage = match &page.usage {
+                Some(usage) => usage,
+                None => continue,
+            };
+            for form in usage.split(|c| c == ',' || c == ';').map(|form| form.trim()) {
+                if !form.starts_with("or ") {
+                    forms.push(form.into());
+                }
+            }
+        }
+
+        for form in forms {
+            let input: Vec<&str> = form
+                .split_whitespace()
+                .map(|word| match word {
+                    "<direction>" => "north",
+                    "<number>" | "<code>" => "1",
+                    "<text>" => "hello",
+                    word if word.starts_with('<') => "xyzzy",
+                    word => word,
+                })
+                .collect();
+            let player = Player {
+                id: game.world.pid,
+                pid: game.world.player_id(game.world.pid).unwrap(),
+                loc: phys::loc(&game.world, game.world.pid),
+            };
+            let result = handle_input(&mut game, &player, &input.join(" "));
+            assert!(
+                !matches!(&result, Err(msg) if msg == "I don't understand."),
+                "No handler for: {}",
+                form
+            );
+        }
     }
 }
@@ -0,0 +1,62 @@
+//! The Portal System
+//!
+//! A portal is a thing the player can enter to be taken somewhere else entirely: a
+//! magic mirror, a painting, a shimmering archway.  Each portal leads to another
+//! portal, and entering it takes the player to the room that portal is in, just as
+//! following a link would.  Rules on the EnterPortal event can forbid the trip, e.g.,
+//! until the mirror has been polished, or add to it.
+
+use crate::entity::ID;
+use crate::entity::PlayerID;
+use crate::entity::ThingID;
+use crate::phys;
+use crate::rule;
+use crate::types::Event::*;
+use crate::visual;
+use crate::world::World;
+use crate::world::LIMBO;
+
+/// Is the thing a portal?
+pub fn is_portal(world: &World, thing: ID) -> bool {
+    world.portals.get(&thing).is_some()
+}
+
+/// Returns the room the portal currently leads to: the room its far end is in, if the
+/// portal leads anywhere and the far end is in play.
+pub fn dest_room(world: &World, portal: ID) -> Option<ID> {
+    let mut id = world.portals.get(&portal)?.dest?;
+
+    while !world.is_room(id) {
+        id = phys::loc(world, id);
+
+        if id == LIMBO {
+            return None;
+        }
+    }
+
+    Some(id)
+}
+
+/// The player enters the portal, and is taken to the room its far end is in.  If the
+/// portal leads nowhere, the player gets nowhere.
+pub fn enter(world: &mut World, pid: PlayerID, portal: ThingID) -> Result<(), String> {
+    let event = EnterPortal(pid.id(), portal.id());
+
+    let dest = match dest_room(world, portal.id()) {
+        Some(dest) => dest,
+        None => {
+            visual::info("$You $get nowhere.");
+            return Ok(());
+        }
+    };
+
+    if rule::allows(world, &event) {
+        visual::act(&format!("$You $step through {}.", visual::the_name(world, portal.id())));
+
+        let room = world.room_id(dest).expect("portal leads to a room");
+        phys::enter_room(world, pid, room)?;
+        rule::fire_event(world, &event);
+    }
+
+    Ok(())
+}
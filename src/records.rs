@@ -0,0 +1,178 @@
+//! Records of Best Completions
+//!
+//! When the player wins, the run's turns and score are added to a local records file,
+//! which holds the best runs for each scenario, keyed by the scenario's title.  The
+//! scenario chooses how runs are ranked; see Ranking.  The "records" command displays
+//! them.
+//!
+//! Records are only kept once a file has been set with set_path(), as the game does
+//! for interactive play; automated runs, e.g., walkthroughs and tests, leave no files
+//! behind.
+
+use crate::types::Ranking;
+use crate::world::World;
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+
+/// The default records file, in the current directory.
+pub const RECORDS_FILE: &str = "bonaventure-records.txt";
+
+/// The number of runs kept for each scenario.
+pub const MAX_RECORDS: usize = 10;
+
+thread_local! {
+    /// The records file, if records are being kept.
+    static PATH: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// A completed run of a scenario.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    /// The scenario's title.
+    pub scenario: String,
+
+    /// The number of turns taken.
+    pub turns: usize,
+
+    /// The final score.
+    pub score: i32,
+}
+
+/// Sets the records file, or stops keeping records if None.
+pub fn set_path(path: Option<&str>) {
+    PATH.with(|p| *p.borrow_mut() = path.map(|path| path.to_string()));
+}
+
+/// Returns the records file, if records are being kept.
+pub fn path() -> Option<String> {
+    PATH.with(|p| p.borrow().clone())
+}
+
+/// Adds the player's winning run to the records file.  Returns the run's rank among
+/// the scenario's best runs, starting at 1, or None if it didn't make the list or
+/// records aren't being kept.
+pub fn record(world: &World) -> Result<Option<usize>, String> {
+    let path = match path() {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+
+    let run = Record {
+        scenario: world.title.clone(),
+        turns: world.metrics.turns,
+        score: world.score,
+    };
+
+    let mut records = load(&path).map_err(|err| format!("Cannot read {}: {}", path, err))?;
+    records.push(run.clone());
+    let (mine, others): (Vec<Record>, Vec<Record>) =
+        records.into_iter().partition(|r| r.scenario == run.scenario);
+
+    let mine = rank(mine, world.ranking);
+    let place = mine.iter().position(|r| *r == run).map(|i| i + 1);
+
+    let mut records = others;
+    records.extend(mine);
+    fs::write(&path, format_records(&records))
+        .map_err(|err| format!("Cannot write {}: {}", path, err))?;
+
+    Ok(place)
+}
+
+/// Returns the best runs of the world's scenario, in rank order.
+pub fn best(world: &World) -> Result<Vec<Record>, String> {
+    let path = match path() {
+        Some(path) => path,
+        None => return Ok(Vec::new()),
+    };
+
+    let records = load(&path).map_err(|err| format!("Cannot read {}: {}", path, err))?;
+    let mine = records.into_iter().filter(|r| r.scenario == world.title).collect();
+
+    Ok(rank(mine, world.ranking))
+}
+
+/// Loads the records from the file.  A missing file has no records.
+fn load(path: &str) -> io::Result<Vec<Record>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(parse_records(&text)),
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Sorts one scenario's runs best first, and keeps the best MAX_RECORDS of them.
+/// Ties go to the earlier run.
+fn rank(mut records: Vec<Record>, ranking: Ranking) -> Vec<Record> {
+    match ranking {
+        Ranking::FewestTurns => records.sort_by_key(|r| (r.turns, -r.score)),
+        Ranking::HighestScore => records.sort_by_key(|r| (-r.score, r.turns)),
+    }
+
+    records.truncate(MAX_RECORDS);
+    records
+}
+
+/// Parses the records file: one run per line, as turns, score, and scenario title,
+/// separated by tabs.  Malformed lines are ignored.
+fn parse_records(text: &str) -> Vec<Record> {
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let turns = fields.next()?.parse().ok()?;
+            let score = fields.next()?.parse().ok()?;
+            let scenario = fields.next()?.to_string();
+            Some(Record { scenario, turns, score })
+        })
+        .collect()
+}
+
+/// Formats the records for the records file.
+fn format_records(records: &[Record]) -> String {
+    let mut text = String::new();
+
+    for r in records {
+        text.push_str(&format!("{}\t{}\t{}\n", r.turns, r.score, r.scenario));
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(turns: usize, score: i32) -> Record {
+        Record {
+            scenario: "Test".into(),
+            turns,
+            score,
+        }
+    }
+
+    #[test]
+    fn test_parse_format() {
+        let records = vec![run(87, 20), run(90, 25)];
+        let text = format_records(&records);
+        assert_eq!(text, "87\t20\tTest\n90\t25\tTest\n");
+        assert_eq!(parse_records(&text), records);
+        assert_eq!(parse_records("bogus\n"), Vec::new());
+    }
+
+    #[test]
+    fn test_rank() {
+        let records = vec![run(90, 25), run(87, 20), run(87, 30)];
+        assert_eq!(
+            rank(records.clone(), Ranking::FewestTurns),
+            vec![run(87, 30), run(87, 20), run(90, 25)]
+        );
+        assert_eq!(
+            rank(records, Ranking::HighestScore),
+            vec![run(87, 30), run(90, 25), run(87, 20)]
+        );
+
+        let many = (0..20).map(|i| run(i, 0)).collect();
+        assert_eq!(rank(many, Ranking::FewestTurns).len(), MAX_RECORDS);
+    }
+}
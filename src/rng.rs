@@ -0,0 +1,77 @@
+//! A small, seeded pseudo-random number generator.
+//!
+//! The generator's state lives in the World, so that random outcomes are reproducible
+//! given the scenario's seed, and so that undo restores the generator along with
+//! everything else.  It's an xorshift64* generator: not suitable for cryptography,
+//! but plenty good enough for wandering monsters.
+
+/// The seed used if the scenario doesn't provide one.
+const DEFAULT_SEED: u64 = 0x2545_F491_4F6C_DD1D;
+
+/// A seeded pseudo-random number generator.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a new generator with the given seed.  A zero seed is replaced
+    /// with the default seed, as xorshift generators can't start from zero.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: if seed == 0 { DEFAULT_SEED } else { seed },
+        }
+    }
+
+    /// Returns the next pseudo-random number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Returns a pseudo-random number in the range 0..n.
+    ///
+    /// * Panics if n is 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Rng::below(0)");
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+
+        for _ in 0..10 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn zero_seed() {
+        let mut rng = Rng::new(0);
+        assert_ne!(rng.next_u64(), 0);
+    }
+
+    #[test]
+    fn below() {
+        let mut rng = Rng::new(7);
+
+        for _ in 0..100 {
+            assert!(rng.below(6) < 6);
+        }
+    }
+}
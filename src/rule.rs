@@ -1,63 +1,468 @@
 //! Rule Monitor System
 
 use crate::entity::ID;
+use crate::phys;
 use crate::types::Event;
+use crate::types::Outcome;
 use crate::types::Flag::*;
+use crate::world::LIMBO;
 use crate::world::World;
+use std::mem::discriminant;
 
 /// Executes the guard that applies to the given event (if any), and returns
 /// whether or not the event is allowed.  If the event is denied, the guard's
-/// script is executed.
+/// script is executed.  A guard that would redirect the event denies it here; see
+/// outcome().
 pub fn allows(world: &mut World, event: &Event) -> bool {
+    outcome(world, event) == Outcome::Allow
+}
+
+/// Executes the guard that applies to the given event (if any), and returns its
+/// outcome: the event is allowed, denied, or redirected elsewhere.  Unless the event
+/// is allowed, the guard's script is executed.
+pub fn outcome(world: &mut World, event: &Event) -> Outcome {
     for id in world.rules.keys().cloned() {
         let rulec = &world.rules[&id];
-        if rulec.is_guard && event == &rulec.event {
+        if rulec.is_guard && matches(world, id, event) && in_chapter(world, id) {
             if (rulec.predicate)(world) {
-                // The action is not allowed; execute the script.
+                // The action is not allowed as is; execute the script.
                 let script = rulec.script.clone();
-                script.execute(world);
-                return false;
+                let outcome = rulec.redirect.map_or(Outcome::Deny, Outcome::Redirect);
+                world.log(&format!("Guard {} made {:?} of {:?}", world.tag(id), outcome, event));
+                script.execute_on(world, event);
+                return outcome;
             } else {
                 // The action is allowed.
-                return true;
+                return Outcome::Allow;
             }
         }
     }
 
     // NEXT, no guard matches; carry on normally.
-    true
+    Outcome::Allow
 }
 
 /// Fire all rules for the given event, and execute those whose predicates are met.
-pub fn fire_event(world: &mut World, event: &Event) {
-    fire_events(world, &[event]);
+/// Returns true if any rule fired.
+pub fn fire_event(world: &mut World, event: &Event) -> bool {
+    fire_events(world, &[event])
 }
 
 /// Fire all rules whose events are in the events set, and execute those whose
-/// predicates are met.
-pub fn fire_events(world: &mut World, events: &[&Event]) {
+/// predicates are met.  Returns true if any rule fired.
+pub fn fire_events(world: &mut World, events: &[&Event]) -> bool {
+    for event in events {
+        world.log(&format!("Event: {:?}", event));
+    }
+
     let rules: Vec<ID> = world
         .rules
         .keys()
         .cloned()
-        .filter(|id| !world.has_flag(*id, FireOnce) || !world.has_flag(*id, Fired))
+        .filter(|id| is_active(world, *id))
         .collect();
 
+    let mut fired = false;
+
     for id in rules {
         let rulec = &world.rules[&id];
-        if !rulec.is_guard
-            && events.contains(&&rulec.event)
-            && (rulec.predicate)(world)
-        {
-            fire_rule(world, id);
+        let event = events.iter().find(|event| matches(world, id, event));
+
+        if let Some(event) = event {
+            if !rulec.is_guard && (rulec.predicate)(world) {
+                fire_rule(world, id, event);
+                fired = true;
+            }
+        }
+    }
+
+    fired
+}
+
+/// Would the tagged rule fire for the given event in the world's current state?  That
+/// is, does the rule apply to the event, can it fire at present, and is its predicate
+/// met?  For a guard, this means that the guard would deny the event.  Panics if there
+/// is no such rule.
+///
+/// This is for scenario tests, which can check a rule's predicate in isolation without
+/// playing the game up to the point where it matters.
+#[cfg(test)]
+pub fn would_fire(world: &World, rule_tag: &str, event: &Event) -> bool {
+    let id = lookup_rule(world, rule_tag);
+    let rulec = &world.rules[&id];
+
+    matches(world, id, event) && is_active(world, id) && (rulec.predicate)(world)
+}
+
+/// Fires the tagged rule as though its own event had occurred, if it can fire at present
+/// and its predicate is met.  Returns true if it fired.  Panics if there is no such
+/// rule.
+///
+/// This is for scenario tests, which can check a rule's script in isolation without
+/// playing the game up to the point where it matters.
+#[cfg(test)]
+pub fn test_fire(world: &mut World, rule_tag: &str) -> bool {
+    let id = lookup_rule(world, rule_tag);
+    let rulec = &world.rules[&id];
+
+    if is_active(world, id) && (rulec.predicate)(world) {
+        let event = rulec.event.clone();
+        fire_rule(world, id, &event);
+        true
+    } else {
+        false
+    }
+}
+
+/// Looks up the tagged rule's ID.  Panics if there is no such rule.
+#[cfg(test)]
+fn lookup_rule(world: &World, rule_tag: &str) -> ID {
+    let id = world.lookup(rule_tag);
+    assert!(world.rules.contains_key(&id), "Not a rule: {}", rule_tag);
+    id
+}
+
+/// Roll each random event table that applies to the player's location.  If a table comes
+/// up, choose one of its events by weight (among those whose predicates are met) and
+/// fire it.
+pub fn roll_random_tables(world: &mut World) {
+    let here = phys::loc(world, world.pid);
+    let tables: Vec<ID> = world.random_tables.keys().cloned().collect();
+
+    for tid in tables {
+        let tablec = &world.random_tables[&tid];
+
+        // FIRST, is the player in the table's region?
+        if let Some(flag) = tablec.region {
+            if !world.has_flags(here) || !world.has_flag(here, flag) {
+                continue;
+            }
+        }
+
+        // NEXT, is the table cooling down?
+        if let Some(last) = tablec.last_fired {
+            if world.clock < last + tablec.cooldown {
+                continue;
+            }
         }
+
+        // NEXT, does the table come up this turn?
+        let chance = tablec.chance;
+        if world.rng.below(100) >= chance {
+            continue;
+        }
+
+        // NEXT, get the candidate events.
+        let event = Event::Random(tid);
+        let candidates: Vec<ID> = world
+            .rules
+            .keys()
+            .cloned()
+            .filter(|id| is_active(world, *id))
+            .filter(|id| {
+                let rulec = &world.rules[id];
+                !rulec.is_guard && matches(world, *id, &event) && (rulec.predicate)(world)
+            })
+            .collect();
+
+        let total: usize = candidates.iter().map(|id| world.rules[id].weight).sum();
+
+        if total == 0 {
+            continue;
+        }
+
+        // NEXT, choose one by weight, and fire it.
+        let mut roll = world.rng.below(total);
+
+        for id in candidates {
+            let weight = world.rules[&id].weight;
+            if roll < weight {
+                fire_rule(world, id, &event);
+                break;
+            }
+            roll -= weight;
+        }
+
+        world.random_tables.get_mut(&tid).unwrap().last_fired = Some(world.clock);
     }
 }
 
+/// Fires an InventoryChanged event for each player whose inventory has changed since
+/// the last call.
+pub fn fire_inventory_events(world: &mut World) {
+    let changes = std::mem::take(&mut world.inventory_changes);
+
+    for pid in changes {
+        fire_event(world, &Event::InventoryChanged(pid));
+    }
+}
 
-/// Execute the given rule
-fn fire_rule(world: &mut World, id: ID) {
+/// Fires a PlatePressed or PlateReleased event for each pressure plate whose load has
+/// crossed its threshold since the last call.
+pub fn fire_plate_events(world: &mut World) {
+    let plates: Vec<ID> = world.plates.keys().cloned().collect();
+
+    for plate in plates {
+        let pressed = phys::load(world, plate) >= world.plates[&plate].threshold;
+
+        if pressed != world.plates[&plate].pressed {
+            world.plates.get_mut(&plate).unwrap().pressed = pressed;
+
+            if pressed {
+                fire_event(world, &Event::PlatePressed(plate));
+            } else {
+                fire_event(world, &Event::PlateReleased(plate));
+            }
+        }
+    }
+}
+
+/// Does the rule apply to the event?  A rule for a class of entities applies to any
+/// event of the same kind whose subject is in the class, a theft rule for LIMBO
+/// applies to the theft of any of the owner's things, and a get rule for LIMBO applies
+/// to getting the thing into anything.
+fn matches(world: &World, id: ID, event: &Event) -> bool {
+    let rulec = &world.rules[&id];
+
+    match rulec.class {
+        Some(flag) => {
+            discriminant(&rulec.event) == discriminant(event)
+                && event.subject().map_or(false, |subject| {
+                    world.has_flags(subject) && world.has_flag(subject, flag)
+                })
+        }
+        None => match (&rulec.event, event) {
+            (Event::Steal(p1, o1, LIMBO), Event::Steal(p2, o2, _)) => p1 == p2 && o1 == o2,
+            (Event::GetThing(p1, t1, LIMBO), Event::GetThing(p2, t2, _)) => p1 == p2 && t1 == t2,
+            (pattern, event) => pattern == event,
+        },
+    }
+}
+
+/// Can the rule fire at present?  A rule can't fire if it's already fired and is
+/// once-only, or if it's scoped to a chapter other than the current one.
+fn is_active(world: &World, id: ID) -> bool {
+    (!world.has_flag(id, FireOnce) || !world.has_flag(id, Fired))
+        && in_chapter(world, id)
+        && !is_cooling_down(world, id)
+        && !is_fired_this_visit(world, id)
+}
+
+/// Is the rule cooling down, having fired within its cooldown?
+fn is_cooling_down(world: &World, id: ID) -> bool {
+    let rulec = &world.rules[&id];
+
+    match rulec.last_fired {
+        Some(last) => rulec.cooldown > 0 && world.clock <= last + rulec.cooldown,
+        None => false,
+    }
+}
+
+/// Is the rule in scope for the current chapter?
+fn in_chapter(world: &World, id: ID) -> bool {
+    match world.rules[&id].chapter {
+        Some(chapter) => world.chapter == Some(chapter),
+        None => true,
+    }
+}
+
+/// Has the rule already fired during the player's current visit to a room, if it's a
+/// once-per-visit rule?
+fn is_fired_this_visit(world: &World, id: ID) -> bool {
+    let rulec = &world.rules[&id];
+    rulec.once_per_visit && rulec.fired_visit.is_some() && rulec.fired_visit == visit(world)
+}
+
+/// Returns the room the player is in and the number of the player's visit to it, or
+/// None if the player isn't in a room.
+fn visit(world: &World) -> Option<(ID, usize)> {
+    let here = phys::loc(world, world.pid);
+    world.rooms.get(&here).map(|roomc| (here, roomc.visits))
+}
+
+/// Execute the given rule, which was triggered by the event.
+fn fire_rule(world: &mut World, id: ID, event: &Event) {
     let script = world.rules[&id].script.clone();
-    script.execute(world);
+    world.log(&format!("Rule {} fired", world.tag(id)));
+    script.execute_on(world, event);
     world.set_flag(id, Fired);
+    let visit = visit(world);
+    let rulec = world.rules.get_mut(&id).unwrap();
+    rulec.last_fired = Some(world.clock);
+    rulec.fired_visit = visit;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console;
+    use crate::visual;
+    use crate::wash;
+    use crate::world::WorldQuery;
+    use crate::world::LIMBO;
+    use crate::world_builder::WBEvent;
+    use crate::world_builder::WorldBuilder;
+    use crate::types::Once;
+    use crate::Game;
+
+    /// Builds a small world for testing rules: the player in the clearing, with the
+    /// grotto and the hilltop nearby.  The build function adds the rules under test.
+    fn world_with(build: impl FnOnce(&mut WorldBuilder)) -> World {
+        let mut wb = WorldBuilder::new();
+        wb.player().location("clearing");
+        wb.room("clearing", "Clearing").prose("A clearing in the woods.");
+        wb.room("grotto", "Grotto").prose("A damp grotto.");
+        wb.room("hilltop", "Hilltop").prose("A windy hilltop.");
+        build(&mut wb);
+        wb.world()
+    }
+
+    #[test]
+    fn test_would_fire() {
+        let mut world = Game::new().world;
+        let get_sword = Event::GetThing(world.pid, world.lookup("sword"), world.pid);
+        let enter_cave = Event::EnterRoom(world.pid, world.lookup("cave-1"));
+
+        // The guard denies the sword to a player with dirty hands.
+        assert!(would_fire(&world, "allow-get-sword", &get_sword));
+        assert!(!would_fire(&world, "allow-get-sword", &enter_cave));
+
+        let hands = world.lookup("hands");
+        let dirty = wash::dirt_flag(&world);
+        world.unset_flag(hands, dirty);
+        assert!(!would_fire(&world, "allow-get-sword", &get_sword));
+    }
+
+    #[test]
+    fn test_test_fire() {
+        console::set_interactive(false);
+
+        let mut world = Game::new().world;
+        let stone = world.lookup("stone");
+        let rule = world.lookup("on-get-sword");
+
+        assert!(test_fire(&mut world, "on-get-sword"));
+        assert_eq!(phys::loc(&world, stone), LIMBO);
+        assert!(world.has_flag(rule, Fired));
+
+        // It's a once-only rule.
+        assert!(!test_fire(&mut world, "on-get-sword"));
+    }
+
+    #[test]
+    fn test_cooldown() {
+        console::set_interactive(false);
+
+        let mut world = world_with(|wb| {
+            wb.rule("bird").cooldown(2).print("A bird sings.");
+        });
+
+        assert!(test_fire(&mut world, "bird"));
+
+        // It can't fire again for two turns.
+        world.clock += 1;
+        assert!(!test_fire(&mut world, "bird"));
+        world.clock += 1;
+        assert!(!test_fire(&mut world, "bird"));
+        world.clock += 1;
+        assert!(test_fire(&mut world, "bird"));
+    }
+
+    #[test]
+    fn test_once_per_visit() {
+        console::set_interactive(false);
+
+        let mut world = world_with(|wb| {
+            wb.rule("bird").once_per(Once::Visit).print("A bird sings.");
+            wb.rule("owl").once_per(Once::Game).print("An owl hoots.");
+        });
+        let pid = world.player_id(world.pid).unwrap();
+        let clearing = world.room_id(world.lookup("clearing")).unwrap();
+        let grotto = world.room_id(world.lookup("grotto")).unwrap();
+
+        assert!(test_fire(&mut world, "bird"));
+        assert!(!test_fire(&mut world, "bird"));
+        assert!(test_fire(&mut world, "owl"));
+        assert!(!test_fire(&mut world, "owl"));
+
+        // The bird can sing again on the next visit, but the owl is done.
+        phys::enter_room(&mut world, pid, grotto).unwrap();
+        assert!(test_fire(&mut world, "bird"));
+        phys::enter_room(&mut world, pid, clearing).unwrap();
+        assert!(test_fire(&mut world, "bird"));
+        assert!(!test_fire(&mut world, "bird"));
+        assert!(!test_fire(&mut world, "owl"));
+
+        // The start of the game counts as a visit.
+        assert_eq!(world.visits("clearing"), 2);
+        assert_eq!(world.visits("grotto"), 1);
+        assert_eq!(world.visits("hilltop"), 0);
+        assert_eq!(visual::expand(&world, "Visit {visits}."), "Visit 2.");
+    }
+
+    #[test]
+    fn test_redirect() {
+        console::set_interactive(false);
+
+        // The cave sends the player back to the hilltop.
+        let mut world = world_with(|wb| {
+            wb.room("cave", "Cave").prose("A dark cave.");
+            wb.allow(&WBEvent::EnterRoom("cave"))
+                .redirect("hilltop")
+                .print("$You $find $yourself back on the hilltop.");
+        });
+        let pid = world.player_id(world.pid).unwrap();
+        let cave = world.room_id(world.lookup("cave")).unwrap();
+        let hilltop = world.lookup("hilltop");
+        let event = Event::EnterRoom(pid.id(), cave.id());
+
+        assert_eq!(outcome(&mut world, &event), Outcome::Redirect(hilltop));
+        assert!(!allows(&mut world, &event));
+
+        phys::enter_room(&mut world, pid, cave).unwrap();
+        assert_eq!(phys::loc(&world, pid.id()), hilltop);
+    }
+
+    #[test]
+    fn test_redirect_cycle() {
+        console::set_interactive(false);
+
+        // The grotto sends the player to the hilltop, and the hilltop sends them back.
+        let mut world = world_with(|wb| {
+            wb.allow(&WBEvent::EnterRoom("grotto")).redirect("hilltop");
+            wb.allow(&WBEvent::EnterRoom("hilltop")).redirect("grotto");
+        });
+        let pid = world.player_id(world.pid).unwrap();
+        let start = phys::loc(&world, pid.id());
+        let grotto = world.room_id(world.lookup("grotto")).unwrap();
+
+        // The cycle is a denial; the player stays put.
+        phys::enter_room(&mut world, pid, grotto).unwrap();
+        assert_eq!(phys::loc(&world, pid.id()), start);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot set 'redirect' on normal rule")]
+    fn test_redirect_normal_rule() {
+        world_with(|wb| {
+            wb.on(&WBEvent::EnterRoom("grotto")).redirect("hilltop");
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot set 'redirect' on a guard for anything but EnterRoom")]
+    fn test_redirect_other_event() {
+        world_with(|wb| {
+            wb.thing("stone", "stone", "stone").location("clearing");
+            wb.allow(&WBEvent::GetThing("stone")).redirect("hilltop");
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot 'redirect' a guard to the room it guards")]
+    fn test_redirect_to_itself() {
+        world_with(|wb| {
+            wb.allow(&WBEvent::EnterRoom("grotto")).redirect("grotto");
+        });
+    }
 }
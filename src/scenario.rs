@@ -3,42 +3,44 @@
 use crate::types::Dir::*;
 use crate::types::Flag;
 use crate::types::Flag::*;
-use crate::types::ProseBuffer;
 use crate::world::World;
 use crate::world_builder::*;
 use crate::world_builder::WBEvent::*;
 
 // User-defined flags
 const DIRTY: Flag = User("DIRTY");
-const HAS_WATER: Flag = User("HAS_WATER");
 const TAKEN: Flag = User("TAKEN");
+const WOODS: Flag = User("WOODS");
 
 /// Build the initial state of the game world.
 pub fn build() -> World {
+    builder().world()
+}
+
+/// Defines the scenario, returning the world builder so that the world can be built or
+/// reviewed; see WorldBuilder::report().
+pub fn builder() -> WorldBuilder {
     // FIRST, create the world builder
     let mut wb = WorldBuilder::new();
 
     // NEXT, configure the player
     wb.player()
         .location("clearing")
-        .on_examine("You've got all the usual bits.");
-
-    wb.feature("hands", "hands", "hands")
-        .location(PLAYER)
+        .on_examine("You've got all the usual bits.")
+        .feature("hands", "hands", "hands")
         .flag(DIRTY)
         .on_examine_hook(&|w,e,buff| {
-            if w.has(e, DIRTY) {
-                buff.puts("You don't remember what you were doing, but it must have been messy.");
-            } else {
-                buff.puts("Fresh and clean.");
-            }
+            buff.when_else(w.has(e, DIRTY),
+                "You don't remember what you were doing, but it must have been messy.",
+                "Fresh and clean.");
         })
         .on_scenery_hook(&|w,e,buff| {
-            if w.has(e, DIRTY) {
-                buff.puts("Your hands are kind of dirty, though.");
-            }
+            buff.when(w.has(e, DIRTY), "Your hands are kind of dirty, though.");
         });
 
+    // The player washes things with the standard "wash" verb, which clears DIRTY.
+    wb.dirt_flag(DIRTY);
+
     // NEXT, create and configure the things in the world.
 
     // Rule: Story 1
@@ -63,7 +65,8 @@ what's back under the trees to the north, but you're pretty sure you didn't like
 and that you don't want to go find it again.
         ")
         .link(East, "grotto")
-        .link(South, "hilltop");
+        .link(South, "hilltop")
+        .flag(WOODS);
 
     // Thing: A ransom note, found in the clearing
     wb.thing("note", "note", "note")
@@ -86,11 +89,9 @@ under the statue in the castle courtyard before nine o'clock tomorrow morning.
         .unless(&|w| w.has("note", DIRTY))
         .print("You've gotten it too dirty to read.");
 
-    // The note gets dirty if the player picks it up with dirty hands.
-    wb.on(&GetThing("note"))
-        .when(&|w| w.has("hands", DIRTY) && !w.has("note", DIRTY))
-        .print("The dirt from your hands got all over the note.")
-        .set_flag("note", DIRTY);
+    // The note gets dirty if the player picks it up with dirty hands; and so does
+    // anything else.
+    wb.contamination(true);
 
     // Room: Grotto
     wb.room("grotto", "A Grotto in the Woods")
@@ -99,7 +100,8 @@ under the statue in the castle courtyard before nine o'clock tomorrow morning.
 Nestled in a grotto among the trees you find a pool of water.
 A path leads west.
         ")
-        .flag(HAS_WATER);
+        .flag(HasWater)
+        .flag(WOODS);
 
     // Feature: Pool, a pool in the Grotto
     wb.feature("pool", "pool", "pool")
@@ -121,6 +123,7 @@ space.  Trails lead to the north and south.
     // Thing: The Stone on the Hilltop
     wb.feature("stone", "stone", "stone")
         .location("hilltop")
+        .alias("block")
         .on_scenery("\
 A massive block of stone squats on the crest of the hill.  There seems to be a sword hilt
 poking out of the top, and there's something on one of the sides.
@@ -209,6 +212,9 @@ and get that sword.
 It's an unpleasant place but your sword gives you confidence and warm fuzzies.
         ");
 
+    // The player wins on entering the cave, since that's as far as the game goes.
+    wb.win_when(&|w| w.loc(PLAYER) == "cave-1");
+
     // If the player dies, the fairy godmother revives him.
     wb.rule("fairy-godmother-rule")
         .when(&|w| w.has(PLAYER, Dead))
@@ -219,33 +225,17 @@ her wand.  There's a flash, and she disappears.
         ")
         .revive(PLAYER);
 
-    // NEXT, add custom commands.
-    // NOTE: Order is important!
-
-    wb.verb_noun("wash", "hands", &|w,_,script| {
-        if !w.has(&w.loc(PLAYER), HAS_WATER) {
-            return Err("That'd be a neat trick, since there's no water here.".into());
-        }
-
-        // TODO: Provide actions that build up paragraphs?
-        let mut buff = ProseBuffer::new();
-        buff.puts("You wash your hands in the water.");
-        if w.has("hands", DIRTY) {
-            buff.puts("They look much cleaner now.");
-        }
-
-        script.print(&buff.get());
-        script.unset_flag("hands", DIRTY);
-
-        Ok(())
-    });
+    // Random events: the sounds of the woods.
+    wb.random_table("woods-sounds", 20)
+        .region(WOODS)
+        .cooldown(5);
 
-    wb.verb_visible("wash", &|_,_,script| {
-        script.print("You can't wash that.");
-        Ok(())
-    });
+    wb.random_event("woods-sounds", "woods-bird", 2)
+        .print("A bird calls somewhere in the trees.");
 
+    wb.random_event("woods-sounds", "woods-wind", 1)
+        .print("The wind rustles the leaves overhead.");
 
-    // NEXT, return the world.
-    wb.world()
+    // NEXT, return the builder.
+    wb
 }
@@ -1,18 +1,39 @@
 //! Scripts that mutate the world
 
+use crate::consumable;
+#[cfg(feature = "deadlines")]
+use crate::deadline;
+#[cfg(feature = "effects")]
+use crate::effect;
+use crate::entity::player_component::Note;
+use crate::entity::prose_component::Prose;
+use crate::entity::ID;
+use crate::entity::prose_component::ProseComponent;
+use crate::entity::RoomID;
+use crate::entity::RuleID;
 use crate::phys;
 use self::Action::*;
+use crate::types::Audience;
+use crate::types::Dir;
+use crate::types::Event;
+use crate::types::LinkDest;
 use crate::types::Flag;
+use crate::types::Prompt as Question;
+use crate::types::ProseType;
+#[cfg(feature = "effects")]
+use crate::types::Time;
 use crate::visual;
 use crate::world::World;
+use crate::world::LIMBO;
 use crate::world_builder;
+use std::rc::Rc;
 
 /// Actions taken by rules (and maybe other things)
 #[allow(dead_code)]
 #[derive(Clone, Debug)]
 enum Action {
-    /// Print the entity's visual
-    Print(String),
+    /// Print(audience, text): Print the text, if the audience can perceive it.
+    Print(Audience, Rc<str>),
 
     /// SetFlag(tag,flag): Set the flag on the tagged entity
     SetFlag(String, Flag),
@@ -20,6 +41,40 @@ enum Action {
     /// UnsetFlag(tag,flag): Unset the flag on the tagged entity
     UnsetFlag(String, Flag),
 
+    /// Cutscene(pages): Display the pages with pagination, and suppress any further
+    /// output for the turn.
+    Cutscene(Rc<[String]>),
+
+    /// Sound(room, loudness, sound): Make a sound in the tagged room that carries
+    /// across the given number of links.
+    Sound(String, usize, String),
+
+    /// Rename(tag, name): Give the tagged room or thing a new name.
+    Rename(String, String),
+
+    /// SetProse(tag, prose_type, text): Replace the tagged entity's prose of the
+    /// given type.
+    SetProse(String, ProseType, Rc<str>),
+
+    /// Identify(thing, name, nouns): Give the tagged thing its true name and nouns.
+    Identify(String, String, Vec<String>),
+
+    /// Lock(thing): Lock the tagged lockable thing, without need of a key.
+    Lock(String),
+
+    /// Unlock(thing): Unlock the tagged lockable thing, without need of a key.
+    Unlock(String),
+
+    /// Consume(thing): Use one of the tagged consumable's charges.
+    Consume(String),
+
+    /// Link(room, dir, dest): Add a link from the tagged room to the tagged destination
+    /// room in the given direction, replacing any existing link.
+    Link(String, Dir, String),
+
+    /// Unlink(room, dir): Remove the tagged room's link in the given direction.
+    Unlink(String, Dir),
+
     /// PutIn(thing, inv): Put the tagged thing in the tagged
     /// entity's inventory
     PutIn(String, String),
@@ -30,13 +85,63 @@ enum Action {
     /// Drop(player,thing): Drop a held item into the current location.
     Drop(String, String),
 
-    /// Kill(player): Kill the tagged player/NPC (currently, only the player)
-    Kill(String),
+    /// Score(points): Add points to the player's score (or remove them)
+    Score(i32),
+
+    /// Kill(player, message, cause): Kill the tagged player/NPC (currently, only the
+    /// player), announcing the death with the message, if given, and showing the
+    /// epitaph for the cause of death, if any.
+    Kill(String, Option<Rc<str>>, Option<String>),
 
     /// Revive(player): Revive the tagged player/NPC (currently, only the player)
     Revive(String),
+
+    /// Call(rule): Run the tagged rule's (or named script's) script.
+    Call(String),
+
+    /// JournalEntry(text): Add an entry to the player's quest journal.
+    JournalEntry(String),
+
+    /// Disown(thing): The tagged thing no longer belongs to anyone, e.g., once the
+    /// player has paid for it.
+    Disown(String),
+
+    /// Elapse(turns, text): Print the transition text, e.g., "Later that day...", and
+    /// skip the given number of turns at the end of this one.
+    Elapse(usize, String),
+
+    /// Prompt(question, answers): Ask the player a question at the end of the turn.
+    /// The answers pair each expected answer with the tag of the rule or named script to
+    /// run when the player gives it; "*" accepts any text.
+    Prompt(String, Vec<(String, String)>),
+
+    /// ApplyEffect(effect, turns): Apply the tagged status effect to the player for the
+    /// given number of turns, or until removed.
+    #[cfg(feature = "effects")]
+    ApplyEffect(String, Option<Time>),
+
+    /// RemoveEffect(effect): Remove the tagged status effect from the player.
+    #[cfg(feature = "effects")]
+    RemoveEffect(String),
+
+    /// StartDeadline(deadline): Start the tagged deadline counting down, or start it
+    /// over.
+    #[cfg(feature = "deadlines")]
+    StartDeadline(String),
+
+    /// StopDeadline(deadline): Stop the tagged deadline.
+    #[cfg(feature = "deadlines")]
+    StopDeadline(String),
 }
 
+/// The tag by which a script refers to the subject of the event that triggered it,
+/// e.g., the thing that was gotten.
+pub const SUBJECT: &str = "{subject}";
+
+/// The maximum depth of nested Call actions.  A script that calls itself, directly or
+/// indirectly, fails when it reaches this depth.
+const MAX_CALL_DEPTH: usize = 16;
+
 /// A script of actions for execution.  Scripts can be pre-defined and executed
 /// later, or created and executed immediately.
 #[derive(Clone, Debug, Default)]
@@ -59,58 +164,390 @@ impl Script {
         }
     }
 
-    /// Executes a script on the world.
+    /// Executes a script on the world as a single transaction.  Every action is checked
+    /// before any is applied; if one can't be, e.g., because of a bad tag or a missing
+    /// component, none are, and the error is logged, so that a buggy rule can't leave
+    /// the world half-modified.  A script that moves things into containers can still
+    /// fail partway, e.g., by putting a in b and then b in a; it's applied to a staged
+    /// copy of the world, which is discarded if it fails.
     pub fn execute(&self, world: &mut World) {
+        self.execute_with(world, None);
+    }
+
+    /// Executes a script triggered by an event.  The script can refer to the event's
+    /// subject, e.g., the thing that was gotten, as SUBJECT, and print its name with
+    /// "{subject}".  This allows one rule to apply to many things.
+    pub fn execute_on(&self, world: &mut World, event: &Event) {
+        self.execute_with(world, event.subject());
+    }
+
+    /// Executes the script as a transaction, with the given subject, e.g., when the
+    /// player answers a question asked by a script triggered by an event.
+    pub fn execute_with(&self, world: &mut World, subject: Option<ID>) {
+        if let Err(msg) = self.check(world, subject, 0) {
+            world.log(&format!("Script error, no actions applied: {}", msg));
+            return;
+        }
+
+        if !self.puts_in(world, subject) {
+            // check() has settled whether every action succeeds.
+            self.apply(world, subject, 0).expect("checked script");
+            return;
+        }
+
+        let mut staged = world.clone();
+
+        match self.apply(&mut staged, subject, 0) {
+            Ok(()) => *world = staged,
+            Err(msg) => world.log(&format!("Script error, no actions applied: {}", msg)),
+        }
+    }
+
+    /// Does the script, or any script it calls, put things into containers?  Whether
+    /// that succeeds depends on where the script's earlier actions have put things.
+    /// The script must have passed check().
+    fn puts_in(&self, world: &World, subject: Option<ID>) -> bool {
+        self.actions.iter().any(|action| match action {
+            PutIn(_, _) => true,
+            Call(tag) => {
+                let id = rule(world, subject, tag).expect("checked tag");
+                world.rules[&id.id()].script.puts_in(world, subject)
+            }
+            _ => false,
+        })
+    }
+
+    /// Checks that every action can be applied, i.e., that its tags name entities with
+    /// the components it needs, without changing the world.  Called scripts are checked
+    /// too.  The depth is the number of Call actions that led here.
+    fn check(&self, world: &World, subject: Option<ID>, depth: usize) -> Result<(), String> {
+        for action in &self.actions {
+            match action {
+                Print(audience, visual) => {
+                    if let Audience::Room(tag) = audience {
+                        entity(world, subject, tag)?;
+                    }
+                    if visual.contains("{subject}") {
+                        subject_name(world, subject)?;
+                    }
+                }
+                Cutscene(_) | Score(_) | JournalEntry(_) | Elapse(_, _) => {}
+                Sound(tag, _, _) | Rename(tag, _) | SetProse(tag, _, _) | Consume(tag) => {
+                    entity(world, subject, tag)?;
+                }
+                SetFlag(tag, _) | UnsetFlag(tag, _) | Lock(tag) | Unlock(tag) => {
+                    flagged(world, subject, tag)?;
+                }
+                Kill(tag, _, _) | Revive(tag) => {
+                    flagged(world, subject, tag)?;
+                }
+                Identify(tag, _, _) => {
+                    thing(world, subject, tag)?;
+                    located(world, subject, tag)?;
+                }
+                Disown(tag) => {
+                    thing(world, subject, tag)?;
+                }
+                Link(room_tag, _, dest) => {
+                    room(world, subject, dest)?;
+                    room(world, subject, room_tag)?;
+                }
+                Unlink(room_tag, _) => {
+                    room(world, subject, room_tag)?;
+                }
+                PutIn(thing, inv) => {
+                    let (thing, inv) = (located(world, subject, thing)?, container(world, subject, inv)?);
+                    if phys::encloses(world, thing, inv) {
+                        return Err(format!("Cannot put {} inside itself", world.tag(thing)));
+                    }
+                }
+                Drop(a, b) | Swap(a, b) => {
+                    located(world, subject, a)?;
+                    located(world, subject, b)?;
+                }
+                Prompt(_, answers) => {
+                    for (_, tag) in answers {
+                        if !world.rules.contains_key(&entity(world, subject, tag)?) {
+                            return Err(format!("Not a rule or script: {}", tag));
+                        }
+                    }
+                }
+                #[cfg(feature = "effects")]
+                ApplyEffect(tag, _) | RemoveEffect(tag) => {
+                    effect(world, subject, tag)?;
+                }
+                #[cfg(feature = "deadlines")]
+                StartDeadline(tag) | StopDeadline(tag) => {
+                    deadline(world, subject, tag)?;
+                }
+                Call(tag) => {
+                    if depth >= MAX_CALL_DEPTH {
+                        return Err(format!("Call depth exceeded calling: {}", tag));
+                    }
+                    let id = rule(world, subject, tag)?;
+                    world.rules[&id.id()].script.check(world, subject, depth + 1)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies the script's actions to the world, stopping at the first action that
+    /// can't be applied.  The depth is the number of Call actions that led here.
+    fn apply(&self, world: &mut World, subject: Option<ID>, depth: usize) -> Result<(), String> {
         for action in &self.actions {
             match action {
-                // Print the rule's visual
-                Print(visual) => {
-                    visual::info(&visual);
+                // Print the rule's visual, if the player can perceive it.
+                Print(audience, visual) => {
+                    if let Audience::Room(tag) = audience {
+                        entity(world, subject, tag)?;
+                    }
+                    let visual = visual::expand(world, visual);
+                    let visual = match &world.answer {
+                        Some(answer) => visual.replace("{answer}", answer),
+                        None => visual,
+                    };
+                    if visual.contains("{subject}") {
+                        let name = subject_name(world, subject)?;
+                        visual::observe(world, audience, &visual.replace("{subject}", &name));
+                    } else {
+                        visual::observe(world, audience, &visual);
+                    }
+                }
+
+                // Play a cutscene
+                Cutscene(pages) => {
+                    visual::cutscene(&pages);
+                }
+
+                // Make a sound the player might hear.
+                Sound(room, loudness, sound) => {
+                    visual::hear(world, entity(world, subject, room)?, *loudness, &sound);
                 }
 
                 // Set the flag on the entity's flag set
                 SetFlag(tag, flag) => {
-                    world.set_flag(world.lookup(tag), *flag);
+                    world.set_flag(flagged(world, subject, tag)?, *flag);
                 }
 
                 // Clear the flag on the entity's flag set
                 UnsetFlag(tag, flag) => {
-                    world.unset_flag(world.lookup(tag), *flag);
+                    world.unset_flag(flagged(world, subject, tag)?, *flag);
+                }
+
+                // Rename a room or thing
+                Rename(tag, name) => {
+                    let id = entity(world, subject, tag)?;
+                    if let Some(roomc) = world.rooms.get_mut(&id) {
+                        roomc.name = name.clone();
+                    }
+                    if let Some(thingc) = world.things.get_mut(&id) {
+                        thingc.name = name.clone();
+                    }
+                }
+
+                // Replace one of the entity's prose strings
+                SetProse(tag, prose_type, text) => {
+                    let id = entity(world, subject, tag)?;
+                    let prosec = world.proses.entry(id).or_insert_with(ProseComponent::new);
+                    prosec.types.insert(*prose_type, Prose::Prose(text.clone()));
+                }
+
+                // Identify a thing
+                Identify(tag, name, nouns) => {
+                    let id = thing(world, subject, tag)?;
+                    world.things.get_mut(&id).unwrap().name = name.clone();
+                    if let Some((noun, aliases)) = nouns.split_first() {
+                        phys::set_nouns(world, id, noun, aliases);
+                    }
+                }
+
+                // Lock or unlock a thing
+                Lock(tag) => {
+                    world.set_flag(flagged(world, subject, tag)?, Flag::Locked);
+                }
+
+                Unlock(tag) => {
+                    world.unset_flag(flagged(world, subject, tag)?, Flag::Locked);
+                }
+
+                // Use up a charge
+                Consume(tag) => {
+                    consumable::consume(world, entity(world, subject, tag)?);
+                }
+
+                // Open or close a passage
+                Link(room_tag, dir, dest) => {
+                    let dest = LinkDest::Room(RoomID::new(room(world, subject, dest)?));
+                    let roomc = world.rooms.get_mut(&room(world, subject, room_tag)?).unwrap();
+                    roomc.links.insert(*dir, dest);
+                }
+
+                Unlink(room_tag, dir) => {
+                    let roomc = world.rooms.get_mut(&room(world, subject, room_tag)?).unwrap();
+                    roomc.links.remove(dir);
                 }
 
                 // Moves a thing to a given place.
                 PutIn(thing, inv) => {
-                    phys::put_in(world, world.lookup(thing), world.lookup(inv));
+                    let (thing, inv) = (located(world, subject, thing)?, container(world, subject, inv)?);
+                    if phys::encloses(world, thing, inv) {
+                        return Err(format!("Cannot put {} inside itself", world.tag(thing)));
+                    }
+                    if inv == LIMBO {
+                        phys::destroy(world, thing);
+                    } else {
+                        phys::move_thing(world, thing, inv);
+                    }
                 }
 
                 // Player/NPC drops thing into its current location.
                 Drop(player, thing) => {
-                    let loc = phys::loc(world, world.lookup(player));
-                    phys::put_in(world, world.lookup(thing), loc);
+                    let loc = phys::loc(world, located(world, subject, player)?);
+                    phys::move_thing(world, located(world, subject, thing)?, loc);
                 }
 
                 // Swap a, in a place, with b, in LIMBO
                 Swap(a, b) => {
-                    let aid = world.lookup(a);
-                    let bid = world.lookup(b);
+                    let aid = located(world, subject, a)?;
+                    let bid = located(world, subject, b)?;
                     let loc = phys::loc(world, aid);
-                    phys::take_out(world, aid);
-                    phys::put_in(world, bid, loc);
+                    phys::destroy(world, aid);
+                    phys::move_thing(world, bid, loc);
+                }
+
+                // Change the player's score
+                Score(points) => {
+                    world.score += *points;
+                    visual::score(*points);
                 }
 
                 // Kill the player/NPC
-                Kill(player) => {
-                    world.set_flag(world.lookup(player), Flag::Dead);
-                    visual::act("*** You have died. ***");
+                Kill(player, message, cause) => {
+                    let id = flagged(world, subject, player)?;
+                    let message = message.as_deref().unwrap_or(phys::DEATH_MESSAGE);
+                    phys::kill(world, id, message, cause.as_deref());
                 }
 
                 // Revive the player/NPC
                 Revive(player) => {
-                    world.unset_flag(world.lookup(player), Flag::Dead);
-                    visual::act("*** You are alive! ***");
+                    let id = flagged(world, subject, player)?;
+                    phys::revive(world, id);
+                }
+
+                // Add an entry to the quest journal.
+                JournalEntry(text) => {
+                    let entry = Note {
+                        turn: world.clock,
+                        text: text.clone(),
+                    };
+                    let pid = world.pid;
+                    world.players.get_mut(&pid).unwrap().journal.push(entry);
+                    visual::info("($Your journal has been updated.)");
+                }
+
+                // The thing is the player's to take.
+                Disown(tag) => {
+                    let id = thing(world, subject, tag)?;
+                    world.things.get_mut(&id).unwrap().owner = None;
+                }
+
+                // Cut to later in the day.
+                Elapse(turns, text) => {
+                    visual::info(text);
+                    world.elapsing += *turns;
+                }
+
+                // Ask the player a question; the answer runs a script later.
+                Prompt(question, answers) => {
+                    let mut scripts = Vec::new();
+                    for (answer, tag) in answers {
+                        let id = entity(world, subject, tag)?;
+                        if !world.rules.contains_key(&id) {
+                            return Err(format!("Not a rule or script: {}", tag));
+                        }
+                        scripts.push((answer.clone(), id));
+                    }
+                    world.prompt = Some(Question {
+                        question: question.clone(),
+                        answers: scripts,
+                        subject,
+                        posed: false,
+                    });
+                }
+
+                // Put the player under a status effect, or lift it.
+                #[cfg(feature = "effects")]
+                ApplyEffect(tag, turns) => {
+                    let pid = world.pid;
+                    effect::apply(world, pid, effect(world, subject, tag)?, *turns);
+                }
+
+                #[cfg(feature = "effects")]
+                RemoveEffect(tag) => {
+                    let pid = world.pid;
+                    effect::remove(world, pid, effect(world, subject, tag)?);
+                }
+
+                // Start the clock ticking, or stop it.
+                #[cfg(feature = "deadlines")]
+                StartDeadline(tag) => deadline::start(world, deadline(world, subject, tag)?),
+
+                #[cfg(feature = "deadlines")]
+                StopDeadline(tag) => {
+                    deadline::stop(world, deadline(world, subject, tag)?);
+                }
+
+                // Run another rule's script as part of this one.
+                Call(tag) => {
+                    if depth >= MAX_CALL_DEPTH {
+                        return Err(format!("Call depth exceeded calling: {}", tag));
+                    }
+                    let id = rule(world, subject, tag)?;
+                    let script = world.rules[&id.id()].script.clone();
+                    script.apply(world, subject, depth + 1)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the tags of the entities the script refers to.
+    pub fn tags(&self) -> Vec<&str> {
+        let mut result = Vec::new();
+
+        for action in &self.actions {
+            match action {
+                Print(Audience::Room(tag), _) => result.push(tag.as_str()),
+                Print(_, _) | Cutscene(_) | Score(_) | JournalEntry(_) | Elapse(_, _) => {}
+                Sound(tag, _, _) => result.push(tag),
+                SetFlag(tag, _) | UnsetFlag(tag, _) => result.push(tag),
+                Rename(tag, _) | SetProse(tag, _, _) | Identify(tag, _, _) => result.push(tag),
+                PutIn(a, b) | Swap(a, b) | Drop(a, b) => {
+                    result.push(a);
+                    result.push(b);
+                }
+                Kill(tag, _, _) | Revive(tag) | Lock(tag) | Unlock(tag) => result.push(tag),
+                Disown(tag) => result.push(tag),
+                #[cfg(feature = "effects")]
+                ApplyEffect(tag, _) | RemoveEffect(tag) => result.push(tag),
+                #[cfg(feature = "deadlines")]
+                StartDeadline(tag) | StopDeadline(tag) => result.push(tag),
+                Call(tag) => result.push(tag),
+                Prompt(_, answers) => result.extend(answers.iter().map(|(_, tag)| tag.as_str())),
+                Consume(tag) | Unlink(tag, _) => result.push(tag),
+                Link(a, _, b) => {
+                    result.push(a);
+                    result.push(b);
                 }
             }
         }
+
+        // The subject isn't known until the script runs.
+        result.retain(|tag| *tag != SUBJECT);
+        result
     }
 
     //-------------------------------------------------------------------------------------------
@@ -124,7 +561,25 @@ impl Script {
 
     /// Adds an action to print the given text string.
     pub fn print(&mut self, text: &str) {
-        self.add(Print(text.into()));
+        self.add(Print(Audience::Global, text.into()));
+    }
+
+    /// Adds an action to print the given text string, but only if the
+    /// audience can perceive it.
+    pub fn print_to(&mut self, audience: Audience, text: &str) {
+        self.add(Print(audience, text.into()));
+    }
+
+    /// Adds an action to play a cutscene, i.e., to display the pages one at a time.
+    pub fn cutscene(&mut self, pages: &[&str]) {
+        let pages: Vec<String> = pages.iter().map(|p| p.trim().to_string()).collect();
+        self.add(Cutscene(pages.into()));
+    }
+
+    /// Adds an action to make a sound in the tagged room.  The player will hear it if
+    /// they are within the given number of links.
+    pub fn sound(&mut self, room: &str, loudness: usize, sound: &str) {
+        self.add(Sound(room.into(), loudness, sound.into()));
     }
 
     /// Adds an action to set the given flag on the tagged entity.
@@ -137,15 +592,133 @@ impl Script {
         self.add(UnsetFlag(tag.into(), flag));
     }
     
+    /// Adds an action to give the tagged room or thing a new name.
+    pub fn rename(&mut self, tag: &str, name: &str) {
+        self.add(Rename(tag.into(), name.into()));
+    }
+
+    /// Adds an action to replace the tagged entity's prose of the given type.
+    pub fn set_prose(&mut self, tag: &str, prose_type: ProseType, text: &str) {
+        self.add(SetProse(tag.into(), prose_type, text.trim().into()));
+    }
+
+    /// Adds an action to identify the tagged thing, giving it a new name and nouns.
+    /// The first noun is the thing's primary noun; the rest are aliases.
+    pub fn identify(&mut self, thing: &str, name: &str, nouns: &[&str]) {
+        let nouns = nouns.iter().map(|n| n.to_string()).collect();
+        self.add(Identify(thing.into(), name.into(), nouns));
+    }
+
+    /// Adds an action to lock the tagged lockable thing.
+    pub fn lock(&mut self, thing: &str) {
+        self.add(Lock(thing.into()));
+    }
+
+    /// Adds an action to unlock the tagged lockable thing.
+    pub fn unlock(&mut self, thing: &str) {
+        self.add(Unlock(thing.into()));
+    }
+
+    /// Adds an action to use one of the tagged consumable's charges.
+    pub fn consume(&mut self, thing: &str) {
+        self.add(Consume(thing.into()));
+    }
+
+    /// Adds an action to link the tagged room to the tagged destination room in the
+    /// given direction, e.g., when a bookcase slides aside to reveal a passage.
+    pub fn link(&mut self, room: &str, dir: Dir, dest: &str) {
+        self.add(Link(room.into(), dir, dest.into()));
+    }
+
+    /// Adds an action to remove the tagged room's link in the given direction.
+    pub fn unlink(&mut self, room: &str, dir: Dir) {
+        self.add(Unlink(room.into(), dir));
+    }
+
+    /// Adds an action to put the tagged thing in the tagged entity's inventory.
+    pub fn put_in(&mut self, thing: &str, inv: &str) {
+        self.add(PutIn(thing.into(), inv.into()));
+    }
+
+    /// Adds an action to run the tagged rule's or named script's script.
+    pub fn call(&mut self, rule: &str) {
+        self.add(Call(rule.into()));
+    }
+
+    /// Adds an action to add an entry to the player's quest journal.
+    pub fn journal_entry(&mut self, text: &str) {
+        self.add(JournalEntry(text.trim().into()));
+    }
+
+    /// Adds an action to clear the tagged thing's owner.
+    pub fn disown(&mut self, thing: &str) {
+        self.add(Disown(thing.into()));
+    }
+
+    /// Adds an action to print the transition text and skip the given number of turns,
+    /// for a scene cut.
+    pub fn elapse(&mut self, turns: usize, text: &str) {
+        self.add(Elapse(turns, text.trim().into()));
+    }
+
+    /// Adds an action to apply the tagged status effect to the player for the given
+    /// number of turns, or until removed.
+    #[cfg(feature = "effects")]
+    pub fn apply_effect(&mut self, effect: &str, turns: Option<Time>) {
+        self.add(ApplyEffect(effect.into(), turns));
+    }
+
+    /// Adds an action to remove the tagged status effect from the player.
+    #[cfg(feature = "effects")]
+    pub fn remove_effect(&mut self, effect: &str) {
+        self.add(RemoveEffect(effect.into()));
+    }
+
+    /// Adds an action to start the tagged deadline, or start it over.
+    #[cfg(feature = "deadlines")]
+    pub fn start_deadline(&mut self, deadline: &str) {
+        self.add(StartDeadline(deadline.into()));
+    }
+
+    /// Adds an action to stop the tagged deadline.
+    #[cfg(feature = "deadlines")]
+    pub fn stop_deadline(&mut self, deadline: &str) {
+        self.add(StopDeadline(deadline.into()));
+    }
+
+    /// Adds an action to ask the player a question.  Each answer is paired with the tag
+    /// of the rule or named script to run when the player gives it; "*" accepts any
+    /// text, which prose can show as "{answer}".
+    pub fn prompt(&mut self, question: &str, answers: &[(&str, &str)]) {
+        let answers = answers
+            .iter()
+            .map(|(answer, tag)| (answer.to_lowercase(), tag.to_string()))
+            .collect();
+        self.add(Prompt(question.into(), answers));
+    }
+
     /// Adds an action to move the tagged entity to LIMBO.
     pub fn forget(&mut self, thing: &str) {
         self.add(PutIn(thing.into(), world_builder::LIMBO.into()));
     }
 
+    /// Adds an action to add points to the player's score.  The points
+    /// can be negative.
+    pub fn score(&mut self, points: i32) {
+        self.add(Score(points));
+    }
+
     /// Adds an action to kill the given entity (i.e., set its Dead flag).
     /// At present the only thing that can be killed is the player.
     pub fn kill(&mut self, player: &str) {
-        self.add(Action::Kill(player.into()));
+        self.add(Action::Kill(player.into(), None, None));
+    }
+
+    /// Adds an action to kill the given entity, announcing the death with the message
+    /// instead of the usual one, and showing the epitaph for the cause of death.
+    pub fn kill_as(&mut self, player: &str, message: Option<&str>, cause: Option<&str>) {
+        let message = message.map(|text| text.trim().into());
+        self.add(Action::Kill(player.into(), message, cause.map(String::from)));
     }
 
     /// Adds an action to revive the given entity (i.e., clear its Dead flag).
@@ -154,3 +727,184 @@ impl Script {
         self.add(Action::Revive(player.into()));
     }
 }
+
+//-------------------------------------------------------------------------------------------
+// Tag Lookups
+//
+// These look up the tagged entity for an action, checking that it has the components the
+// action needs.  The SUBJECT tag refers to the subject of the event that triggered the
+// script, if any.
+
+/// Looks up the tagged entity, or the subject of the triggering event.
+fn entity(world: &World, subject: Option<ID>, tag: &str) -> Result<ID, String> {
+    if tag == SUBJECT {
+        subject.ok_or_else(|| "The script has no subject".to_string())
+    } else {
+        world.lookup_id(tag).ok_or_else(|| format!("No entity with tag: {}", tag))
+    }
+}
+
+/// Gets the name of the triggering event's subject, which must be a room or a thing.
+fn subject_name(world: &World, subject: Option<ID>) -> Result<String, String> {
+    let id = entity(world, subject, SUBJECT)?;
+    if let Some(roomc) = world.rooms.get(&id) {
+        Ok(roomc.name.clone())
+    } else if let Some(thingc) = world.things.get(&id) {
+        Ok(thingc.name.clone())
+    } else {
+        Err(format!("The subject has no name: {}", world.tag(id)))
+    }
+}
+
+/// Looks up the tagged entity, which must have a flag set.
+fn flagged(world: &World, subject: Option<ID>, tag: &str) -> Result<ID, String> {
+    let id = entity(world, subject, tag)?;
+    if world.has_flags(id) {
+        Ok(id)
+    } else {
+        Err(format!("Not a flag set: {}", tag))
+    }
+}
+
+/// Looks up the tagged room.
+fn room(world: &World, subject: Option<ID>, tag: &str) -> Result<ID, String> {
+    let id = entity(world, subject, tag)?;
+    if world.is_room(id) {
+        Ok(id)
+    } else {
+        Err(format!("Not a room: {}", tag))
+    }
+}
+
+/// Looks up the tagged rule or named script.
+fn rule(world: &World, subject: Option<ID>, tag: &str) -> Result<RuleID, String> {
+    let id = entity(world, subject, tag)?;
+    world.rule_id(id).ok_or_else(|| format!("Not a rule or script: {}", tag))
+}
+
+/// Looks up the tagged status effect.
+#[cfg(feature = "effects")]
+fn effect(world: &World, subject: Option<ID>, tag: &str) -> Result<ID, String> {
+    let id = entity(world, subject, tag)?;
+    if world.effects.contains_key(&id) {
+        Ok(id)
+    } else {
+        Err(format!("Not an effect: {}", tag))
+    }
+}
+
+/// Looks up the tagged deadline.
+#[cfg(feature = "deadlines")]
+fn deadline(world: &World, subject: Option<ID>, tag: &str) -> Result<ID, String> {
+    let id = entity(world, subject, tag)?;
+    if world.deadlines.contains_key(&id) {
+        Ok(id)
+    } else {
+        Err(format!("Not a deadline: {}", tag))
+    }
+}
+
+/// Looks up the tagged thing.
+fn thing(world: &World, subject: Option<ID>, tag: &str) -> Result<ID, String> {
+    let id = entity(world, subject, tag)?;
+    if world.things.contains_key(&id) {
+        Ok(id)
+    } else {
+        Err(format!("Not a thing: {}", tag))
+    }
+}
+
+/// Looks up the tagged entity, which must have a location.
+fn located(world: &World, subject: Option<ID>, tag: &str) -> Result<ID, String> {
+    let id = entity(world, subject, tag)?;
+    if world.has_location(id) {
+        Ok(id)
+    } else {
+        Err(format!("Has no location component: {}", tag))
+    }
+}
+
+/// Looks up the tagged entity, which must have an inventory.
+fn container(world: &World, subject: Option<ID>, tag: &str) -> Result<ID, String> {
+    let id = entity(world, subject, tag)?;
+    if world.has_inventory(id) {
+        Ok(id)
+    } else {
+        Err(format!("Has no inventory component: {}", tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console;
+    use crate::world_builder::WorldBuilder;
+    use crate::Game;
+
+    #[test]
+    fn test_failed_action_applies_nothing() {
+        console::set_interactive(false);
+
+        let mut world = Game::new().world;
+        let sword = world.lookup("sword");
+        assert!(!world.has_flag(sword, Flag::Immovable));
+
+        // The second action can't be applied, so neither is the first.
+        let mut script = Script::new();
+        script.set_flag("sword", Flag::Immovable);
+        script.set_flag("no-such-thing", Flag::Immovable);
+        script.execute(&mut world);
+
+        assert!(!world.has_flag(sword, Flag::Immovable));
+
+        // Without it, the first action is applied.
+        let mut script = Script::new();
+        script.set_flag("sword", Flag::Immovable);
+        script.execute(&mut world);
+
+        assert!(world.has_flag(sword, Flag::Immovable));
+    }
+
+    #[test]
+    fn test_failed_move_applies_nothing() {
+        console::set_interactive(false);
+
+        let mut wb = WorldBuilder::new();
+        wb.player().location("room");
+        wb.room("room", "A Room");
+        wb.thing("box", "box", "box").location("room").container();
+        wb.thing("bag", "bag", "bag").location("room").container();
+        let mut world = wb.world();
+        let (room, bx) = (world.lookup("room"), world.lookup("box"));
+
+        // The box can go in the bag, but then the bag can't go in the box.
+        let mut script = Script::new();
+        script.set_flag("box", Flag::Immovable);
+        script.put_in("box", "bag");
+        script.put_in("bag", "box");
+        script.execute(&mut world);
+
+        assert!(!world.has_flag(bx, Flag::Immovable));
+        assert_eq!(phys::loc(&world, bx), room);
+    }
+
+    #[test]
+    fn test_kill_as() {
+        console::set_interactive(false);
+
+        let mut wb = WorldBuilder::new();
+        wb.player().location("room");
+        wb.room("room", "A Room").prose("An empty room.");
+        let mut world = wb.world();
+        let pid = world.pid;
+
+        let mut script = Script::new();
+        script.kill_as(&world.tag(pid), Some("$You $have died {deaths}."), Some("fall"));
+        script.execute(&mut world);
+
+        assert!(world.has_flag(pid, Flag::Dead));
+        assert_eq!(visual::expand(&world, "$You $have died {deaths}."), "$You $have died once.");
+        world.metrics.deaths = 3;
+        assert_eq!(visual::expand(&world, "$You $have died {deaths}."), "$You $have died 3 times.");
+    }
+}
@@ -0,0 +1,54 @@
+//! Story Progression System
+//!
+//! Longer games are divided into chapters.  Chapters are entered in order of definition;
+//! each turn, if the entry condition for the next chapter is met, the story moves on to
+//! that chapter and fires its StartChapter event.  The game is won when the scenario's
+//! win condition is met.  Rules can be scoped to a chapter, so
+//! that they only fire while it's current.
+
+use crate::entity::ID;
+use crate::records;
+use crate::rule;
+use crate::types::Event;
+use crate::visual;
+use crate::world::World;
+
+/// The Story system.  Moves the story to the next chapter, if its time has come, and
+/// determines whether the player has won.
+pub fn system(world: &mut World) {
+    if let Some(next) = next_chapter(world) {
+        if (world.chapters[&next].predicate)(world) {
+            start_chapter(world, next);
+        }
+    }
+
+    if let Some(predicate) = world.win_condition {
+        if !world.won && predicate(world) {
+            world.won = true;
+            visual::banner("$You $have won!");
+            visual::metrics(world);
+
+            match records::record(world) {
+                Ok(Some(place)) => visual::info(&format!("That's run #{} in the records.", place)),
+                Ok(None) => (),
+                Err(msg) => visual::error(&msg),
+            }
+        }
+    }
+}
+
+/// Returns the chapter that follows the current one, if any.  If there is no current
+/// chapter, returns the first chapter.
+pub fn next_chapter(world: &World) -> Option<ID> {
+    match world.chapter {
+        Some(current) => world.chapters.keys().cloned().find(|id| *id > current),
+        None => world.chapters.keys().cloned().next(),
+    }
+}
+
+/// Makes the chapter the current chapter, and fires its StartChapter event.
+pub fn start_chapter(world: &mut World, chapter: ID) {
+    world.chapter = Some(chapter);
+    visual::chapter_title(world, chapter);
+    rule::fire_event(world, &Event::StartChapter(chapter));
+}
@@ -0,0 +1,140 @@
+//! Testing Tools for Scenario Authors
+//!
+//! The CommandFuzzer plays a game with random but syntactically valid commands, built
+//! from the verb registry and the nouns of the things the player can see.  After each
+//! step it checks that nothing panicked and that the world's invariants still hold.
+
+use crate::entity::ID;
+use crate::phys;
+use crate::rng::Rng;
+use crate::world::World;
+use crate::Game;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+
+/// Verbs the fuzzer never uses, because they end the program.
+const EXCLUDED_VERBS: [&str; 3] = ["quit", "exit", "bye"];
+
+/// Words that can appear in commands but aren't verbs or nouns.
+const PARTICLES: [&str; 4] = ["to", "up", "with", "about"];
+
+/// A generator of random commands.
+pub struct CommandFuzzer {
+    /// The random number generator.
+    rng: Rng,
+
+    /// Verbs that shouldn't be used.
+    excluded: Vec<String>,
+}
+
+impl CommandFuzzer {
+    /// Creates a new fuzzer.  The same seed produces the same commands for the same
+    /// game, so that failures can be reproduced.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: Rng::new(seed),
+            excluded: EXCLUDED_VERBS.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    /// Prevents the fuzzer from using the verb, e.g., a custom verb that ends the game.
+    pub fn exclude(&mut self, verb: &str) {
+        self.excluded.push(verb.into());
+    }
+
+    /// Generates a random command for the current state of the world.
+    pub fn command(&mut self, world: &World) -> String {
+        // FIRST, get the candidate verbs and nouns.  Sort them, so that the
+        // result doesn't depend on hash order.
+        let mut verbs: Vec<&String> = world
+            .verbs
+            .iter()
+            .filter(|v| !self.excluded.contains(v))
+            .collect();
+        verbs.sort();
+
+        let nouns = visible_nouns(world, world.pid);
+
+        // NEXT, pick a verb, and the rest of the command's shape.
+        let verb = verbs[self.rng.below(verbs.len())].clone();
+
+        if nouns.is_empty() {
+            return verb;
+        }
+
+        let noun = nouns[self.rng.below(nouns.len())].clone();
+
+        match self.rng.below(4) {
+            0 => verb,
+            1 => format!("{} {}", verb, noun),
+            2 => {
+                let particle = PARTICLES[self.rng.below(PARTICLES.len())];
+                format!("{} {} {}", verb, particle, noun)
+            }
+            _ => {
+                let particle = PARTICLES[self.rng.below(PARTICLES.len())];
+                let other = nouns[self.rng.below(nouns.len())].clone();
+                format!("{} {} {} {}", verb, noun, particle, other)
+            }
+        }
+    }
+
+    /// Plays the game for the given number of steps.  Panics if a step panics or leaves
+    /// the world in an invalid state; the message includes the commands entered so far.
+    pub fn run(&mut self, game: &mut Game, steps: usize) {
+        let mut commands: Vec<String> = Vec::new();
+
+        for _ in 0..steps {
+            let cmd = self.command(&game.world);
+            commands.push(cmd.clone());
+
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                // Errors are expected; most random commands make no sense.
+                let _ = game.step(&cmd);
+            }));
+
+            if result.is_err() {
+                panic!("Command panicked: \"{}\"\nCommands: {:?}", cmd, commands);
+            }
+
+            if let Err(msg) = game.world.validate() {
+                panic!("Invalid world after \"{}\": {}\nCommands: {:?}", cmd, msg, commands);
+            }
+        }
+    }
+}
+
+/// Returns the nouns of the things the viewer can see, sorted.
+fn visible_nouns(world: &World, viewer: ID) -> Vec<String> {
+    let mut nouns: Vec<String> = Vec::new();
+
+    for id in phys::visible(world, viewer) {
+        if let Some(thingc) = world.things.get(&id) {
+            nouns.push(thingc.noun.clone());
+            nouns.extend(thingc.aliases.iter().cloned());
+        }
+    }
+
+    nouns.sort();
+    nouns.dedup();
+    nouns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console;
+
+    #[test]
+    fn test_fuzz_scenario() {
+        console::set_interactive(false);
+
+        let mut game = Game::new();
+        game.introduce();
+
+        // Keep to a single playthrough.
+        let mut fuzzer = CommandFuzzer::new(1);
+        fuzzer.exclude("restart");
+        fuzzer.run(&mut game, 500);
+    }
+}
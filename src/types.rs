@@ -2,7 +2,10 @@
 
 use crate::script::Script;
 use crate::entity::ID;
+use crate::entity::RoomID;
 use crate::world::WorldQuery;
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
 
 //------------------------------------------------------------------------------------------------
 // Basic Types
@@ -15,19 +18,49 @@ pub type RulePredicate = &'static Fn(&WorldQuery) -> bool;
 /// receive the string.
 pub type EntityProseHook = &'static Fn(&WorldQuery, &str, &mut ProseBuffer);
 
+/// How a command hook disposed of a command.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Handled {
+    /// The hook didn't handle the command; try the remaining handlers and the
+    /// built-in commands.  The hook's script is not executed.
+    Pass,
+
+    /// The hook handled the command, but no game time passes.
+    NoTurn,
+
+    /// The hook handled the command, and the turn ends.
+    EndTurn,
+
+    /// The hook wants the player to confirm the command, e.g., "The chasm looks deadly.
+    /// Jump anyway?"  The hook's script is not executed.  If the player answers "yes",
+    /// the hook is called again, and WorldQuery::confirmed() is true.
+    Confirm(String),
+}
+
 /// The normal command handler result.
-pub type CommandResult = Result<(), String>;
+pub type CommandResult = Result<Handled, String>;
 
 /// A closure that determines whether a command is valid, then builds a script
 /// to execute it.  The hook may query the world and the command words, and
-/// return an Err(String) if there's a problem, and Ok(()) otherwise.
+/// return an Err(String) if there's a problem, and Ok(Handled) otherwise; see
+/// Handled.  A hook can return Handled::Pass to shadow a built-in command
+/// conditionally.  Numbers among the words can be read with parse_numeral().
 pub type CommandHook = &'static Fn(&WorldQuery, &[&str], &mut Script) -> CommandResult;
 
+/// A closure that chooses where an essential thing turns up if it would otherwise be
+/// lost.  It receives the thing's tag, and returns the tag of a room or container.
+pub type RecoveryHook = &'static Fn(&WorldQuery, &str) -> String;
+
+/// A closure that post-processes each block of prose before it is displayed, e.g., to
+/// censor or restyle it.  It receives the text as written, before narration
+/// placeholders and markup are expanded, and returns the text to display.
+pub type OutputHook = &'static Fn(&str) -> String;
+
 /// The time, in game turns
 pub type Time = usize;
 
 /// Directions
-#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
 #[allow(dead_code)]
 pub enum Dir {
     North,
@@ -38,6 +71,57 @@ pub enum Dir {
     Down,
     In,
     Out,
+
+    /// A direction defined by the scenario, by name, e.g., "port" on a ship; see
+    /// WorldBuilder::direction().
+    Custom(&'static str),
+}
+
+impl Dir {
+    /// All of the directions, in a standard order.
+    pub const ALL: [Dir; 8] = [
+        Dir::North,
+        Dir::South,
+        Dir::East,
+        Dir::West,
+        Dir::Up,
+        Dir::Down,
+        Dir::In,
+        Dir::Out,
+    ];
+
+    /// A phrase describing where something in this direction is, e.g., "to the north",
+    /// or "to port".
+    pub fn phrase(self) -> String {
+        match self {
+            Dir::North => "to the north".into(),
+            Dir::South => "to the south".into(),
+            Dir::East => "to the east".into(),
+            Dir::West => "to the west".into(),
+            Dir::Up => "above".into(),
+            Dir::Down => "below".into(),
+            Dir::In => "inside".into(),
+            Dir::Out => "outside".into(),
+            Dir::Custom(name) => format!("to {}", name),
+        }
+    }
+}
+
+/// How a command that needs the player to hold a thing treats a thing that's lying
+/// within reach.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum HoldPolicy {
+    /// The player must already be holding the thing.
+    Strict,
+
+    /// The player takes the thing first, as an implicit action.
+    Lenient,
+}
+
+impl Default for HoldPolicy {
+    fn default() -> Self {
+        HoldPolicy::Strict
+    }
 }
 
 /// The different kinds of prose supported by an entity.
@@ -54,6 +138,13 @@ pub enum ProseType {
 
     /// The prose description of a thing as scenery in a container
     Scenery,
+
+    /// Prose describing a distant thing's appearance from an adjacent room.
+    Distant,
+
+    /// Prose describing a thing in its room until it's first moved, e.g., "A sword is
+    /// stuck in a stump here."
+    Initial,
 }
 
 #[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
@@ -83,6 +174,78 @@ pub enum Flag {
     /// should also be Immovable.
     Scenery,
 
+    /// Can the thing be climbed, e.g., a tree or a ladder?
+    Climbable,
+
+    /// Is the thing visible from adjacent rooms, e.g., a tower or a mountain?  The
+    /// player can examine it from there, but not reach it.
+    Distant,
+
+    /// The thing is an armed trap.
+    Trapped,
+
+    /// The player has detected the trap on this thing.
+    Detected,
+
+    /// The lockable thing is locked.
+    Locked,
+
+    /// The thing is a key that fits every lock.
+    MasterKey,
+
+    /// The machine is switched on.
+    On,
+
+    /// The room supplies power to machines that need it.
+    Powered,
+
+    /// The consumable thing has used up all of its charges.
+    Exhausted,
+
+    /// The room is deep water: the player drowns there unless they can swim or have
+    /// a boat.
+    DeepWater,
+
+    /// The thing is too heavy to swim with.
+    Heavy,
+
+    /// The thing keeps whoever is carrying it afloat in deep water.
+    Boat,
+
+    /// The thing is dirty, and can be washed.  Scenarios can use a flag of their own
+    /// instead.
+    Dirty,
+
+    /// The room or thing has water the player can wash things in, e.g., a pool or a
+    /// bucket.
+    HasWater,
+
+    /// The thing never gets dirty from being handled by a dirty actor, e.g., a glass
+    /// bottle.
+    NeverDirty,
+
+    /// The thing is critical to the plot, and can't be lost: the player can swim with
+    /// it even if it's Heavy, and if it would sink or be destroyed it turns up where
+    /// the player can recover it instead.  See phys::recovery_place().
+    Essential,
+
+    /// The thing has been moved from where it started.
+    Moved,
+
+    /// The thing is hidden in its room: it isn't listed, and the player can't see or
+    /// handle it until searching the room reveals it.
+    Concealed,
+
+    /// The room is dark unless something in it gives light.  See dark::is_dark().
+    Dark,
+
+    /// The thing gives light, e.g., a torch; if it's a machine, only while it's running.
+    LightSource,
+
+    /// The player has the capability.  On a thing, the capability is granted to
+    /// whoever is carrying it.
+    Can(Capability),
+
     /// A generic flag type for use by users
     User(&'static str),
 
@@ -90,6 +253,344 @@ pub enum Flag {
     UserId(&'static str, ID)
 }
 
+/// Capabilities an actor can have, by virtue of its own flags or the things it carries.
+#[derive(PartialEq, Eq, Hash, Debug, Copy, Clone)]
+#[allow(dead_code)]
+pub enum Capability {
+    /// The actor can climb, e.g., it has a rope.
+    Climb,
+
+    /// The actor can disarm traps, e.g., it has the right tools.
+    Disarm,
+
+    /// The actor can swim.
+    Swim,
+
+    /// A generic capability for use by scenarios.
+    User(&'static str),
+}
+
+/// A capability required to do something, with the prose to display if it's lacking.
+#[derive(Clone, Debug)]
+pub struct Requirement {
+    /// The required capability.
+    pub capability: Capability,
+
+    /// The failure prose.
+    pub prose: String,
+}
+
+impl Requirement {
+    /// Creates a new requirement.
+    pub fn new(capability: Capability, prose: &str) -> Self {
+        Self {
+            capability,
+            prose: prose.trim().into(),
+        }
+    }
+}
+
+/// How winning runs are ranked in the records of best completions; see the records
+/// module.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Ranking {
+    /// The fewest turns wins; ties go to the higher score.
+    FewestTurns,
+
+    /// The highest score wins; ties go to the fewer turns.
+    HighestScore,
+}
+
+impl Default for Ranking {
+    fn default() -> Self {
+        Ranking::FewestTurns
+    }
+}
+
+/// The optional systems that run each turn.  The world builder registers each one
+/// that the scenario needs, e.g., the Machines system if there are machines, and the
+/// game runs only those that are registered.  The rules always run.  The larger
+/// systems are compiled in only with their cargo features, e.g., "machines", so that a
+/// small game can leave them out.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Copy, Clone)]
+pub enum TurnSystem {
+    /// Rolls the random event tables.
+    RandomEvents,
+
+    /// Runs the machines that are switched on.
+    Machines,
+
+    /// Keeps the player afloat in deep water, or not.
+    Water,
+
+    /// Ticks the player's status effects, and wears them off in time.
+    Effects,
+
+    /// Counts down the running deadlines, and ends the game when one runs out.
+    Deadlines,
+
+    /// Fires the events of the pressure plates whose load has changed.
+    Plates,
+
+    /// Moves the story to its next chapter, and determines whether the player has won.
+    Story,
+}
+
+impl TurnSystem {
+    /// Is the system compiled in?  The Machines, Water, Effects, and Deadlines systems
+    /// require the cargo features of the same names.
+    pub fn is_enabled(self) -> bool {
+        match self {
+            TurnSystem::Machines => cfg!(feature = "machines"),
+            TurnSystem::Water => cfg!(feature = "water"),
+            TurnSystem::Effects => cfg!(feature = "effects"),
+            TurnSystem::Deadlines => cfg!(feature = "deadlines"),
+            TurnSystem::RandomEvents | TurnSystem::Plates | TurnSystem::Story => true,
+        }
+    }
+}
+
+/// Which things a verb prefers when a thing the player is carrying and a thing in the
+/// player's location have the same noun.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Preference {
+    /// Prefer the carried thing, e.g., for "drop" or "read".
+    Held,
+
+    /// Prefer the thing in the location, e.g., for "get".
+    Here,
+}
+
+/// What happens when a status effect is applied while it's already active.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[cfg(feature = "effects")]
+pub enum Stacking {
+    /// The effect lasts for the longer of its remaining and new durations.
+    Refresh,
+
+    /// The new duration is added to the remaining duration.
+    Extend,
+
+    /// The effect stacks, e.g., a second dose of poison: its tick script runs once per
+    /// stack, and it lasts for the longer of its remaining and new durations.
+    Stack,
+}
+
+/// Standard combinations of flags and components for common kinds of thing; see
+/// ThingBuilder::preset().
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[allow(dead_code)]
+pub enum Preset {
+    /// Scenery that can't be moved and isn't listed, e.g., a boulder mentioned in the
+    /// room's prose: Immovable and Scenery, weighing nothing.
+    Feature,
+
+    /// A thing that's listed but can't be moved, e.g., a table: Immovable, weighing
+    /// nothing.
+    Fixture,
+
+    /// A thing the player can put things in, e.g., a sack.
+    Container,
+
+    /// A thing that always gives light, e.g., a glowing gem.
+    LightSource,
+
+    /// A machine that gives light while it's switched on, e.g., a flashlight.
+    #[cfg(feature = "machines")]
+    Lamp,
+}
+
+/// What the player forfeits on being revived after a death; see
+/// WorldBuilder::revive_forfeit().
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Default)]
+#[allow(dead_code)]
+pub enum Forfeit {
+    /// The player keeps everything.
+    #[default]
+    Nothing,
+
+    /// The player's things are left where the player died.
+    Drop,
+
+    /// The player's things are lost; essential things turn up at their recovery
+    /// places.
+    Lose,
+}
+
+/// What a guard makes of an event; see rule::outcome().
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Outcome {
+    /// The event goes ahead as usual.
+    Allow,
+
+    /// The event doesn't happen.
+    Deny,
+
+    /// The event happens with a different room as its destination, e.g., the trapdoor
+    /// drops the player into the cellar.  Only EnterRoom guards redirect.
+    Redirect(ID),
+}
+
+/// How often a rule can fire; see RuleBuilder::once_per().
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+#[allow(dead_code)]
+pub enum Once {
+    /// The rule fires no more than once in the game.
+    Game,
+
+    /// The rule fires no more than once per visit to a room: it can fire again once the
+    /// player has left the room the player was in when it fired, and come back.
+    Visit,
+}
+
+/// How an entity's alternate prose strings are chosen on repeat views, so that a room
+/// the player visits often doesn't read identically every time.  The first view always
+/// shows the first string.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub enum Variation {
+    /// Show the strings in turn, starting over after the last.
+    Cycle,
+
+    /// Show a pseudo-random string, never the same one twice in a row.  The choice
+    /// depends only on the entity and the number of views, so it's reproducible.
+    Random,
+}
+
+/// Statistics about the player's session, for the end-of-game summary and for ending
+/// prose, e.g., "You finished in 87 turns."  Undoing a turn doesn't undo the metrics.
+#[derive(Clone, Debug, Default)]
+pub struct Metrics {
+    /// The number of turns taken.
+    pub turns: usize,
+
+    /// The rooms the player has visited.
+    pub rooms: BTreeSet<ID>,
+
+    /// The things the player has carried.
+    pub things: BTreeSet<ID>,
+
+    /// The number of times the player has died.
+    pub deaths: usize,
+
+    /// The number of turns undone.
+    pub undos: usize,
+}
+
+impl Metrics {
+    /// Summarizes the metrics as a phrase, e.g., "12 turns, 3 rooms visited, 2 things
+    /// handled, 0 deaths, and 1 undo".
+    pub fn summary(&self) -> String {
+        let items = [
+            count(self.turns, "turn", "turns"),
+            format!("{} visited", count(self.rooms.len(), "room", "rooms")),
+            format!("{} handled", count(self.things.len(), "thing", "things")),
+            count(self.deaths, "death", "deaths"),
+            count(self.undos, "undo", "undos"),
+        ];
+        let items: Vec<&str> = items.iter().map(|item| item.as_str()).collect();
+
+        list_phrase(&items, "and")
+    }
+}
+
+/// The number of turns of history kept in the event log.
+pub const LOG_TURNS: Time = 20;
+
+/// A log of recent commands, events, and rule firings, each stamped with the clock
+/// time, for diagnosing odd behavior after the fact; see the "!log" debugging command.
+/// Only the last LOG_TURNS turns are kept.  Like the metrics, the log survives undo.
+#[derive(Clone, Debug, Default)]
+pub struct EventLog {
+    entries: VecDeque<(Time, String)>,
+}
+
+impl EventLog {
+    /// Adds an entry at the given clock time, discarding any entries that are too old.
+    pub fn add(&mut self, clock: Time, text: &str) {
+        while let Some((time, _)) = self.entries.front() {
+            if *time + LOG_TURNS > clock {
+                break;
+            }
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back((clock, text.into()));
+    }
+
+    /// Returns the entries, oldest first.
+    pub fn entries(&self) -> &VecDeque<(Time, String)> {
+        &self.entries
+    }
+}
+
+/// A question put to the player by a script, awaiting an answer; see
+/// Script::prompt().  Each answer is paired with the rule or named script to run when the
+/// player gives it; the answer "*" accepts any text.
+#[derive(Clone, Debug)]
+pub struct Prompt {
+    /// The question.
+    pub question: String,
+
+    /// The answers and the IDs of their scripts, in order of preference.
+    pub answers: Vec<(String, ID)>,
+
+    /// The subject of the event that triggered the question, if any.
+    pub subject: Option<ID>,
+
+    /// Has the question been displayed to the player?
+    pub posed: bool,
+}
+
+impl Prompt {
+    /// Returns the ID of the script to run for the player's input, if it answers the
+    /// question.  Answers are matched without regard to case, and "y" and "n" will do
+    /// for "yes" and "no".
+    pub fn script_for(&self, input: &str) -> Option<ID> {
+        let input = input.trim().to_lowercase();
+        let input = match input.as_str() {
+            "y" => "yes",
+            "n" => "no",
+            other => other,
+        };
+
+        self.answers
+            .iter()
+            .find(|(answer, _)| answer == input || (answer == "*" && !input.is_empty()))
+            .map(|(_, script)| *script)
+    }
+
+    /// Returns the question as displayed to the player, listing the expected answers.
+    pub fn text(&self) -> String {
+        let answers: Vec<&str> = self
+            .answers
+            .iter()
+            .map(|(answer, _)| answer.as_str())
+            .filter(|answer| *answer != "*")
+            .collect();
+
+        if answers.is_empty() {
+            self.question.clone()
+        } else {
+            format!("{} ({})", self.question, answers.join("/"))
+        }
+    }
+}
+
+/// Formats a count with the singular or plural noun, e.g., "1 turn", "2 turns".
+fn count(n: usize, singular: &str, plural: &str) -> String {
+    format!("{} {}", n, if n == 1 { singular } else { plural })
+}
+
+/// A recipe: two things that the player can combine to make a third.
+#[derive(Clone, Debug)]
+pub struct Recipe {
+    /// The things to combine, in either order.
+    pub inputs: (ID, ID),
+
+    /// The thing they make.
+    pub output: ID,
+}
+
 /// Things that can happen in the game, to which rules, guards, and hooks can be attached
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Event {
@@ -99,19 +600,216 @@ pub enum Event {
     /// EnterRoom(player, room): A player has entered (or wants to enter) a room
     EnterRoom(ID, ID),
 
-    /// GetThing(player, thing): A player has gotten (or wants to get) a thing
-    GetThing(ID, ID),
+    /// GetThing(player, thing, destination): A player has gotten (or wants to get) a
+    /// thing, into the player's hands or, e.g., straight into a carried sack.  A rule
+    /// for LIMBO as the destination applies to any destination.
+    GetThing(ID, ID, ID),
+
+    /// PutThing(player, thing, container): A player has put (or wants to put) a thing
+    /// into a container.
+    PutThing(ID, ID, ID),
 
     /// ReadThing(player, thing): A player has read (or wants to read) a thing's
     /// Book prose.
     ReadThing(ID, ID),
+
+    /// DetectTrap(player, trap): A player has detected (or is about to detect) a trap
+    DetectTrap(ID, ID),
+
+    /// DisarmTrap(player, trap): A player has disarmed (or wants to disarm) a trap
+    DisarmTrap(ID, ID),
+
+    /// SpringTrap(player, trap): A player has sprung (or is about to spring) a trap
+    SpringTrap(ID, ID),
+
+    /// StartChapter(chapter): The story has moved on to a new chapter
+    StartChapter(ID),
+
+    /// Random(table): A random event table has come up, and this event was chosen.
+    Random(ID),
+
+    /// Unlock(player, thing): A player has unlocked (or wants to unlock) a thing
+    Unlock(ID, ID),
+
+    /// Lock(player, thing): A player has locked (or wants to lock) a thing
+    Lock(ID, ID),
+
+    /// CorrectCode(player, thing): A player has entered (or is about to enter) the
+    /// thing's combination
+    CorrectCode(ID, ID),
+
+    /// WrongCode(player, thing): A player has entered the wrong combination on the thing
+    WrongCode(ID, ID),
+
+    /// SwitchOn(player, machine): A player has switched on (or wants to switch on) a machine
+    SwitchOn(ID, ID),
+
+    /// SwitchOff(player, machine): A player has switched off (or wants to switch off) a
+    /// machine
+    SwitchOff(ID, ID),
+
+    /// Running(machine): The machine is switched on and powered; fired each turn.
+    Running(ID),
+
+    /// Manipulate(player, thing, how): A player has manipulated (or wants to manipulate)
+    /// a thing, e.g., pushed a button or pulled a lever.
+    Manipulate(ID, ID, Manipulation),
+
+    /// Exhausted(thing): The consumable thing has used up its last charge.
+    Exhausted(ID),
+
+    /// Combine(player, output): A player has combined (or wants to combine) two things
+    /// to make the output.
+    Combine(ID, ID),
+
+    /// PlatformMove(player, platform, stop): A player has sent (or wants to send) a
+    /// platform to the stop.
+    PlatformMove(ID, ID, ID),
+
+    /// Climb(player, thing): A player has climbed (or wants to climb) a thing that
+    /// doesn't lead anywhere.
+    Climb(ID, ID),
+
+    /// Drown(player): A player has drowned (or is about to drown) in deep water.
+    Drown(ID),
+
+    /// Call(script): A named script has been called.  Nothing else triggers it.
+    Call(ID),
+
+    /// Wash(player, thing): A player has washed (or wants to wash) a thing.
+    Wash(ID, ID),
+
+    /// Steal(player, owner, thing): A player has taken (or wants to take) a thing in
+    /// its owner's presence.  A rule for LIMBO as the thing applies to all of the
+    /// owner's things.
+    Steal(ID, ID, ID),
+
+    /// Stumble(player, room): A player has stumbled about in a dark room, failing to
+    /// go anywhere.
+    Stumble(ID, ID),
+
+    /// InventoryChanged(player): Something has been added to or removed from what the
+    /// player is carrying, directly or in a container.
+    InventoryChanged(ID),
+
+    /// PlatePressed(plate): The load on a pressure plate has risen to its threshold.
+    PlatePressed(ID),
+
+    /// PlateReleased(plate): The load on a pressure plate has fallen below its
+    /// threshold.
+    PlateReleased(ID),
+
+    /// EnterPortal(player, portal): A player has entered (or wants to enter) a portal.
+    EnterPortal(ID, ID),
+
+    /// TimeUp(player, deadline): A deadline has run out on a player.
+    TimeUp(ID, ID),
+
+    /// Search(player, room): A player has searched (or wants to search) a room.
+    Search(ID, ID),
+
+    /// Revive(player): A player has been revived after a death.
+    Revive(ID),
+}
+
+impl Event {
+    /// Returns the entities the event refers to.
+    pub fn entities(&self) -> Vec<ID> {
+        match self {
+            Event::Turn => Vec::new(),
+            Event::EnterRoom(a, b) => vec![*a, *b],
+            Event::GetThing(a, b, c) => vec![*a, *b, *c],
+            Event::PutThing(a, b, c) => vec![*a, *b, *c],
+            Event::ReadThing(a, b) => vec![*a, *b],
+            Event::DetectTrap(a, b) => vec![*a, *b],
+            Event::DisarmTrap(a, b) => vec![*a, *b],
+            Event::SpringTrap(a, b) => vec![*a, *b],
+            Event::StartChapter(a) => vec![*a],
+            Event::Random(a) => vec![*a],
+            Event::Unlock(a, b) => vec![*a, *b],
+            Event::Lock(a, b) => vec![*a, *b],
+            Event::CorrectCode(a, b) => vec![*a, *b],
+            Event::WrongCode(a, b) => vec![*a, *b],
+            Event::SwitchOn(a, b) => vec![*a, *b],
+            Event::SwitchOff(a, b) => vec![*a, *b],
+            Event::Running(a) => vec![*a],
+            Event::Manipulate(a, b, _) => vec![*a, *b],
+            Event::Exhausted(a) => vec![*a],
+            Event::Combine(a, b) => vec![*a, *b],
+            Event::PlatformMove(a, b, c) => vec![*a, *b, *c],
+            Event::Climb(a, b) => vec![*a, *b],
+            Event::Drown(a) => vec![*a],
+            Event::Call(a) => vec![*a],
+            Event::Wash(a, b) => vec![*a, *b],
+            Event::Steal(a, b, c) => vec![*a, *b, *c],
+            Event::Stumble(a, b) => vec![*a, *b],
+            Event::InventoryChanged(a) => vec![*a],
+            Event::PlatePressed(a) => vec![*a],
+            Event::PlateReleased(a) => vec![*a],
+            Event::EnterPortal(a, b) => vec![*a, *b],
+            Event::TimeUp(a, b) => vec![*a, *b],
+            Event::Search(a, b) => vec![*a, *b],
+            Event::Revive(a) => vec![*a],
+        }
+    }
+
+    /// Returns the entity the event is chiefly about, e.g., the thing that was gotten,
+    /// if any.  For events involving a player and something else, it's the something
+    /// else.
+    pub fn subject(&self) -> Option<ID> {
+        match self {
+            Event::Turn => None,
+            Event::GetThing(_, b, _) => Some(*b),
+            Event::PutThing(_, b, _) => Some(*b),
+            Event::Manipulate(_, b, _) => Some(*b),
+            Event::PlatformMove(_, b, _) => Some(*b),
+            _ => self.entities().last().cloned(),
+        }
+    }
+}
+
+/// The standard ways of manipulating a thing, each with its own verb.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Manipulation {
+    Push,
+    Pull,
+    Turn,
+    Touch,
+    Shake,
+}
+
+impl Manipulation {
+    /// The verb the player uses for the manipulation.
+    pub fn verb(self) -> &'static str {
+        match self {
+            Manipulation::Push => "push",
+            Manipulation::Pull => "pull",
+            Manipulation::Turn => "turn",
+            Manipulation::Touch => "touch",
+            Manipulation::Shake => "shake",
+        }
+    }
+}
+
+/// Who can perceive a message printed by a script.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Audience {
+    /// The message is always displayed.
+    Global,
+
+    /// Room(room): The message is displayed only if the player is in the tagged room.
+    Room(String),
+
+    /// Region(flag): The message is displayed only if the player is in a room with
+    /// the given flag.  Scenarios define regions by flagging their rooms.
+    Region(Flag),
 }
 
 /// The destination of a link.
 #[derive(Clone, Debug)]
 pub enum LinkDest {
     /// The link goes to another room.
-    Room(ID),
+    Room(RoomID),
 
     /// The link is a dead end.  The string is the prose to display to
     /// the user.
@@ -161,12 +859,42 @@ impl ProseBuffer {
         self.buff.push_str(text);
     }
 
+    /// Adds a sentence to the buffer if the condition is true.
+    pub fn when(&mut self, flag: bool, text: &str) {
+        if flag {
+            self.puts(text);
+        }
+    }
+
+    /// Adds one sentence to the buffer if the condition is true, and another if
+    /// it is false.
+    pub fn when_else(&mut self, flag: bool, if_true: &str, if_false: &str) {
+        if flag {
+            self.puts(if_true);
+        } else {
+            self.puts(if_false);
+        }
+    }
+
+    /// Adds a list of items to the buffer as a phrase, e.g., "a, b, and c".
+    /// The conjunction is usually "and" or "or".
+    pub fn list(&mut self, items: &[&str], conjunction: &str) {
+        self.add_white_space_if_needed();
+        self.put_raw(&list_phrase(items, conjunction));
+    }
+
+    /// Adds text to the buffer as a sentence: the first letter is capitalized,
+    /// and a period is added if there's no closing punctuation.
+    pub fn sentence(&mut self, text: &str) {
+        self.puts(&sentence(text));
+    }
+
     /// Converts the buffer to a string.
     pub fn get(&self) -> String {
         self.buff.clone()
     }
 
-    /// Adds white space before a new sentence, if needed.
+    /// Adds white space before new text, if needed.
     fn add_white_space_if_needed(&mut self) {
         let len = self.buff.len();
 
@@ -178,3 +906,179 @@ impl ProseBuffer {
         }
     }
 }
+
+/// Formats a list of items as a phrase, e.g., "a", "a and b", "a, b, and c".
+/// The conjunction is usually "and" or "or".
+pub fn list_phrase(items: &[&str], conjunction: &str) -> String {
+    match items.len() {
+        0 => String::new(),
+        1 => items[0].to_string(),
+        2 => format!("{} {} {}", items[0], conjunction, items[1]),
+        n => format!("{}, {} {}", items[..n - 1].join(", "), conjunction, items[n - 1]),
+    }
+}
+
+/// The number words the game writes and reads, from "one" to "twelve".
+const NUMBER_WORDS: [&str; 12] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+    "twelve",
+];
+
+/// Formats a count as a word, e.g., "five", or as digits if it's more than twelve.
+pub fn number_word(n: usize) -> String {
+    match n {
+        1..=12 => NUMBER_WORDS[n - 1].into(),
+        _ => n.to_string(),
+    }
+}
+
+/// The number words from "thirteen" to "nineteen".
+const TEEN_WORDS: [&str; 7] =
+    ["thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen"];
+
+/// The number words for the tens, from "twenty" to "ninety".
+const TENS_WORDS: [&str; 8] =
+    ["twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+
+/// Parses a count written as a word, e.g., "five", or as digits.  Returns None if the
+/// word isn't a number, or is zero.
+pub fn parse_number(word: &str) -> Option<usize> {
+    parse_numeral(word).filter(|n| *n > 0)
+}
+
+/// Parses a number written as digits, e.g., "42", or as a word from "zero" to
+/// "ninety-nine", e.g., "forty-two".  Returns None if the word isn't a number.
+pub fn parse_numeral(word: &str) -> Option<usize> {
+    if !word.is_empty() && word.chars().all(|c| c.is_ascii_digit()) {
+        return word.parse().ok();
+    }
+
+    let unit = |w: &str| match w {
+        "zero" => Some(0),
+        _ => NUMBER_WORDS.iter().position(|u| *u == w).map(|i| i + 1),
+    };
+    let teen = |w: &str| TEEN_WORDS.iter().position(|t| *t == w).map(|i| i + 13);
+    let tens = |w: &str| TENS_WORDS.iter().position(|t| *t == w).map(|i| 20 + i * 10);
+
+    match word.split('-').collect::<Vec<_>>().as_slice() {
+        [w] => unit(w).or_else(|| teen(w)).or_else(|| tens(w)),
+        [t, u] => match (tens(t), unit(u)) {
+            (Some(t), Some(u)) if (1..10).contains(&u) => Some(t + u),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Formats text as a sentence: the first letter is capitalized, and a period is added
+/// if there's no closing punctuation.
+pub fn sentence(text: &str) -> String {
+    let text = text.trim();
+    let mut chars = text.chars();
+    let mut result: String = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => return String::new(),
+    };
+
+    if !result.ends_with(|c| c == '.' || c == '!' || c == '?' || c == '"') {
+        result.push('.');
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_phrase() {
+        assert_eq!(list_phrase(&[], "and"), "");
+        assert_eq!(list_phrase(&["a"], "and"), "a");
+        assert_eq!(list_phrase(&["a", "b"], "or"), "a or b");
+        assert_eq!(list_phrase(&["a", "b", "c"], "and"), "a, b, and c");
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(number_word(5), "five");
+        assert_eq!(number_word(20), "20");
+        assert_eq!(parse_number("twelve"), Some(12));
+        assert_eq!(parse_number("20"), Some(20));
+        assert_eq!(parse_number("0"), None);
+        assert_eq!(parse_number("coins"), None);
+        assert_eq!(parse_numeral("0"), Some(0));
+        assert_eq!(parse_numeral("zero"), Some(0));
+        assert_eq!(parse_numeral("fifteen"), Some(15));
+        assert_eq!(parse_numeral("forty"), Some(40));
+        assert_eq!(parse_numeral("forty-two"), Some(42));
+        assert_eq!(parse_numeral("forty-twelve"), None);
+        assert_eq!(parse_numeral("-5"), None);
+    }
+
+    #[test]
+    fn test_sentence() {
+        assert_eq!(sentence(""), "");
+        assert_eq!(sentence("you win"), "You win.");
+        assert_eq!(sentence("You win!"), "You win!");
+    }
+
+    #[test]
+    fn test_when_else() {
+        let mut buff = ProseBuffer::new();
+        buff.when_else(true, "Yes.", "No.");
+        buff.when_else(false, "Yes.", "No.");
+        buff.when(false, "Maybe.");
+        assert_eq!(buff.get(), "Yes.\nNo.");
+    }
+
+    #[test]
+    fn test_metrics_summary() {
+        let mut metrics = Metrics::default();
+        metrics.turns = 12;
+        metrics.rooms.insert(1);
+        metrics.undos = 1;
+        assert_eq!(
+            metrics.summary(),
+            "12 turns, 1 room visited, 0 things handled, 0 deaths, and 1 undo"
+        );
+    }
+
+    #[test]
+    fn test_event_log() {
+        let mut log = EventLog::default();
+        log.add(0, "Command: look");
+        log.add(1, "Command: get note");
+        log.add(LOG_TURNS, "Command: drop note");
+
+        let texts: Vec<&str> = log.entries().iter().map(|(_, text)| text.as_str()).collect();
+        assert_eq!(texts, vec!["Command: get note", "Command: drop note"]);
+    }
+
+    #[test]
+    fn test_prompt() {
+        let prompt = Prompt {
+            question: "Do you want another chance?".into(),
+            answers: vec![("yes".into(), 1), ("no".into(), 2), ("*".into(), 3)],
+            subject: None,
+            posed: false,
+        };
+
+        assert_eq!(prompt.text(), "Do you want another chance? (yes/no)");
+        assert_eq!(prompt.script_for(" Y "), Some(1));
+        assert_eq!(prompt.script_for("no"), Some(2));
+        assert_eq!(prompt.script_for("maybe"), Some(3));
+        assert_eq!(prompt.script_for(""), None);
+    }
+
+    #[test]
+    fn test_event_subject() {
+        assert_eq!(Event::Turn.subject(), None);
+        assert_eq!(Event::GetThing(1, 2, 1).subject(), Some(2));
+        assert_eq!(Event::PutThing(1, 2, 3).subject(), Some(2));
+        assert_eq!(Event::Drown(1).subject(), Some(1));
+        assert_eq!(Event::Manipulate(1, 2, Manipulation::Push).subject(), Some(2));
+        assert_eq!(Event::PlatformMove(1, 2, 3).subject(), Some(2));
+        assert_eq!(Event::Steal(1, 2, 3).subject(), Some(3));
+    }
+}
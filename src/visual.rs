@@ -9,14 +9,35 @@
 // appropriate moments in processing; thus, this module is called as needed, rather than
 // doing its work all at once.
 
-use crate::console::para;
+use crate::consumable;
+use crate::console;
+use crate::dark;
+use crate::entity::prose_component::Prose;
+#[cfg(feature = "effects")]
+use crate::effect;
 use crate::entity::ID;
+use crate::lock;
+#[cfg(feature = "machines")]
+use crate::machine;
 use crate::phys;
+use crate::records::Record;
+use crate::types::Audience;
+use crate::types::Flag;
+use crate::types::OutputHook;
 use crate::types::ProseType;
+use crate::types::list_phrase;
+use crate::types::number_word;
+use crate::types::sentence;
 use crate::types::ProseBuffer;
 use crate::world::World;
+use std::cell::Cell;
 use std::collections::BTreeSet;
 
+thread_local! {
+    /// The scenario's output hook, if any.
+    static OUTPUT_HOOK: Cell<Option<OutputHook>> = Cell::new(None);
+}
+
 //-----------------------------------------------------------------------------
 // Types
 
@@ -27,6 +48,23 @@ enum Detail {
     Brief,
 }
 
+//-----------------------------------------------------------------------------
+// Output
+
+/// Sets the hook that post-processes each block of prose before it is displayed.
+pub fn set_output_hook(hook: Option<OutputHook>) {
+    OUTPUT_HOOK.with(|h| h.set(hook));
+}
+
+/// Outputs the text as a paragraph, after applying the output hook.  All of this
+/// module's output goes through here.
+fn para(text: &str) {
+    match OUTPUT_HOOK.with(|h| h.get()) {
+        Some(hook) => console::para(&hook(text)),
+        None => console::para(text),
+    }
+}
+
 //-----------------------------------------------------------------------------
 // Basic Messages
 //
@@ -48,13 +86,87 @@ pub fn info(msg: &str) {
     para(msg);
 }
 
+/// Outputs an important announcement, e.g., the player's death.  Normally the
+/// announcement is set off with asterisks; in accessible mode, it's displayed plainly.
+pub fn banner(msg: &str) {
+    if console::is_accessible() {
+        para(msg);
+    } else {
+        para!("*** {} ***", msg);
+    }
+}
+
+/// Outputs a message produced by a script, provided that the player can perceive it.
+/// In omniscient mode (a debugging aid) the player perceives everything; messages from
+/// elsewhere are marked as such.
+pub fn observe(world: &World, audience: &Audience, msg: &str) {
+    if can_perceive(world, audience) {
+        info(msg);
+    } else if world.omniscient {
+        para!("(Elsewhere: {:?}) {}", audience, msg);
+    }
+}
+
+/// Outputs a cutscene: a sequence of pages, with a "[more]" prompt between them.
+/// The cutscene takes over the output: nothing else is displayed for the remainder
+/// of the turn.
+pub fn cutscene(pages: &[String]) {
+    for (i, page) in pages.iter().enumerate() {
+        if i > 0 {
+            console::more();
+        }
+        para(page);
+    }
+
+    console::suppress(true);
+}
+
+/// Outputs a sound made in the origin room, provided that the player is close enough
+/// to hear it.  The loudness is the number of links the sound carries across.  The
+/// sound should be a noun phrase, e.g., "a distant crash".
+pub fn hear(world: &World, origin: ID, loudness: usize, sound: &str) {
+    let here = phys::loc(world, world.pid);
+
+    match phys::distance(world, origin, here, loudness) {
+        Some(0) => {
+            para!("$You $hear {}.", sound);
+        }
+        Some(_) => {
+            para!("$You $hear {} in the distance.", sound);
+        }
+        None => {
+            if world.omniscient {
+                para!("(Elsewhere: {}) $You $hear {}.", world.tag(origin), sound);
+            }
+        }
+    }
+}
+
+/// Can the player perceive messages sent to the given audience?
+pub fn can_perceive(world: &World, audience: &Audience) -> bool {
+    let here = phys::loc(world, world.pid);
+
+    match audience {
+        Audience::Global => true,
+        Audience::Room(tag) => world.lookup(tag) == here,
+        Audience::Region(flag) => world.has_flags(here) && world.has_flag(here, *flag),
+    }
+}
+
 //-----------------------------------------------------------------------------
 // Room Visuals
 
 /// Outputs a full description of a room.
 ///
 /// A full description includes the room's name, visual, and any things that are present.
-pub fn room(world: &World, id: ID) {
+pub fn room(world: &mut World, id: ID) {
+    if !dark::is_dark(world, id) {
+        view_prose(world, id, ProseType::Room);
+        for sid in phys::scenery(world, id) {
+            view_prose(world, sid, ProseType::Scenery);
+        }
+    }
+
     print_room(world, id, Detail::Full);
 }
 
@@ -73,13 +185,20 @@ pub fn room_brief(world: &World, id: ID) {
 fn print_room(world: &World, id: ID, detail: Detail) {
     let roomc = &world.rooms[&id];
 
-    // FIRST, display the room's description
+    // FIRST, in the dark there's nothing to see.
+    if dark::is_dark(world, id) {
+        para("Darkness");
+        para("It's pitch dark.  $You $can't see a thing.");
+        return;
+    }
+
+    // NEXT, display the room's description
     if detail == Detail::Full {
         let mut buff = ProseBuffer::new();
         buff.puts(&roomc.name);
         buff.newline();
         buff.puts(&get_prose(world, id, ProseType::Room));
-        for sid in phys::scenery(world, id) {
+        for sid in phys::in_order(world, &phys::scenery(world, id)) {
             if world.has_prose_type(sid, ProseType::Scenery) {
                 buff.puts(&get_prose(world, sid, ProseType::Scenery));
             }
@@ -89,12 +208,61 @@ fn print_room(world: &World, id: ID, detail: Detail) {
         para(&roomc.name);
     }
 
-    // NEXT, list any "removable" objects in the room's inventory.  (We don't list
+    // NEXT, add the ambient lines of any running machines.
+    #[cfg(feature = "machines")]
+    ambient(world, id);
+
+    // NEXT, describe any things that haven't been moved yet and have initial prose.
+    let mut things = phys::non_scenery(world, id);
+    let initial: Vec<ID> = phys::in_order(world, &things)
+        .into_iter()
+        .filter(|tid| world.has_prose_type(*tid, ProseType::Initial))
+        .filter(|tid| !world.has_flag(*tid, Flag::Moved))
+        .collect();
+
+    for tid in initial {
+        para(&get_prose(world, tid, ProseType::Initial));
+        things.remove(&tid);
+    }
+
+    // NEXT, list any other "removable" objects in the room's inventory.  (We don't list
     // scenary; presumably that's in the description.)
-    let list = invent_list(world, &phys::non_scenery(world, id));
+    let list = invent_list(world, &things);
 
     if !list.is_empty() {
-        para!("You see: {}.", list);
+        para!("$You $see {}.", list);
+    }
+
+    // NEXT, mention any distant things visible from here.
+    let mut buff = ProseBuffer::new();
+    for (dir, tid) in phys::distant(world, world.pid) {
+        buff.sentence(&format!("{} $you $see {}", dir.phrase(), a_name(world, tid)));
+    }
+    if !buff.get().is_empty() {
+        para(&buff.get());
+    }
+}
+
+/// Outputs the ambient lines of the running machines in the room, if any.
+#[cfg(feature = "machines")]
+fn ambient(world: &World, room: ID) {
+    let mut buff = ProseBuffer::new();
+    for mid in phys::in_order(world, &phys::contents(world, room)) {
+        if let Some(text) = machine::ambient(world, mid) {
+            buff.puts(text);
+        }
+    }
+    if !buff.get().is_empty() {
+        para(&buff.get());
+    }
+}
+
+/// Announces that the player has arrived in a room.  The announcement is only made in
+/// accessible mode, so that screen reader users needn't infer the move from the room
+/// description.
+pub fn arrive(world: &World, id: ID) {
+    if console::is_accessible() {
+        para!("$You $are now in {}.", world.rooms[&id].name);
     }
 }
 
@@ -102,14 +270,60 @@ fn print_room(world: &World, id: ID, detail: Detail) {
 // Thing Visuals
 
 /// Outputs a description of a thing.
-pub fn thing(world: &World, id: ID) {
-    // FIRST, display the thing's description
-    para(&get_prose(world, id, ProseType::Thing));
+pub fn thing(world: &mut World, id: ID) {
+    view_prose(world, id, ProseType::Thing);
+    para(&thing_prose(world, id));
 
     // TODO: eventually we will want to describe its contents, if it has
     // contents, or other changeable state.
 }
 
+/// Outputs the descriptions of several things in a compact format, a line apiece, for
+/// "examine all".
+pub fn things(world: &mut World, ids: &[ID]) {
+    for id in ids {
+        view_prose(world, *id, ProseType::Thing);
+    }
+
+    let mut buff = ProseBuffer::new();
+
+    for (i, id) in ids.iter().enumerate() {
+        if i > 0 {
+            buff.newline();
+        }
+        let text = format!("{}: {}", the_name(world, *id), thing_prose(world, *id));
+        buff.puts(&sentence(&text));
+    }
+
+    para(&buff.get());
+}
+
+/// Returns the thing's description, with its visible state, e.g., "It's locked."
+fn thing_prose(world: &World, id: ID) -> String {
+    let mut buff = ProseBuffer::new();
+    buff.puts(&get_prose(world, id, ProseType::Thing));
+    if world.has_flag(id, Flag::Trapped) && world.has_flag(id, Flag::Detected) {
+        buff.puts("It's trapped.");
+    }
+    if lock::is_locked(world, id) {
+        buff.puts("It's locked.");
+    }
+    if let Some(text) = consumable::exhausted_prose(world, id) {
+        buff.puts(text);
+    }
+    buff.get()
+}
+
+/// Outputs a description of a distant thing, as seen from an adjacent room.  Uses
+/// the thing's Distant prose, if it has any.
+pub fn distant_thing(world: &World, id: ID) {
+    if world.has_prose_type(id, ProseType::Distant) {
+        para(&get_prose(world, id, ProseType::Distant));
+    } else {
+        para(&get_prose(world, id, ProseType::Thing));
+    }
+}
+
 /// Can this be read as a book?
 pub fn can_read(world: &World, thing: ID) -> bool {
     world.has_prose_type(thing, ProseType::Book)
@@ -125,6 +339,22 @@ pub fn read(world: &World, book: ID) {
     act(&buff.get());
 }
 
+/// Outputs the topics that the thing can currently be asked about.
+pub fn topics(world: &World, id: ID) {
+    let names: Vec<&str> = world.topics[&id]
+        .topics
+        .iter()
+        .filter(|t| (t.predicate)(world))
+        .map(|t| t.name.as_ref())
+        .collect();
+
+    if names.is_empty() {
+        para!("$You $can't think of anything to ask the {} about.", world.things[&id].noun);
+    } else {
+        para!("$You could ask the {} about: {}.", world.things[&id].noun, names.join(", "));
+    }
+}
+
 //-----------------------------------------------------------------------------
 // Player Visuals
 
@@ -133,7 +363,7 @@ pub fn player(world: &World, pid: ID) {
     // FIRST, display the player's description
     let mut buff = ProseBuffer::new();
     buff.puts(&get_prose(world, pid, ProseType::Thing));
-    for sid in phys::scenery(world, pid) {
+    for sid in phys::in_order(world, &phys::scenery(world, pid)) {
         if world.has_prose_type(sid, ProseType::Scenery) {
             let prose = &get_prose(world, sid, ProseType::Scenery);
             // With a prose hook, result could be empty.
@@ -144,41 +374,249 @@ pub fn player(world: &World, pid: ID) {
     }
     para(&buff.get());
 
+    #[cfg(feature = "effects")]
+    if let Some(effects) = effect::describe(world, pid) {
+        para!("$You $are {}.", effects);
+    }
+
     // TODO: Could add inventory.
 }
 
+/// Outputs the player's notes, numbered, with the turn on which each was written.
+pub fn notes(world: &World, pid: ID) {
+    let notes = &world.players[&pid].notes;
+
+    if notes.is_empty() {
+        para("$You $have no notes.");
+        return;
+    }
+
+    let mut buff = ProseBuffer::new();
+    buff.puts("Notes:");
+
+    for (i, note) in notes.iter().enumerate() {
+        buff.newline();
+        buff.puts(&format!("  {}. (Turn {}) {}", i + 1, note.turn, note.text));
+    }
+
+    para(&buff.get());
+}
+
+/// Outputs the entries in the player's quest journal.
+pub fn journal(world: &World, pid: ID) {
+    let journal = &world.players[&pid].journal;
+
+    if journal.is_empty() {
+        para("$Your journal is empty.");
+        return;
+    }
+
+    for entry in journal {
+        para(&entry.text);
+    }
+}
+
 /// Outputs the player's inventory
 pub fn player_inventory(world: &World, pid: ID) {
     // A player's inventory is precisely the things that they are carrying that
     // are (in theory at least) droppable: the player's sword, but not the player's hands.
     let ids = phys::droppable(world, pid);
 
-    if ids.is_empty() {
-        para("You aren't carrying anything.");
+    // In the dark, the player goes by touch.
+    let by_touch = dark::is_dark(world, phys::loc(world, pid));
+
+    match (ids.is_empty(), by_touch) {
+        (true, false) => para("$You $aren't carrying anything."),
+        (true, true) => para("$Your hands are empty."),
+        (false, false) => {
+            para!("$You $have {}.", invent_list(world, &ids));
+        }
+        (false, true) => {
+            para!("By touch, $you $have {}.", invent_list(world, &ids));
+        }
+    }
+}
+
+/// List the names of the entities with their articles in listing order, e.g., "a
+/// note, a sword, and some water".
+pub fn invent_list(world: &World, ids: &BTreeSet<ID>) -> String {
+    // Identical things are listed together, e.g., "five gold coins"; they're adjacent in
+    // listing order.
+    let mut groups: Vec<(ID, usize)> = Vec::new();
+
+    for id in phys::in_order(world, ids) {
+        match groups.last_mut() {
+            Some((first, count)) if world.things[first].is_like(&world.things[&id]) => {
+                *count += 1;
+            }
+            _ => groups.push((id, 1)),
+        }
+    }
+
+    // Things with the same name are told apart by their adjectives, e.g., "a red key
+    // and a rusty key".
+    let same_name = |id: &ID| {
+        let name = &world.things[id].name;
+        groups.iter().filter(|(other, _)| world.things[other].name == *name).count() > 1
+    };
+
+    let names: Vec<String> = groups
+        .iter()
+        .map(|(id, count)| match &world.things[id].plural {
+            Some((plural, _)) if *count > 1 => format!("{} {}", number_word(*count), plural),
+            _ if same_name(id) => world.things[id].a_full_name(),
+            _ => a_name(world, *id),
+        })
+        .collect();
+    let names: Vec<&str> = names.iter().map(|n| n.as_ref()).collect();
+
+    list_phrase(&names, "and")
+}
+
+//-----------------------------------------------------------------------------
+// Status Visuals
+
+/// Returns the status line: the player's location, the score (if the scenario keeps
+/// score), and the number of turns.
+pub fn status_line(world: &World) -> String {
+    let here = phys::loc(world, world.pid);
+    let mut status = String::new();
+
+    if world.is_room(here) {
+        status.push_str(&world.rooms[&here].name);
     } else {
-        para!("You have: {}.\n", invent_list(world, &ids));
+        status.push_str(&world.things[&here].name);
     }
+
+    if world.scored {
+        status.push_str(&format!(" | Score: {}", world.score));
+    }
+
+    status.push_str(&format!(" | Turns: {}", world.clock));
+    format!("[ {} ]", status)
 }
 
-/// List the names of the entities, separated by commas.
-fn invent_list(world: &World, ids: &BTreeSet<ID>) -> String {
-    let mut list = String::new();
+/// Outputs the summary of the session metrics, at the end of the game.
+pub fn metrics(world: &World) {
+    para(&format!("This session: {}.", world.metrics.summary()));
+}
 
-    for id in ids {
-        let thingc = &world.things[&id];
+/// Outputs the best runs of the scenario, from the records file.
+pub fn records(records: &[Record]) {
+    if records.is_empty() {
+        para("There are no records yet.");
+        return;
+    }
+
+    let mut buff = ProseBuffer::new();
+    buff.puts("Best runs:");
+
+    for (i, r) in records.iter().enumerate() {
+        buff.newline();
+        buff.puts(&format!("  {}. {} turns, score {}", i + 1, r.turns, r.score));
+    }
 
-        if !list.is_empty() {
-            list.push_str(", ");
+    para(&buff.get());
+}
+
+/// Outputs the title of a chapter, as the story enters it.
+pub fn chapter_title(world: &World, chapter: ID) {
+    banner(&world.chapters[&chapter].title);
+}
+
+/// Outputs the current chapter and the status of its goals.
+pub fn chapter(world: &World, chapter: ID) {
+    let chapterc = &world.chapters[&chapter];
+    let mut buff = ProseBuffer::new();
+    buff.puts(&chapterc.title);
+
+    for goal in &chapterc.goals {
+        buff.newline();
+        let done = (goal.predicate)(world);
+        if console::is_accessible() {
+            let status = if done { "Done" } else { "Not done" };
+            buff.puts(&format!("{}: {}", status, goal.text));
+        } else if done {
+            buff.puts(&format!("  [x] {}", goal.text));
+        } else {
+            buff.puts(&format!("  [ ] {}", goal.text));
         }
-        list.push_str(&thingc.name);
     }
 
-    list
+    para(&buff.get());
+}
+
+/// Outputs a change in the player's score.
+pub fn score(points: i32) {
+    let amount = match points.abs() {
+        1 => "1 point".to_string(),
+        n => format!("{} points", n),
+    };
+    let msg = if points >= 0 {
+        format!("$Your score has gone up by {}.", amount)
+    } else {
+        format!("$Your score has gone down by {}.", amount)
+    };
+
+    if console::is_accessible() {
+        para(&msg);
+    } else {
+        para!("[{}]", msg);
+    }
 }
 
 //-----------------------------------------------------------------------------
 // Helpers
 
+/// Returns the thing's name with its indefinite article, e.g., "a note", "an apple",
+/// "some water", "Excalibur".
+pub fn a_name(world: &World, id: ID) -> String {
+    world.things[&id].a_name()
+}
+
+/// Returns the thing's name with the definite article, e.g., "the note", "Excalibur".
+pub fn the_name(world: &World, id: ID) -> String {
+    world.things[&id].the_name()
+}
+
+/// Counts a view of the entity's prose of the given type: prose variants move on to
+/// the next text, which get_prose() then returns.
+fn view_prose(world: &mut World, id: ID, prose_type: ProseType) {
+    let variants = match world.proses.get(&id).and_then(|p| p.types.get(&prose_type)) {
+        Some(Prose::Variants(variants)) => variants.clone(),
+        _ => return,
+    };
+
+    let key = (id, prose_type);
+    let (views, last) = world.prose_views.get(&key).copied().unwrap_or((0, 0));
+    let next = variants.choose(views, last, &mut world.rng);
+    world.prose_views.insert(key, (views + 1, next));
+}
+
+/// Fills in the placeholders that any prose can use: "{visits}" becomes the number of
+/// times the player has entered the room they're in, and "{deaths}" the number of times
+/// the player has died, e.g., "twice".
+pub fn expand(world: &World, text: &str) -> String {
+    let mut text = text.to_string();
+
+    if text.contains("{visits}") {
+        let here = phys::loc(world, world.pid);
+        let visits = world.rooms.get(&here).map_or(0, |roomc| roomc.visits);
+        text = text.replace("{visits}", &visits.to_string());
+    }
+
+    if text.contains("{deaths}") {
+        let times = match world.metrics.deaths {
+            1 => "once".to_string(),
+            2 => "twice".to_string(),
+            n => format!("{} times", n),
+        };
+        text = text.replace("{deaths}", &times);
+    }
+
+    text
+}
+
 /// Get the specific type of prose from the entity
 pub fn get_prose(world: &World, id: ID, prose_type: ProseType) -> String {
     assert!(world.has_prose(id), "Not prose: [{}]", id);
@@ -186,8 +624,8 @@ pub fn get_prose(world: &World, id: ID, prose_type: ProseType) -> String {
     let prosec = &world.proses[&id];
 
     if let Some(prose) = &prosec.types.get(&prose_type) {
-        prose.as_string(world, id)
+        expand(world, &prose.as_string(world, id, prose_type))
     } else {
-        "You don't see anything special.".to_string()
+        "$You $don't see anything special.".to_string()
     }
 }
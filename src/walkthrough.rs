@@ -0,0 +1,76 @@
+//! Walkthrough Recording and Verification
+//!
+//! A walkthrough is a text file containing the commands for a successful playthrough,
+//! one per line.  Blank lines and lines beginning with "#" are ignored.  Walkthroughs
+//! are recorded by running the game with "--record <file>", and are verified by
+//! replaying them against the current scenario; this keeps the scenario working as the
+//! engine evolves.
+
+use crate::console;
+use crate::types::Flag;
+use crate::Game;
+use std::fs;
+use std::io;
+
+/// Saves the commands to the file as a walkthrough.
+pub fn save(commands: &[String], path: &str) -> io::Result<()> {
+    let mut text = String::new();
+
+    for cmd in commands {
+        text.push_str(cmd);
+        text.push('\n');
+    }
+
+    fs::write(path, text)
+}
+
+/// Loads the commands from a walkthrough file.
+pub fn load(path: &str) -> io::Result<Vec<String>> {
+    let text = fs::read_to_string(path)?;
+
+    Ok(text
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_string())
+        .collect())
+}
+
+/// Replays the walkthrough in the given file against a new game.  Returns an error
+/// describing the first divergence, i.e., the first command that fails or kills the
+/// player, or an error if the player hasn't won when the walkthrough is complete.
+pub fn verify(path: &str) -> Result<(), String> {
+    let commands = load(path).map_err(|err| format!("Cannot read {}: {}", path, err))?;
+
+    console::set_interactive(false);
+
+    let mut game = Game::new();
+    game.introduce();
+
+    for (i, cmd) in commands.iter().enumerate() {
+        if let Err(msg) = game.step(cmd) {
+            return Err(format!("Step {}, \"{}\": {}", i + 1, cmd, msg));
+        }
+
+        if game.world.has_flag(game.world.pid, Flag::Dead) {
+            return Err(format!("Step {}, \"{}\": the player died", i + 1, cmd));
+        }
+    }
+
+    if game.world.won {
+        Ok(())
+    } else {
+        Err(format!("The walkthrough ended after {} steps without winning", commands.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scenario_walkthrough() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/walkthrough.txt");
+        assert_eq!(verify(path), Ok(()));
+    }
+}
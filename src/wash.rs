@@ -0,0 +1,114 @@
+//! The Washing Module
+//!
+//! The player can "wash" (or "clean") a thing wherever there's water: in a room flagged
+//! HasWater, e.g., beside a pool, or when a thing flagged HasWater, e.g., a bucket, is
+//! carried or at hand.  Washing clears the thing's dirt flag, which is Dirty unless the
+//! scenario chooses another, and fires a Wash event for rules.
+//!
+//! If the scenario enables contamination, a dirty actor, i.e., one that is dirty itself
+//! or has a dirty body part such as its hands, dirties whatever it picks up.  Things
+//! flagged NeverDirty are exempt.
+
+use crate::entity::ID;
+use crate::entity::PlayerID;
+use crate::entity::ThingID;
+use crate::phys;
+use crate::rule;
+use crate::types::Event;
+use crate::types::Flag;
+use crate::types::Flag::*;
+use crate::visual;
+use crate::world::World;
+
+/// The flag that marks things as dirty in this scenario.
+pub fn dirt_flag(world: &World) -> Flag {
+    world.dirt_flag.unwrap_or(Dirty)
+}
+
+/// Is the thing dirty?
+pub fn is_dirty(world: &World, thing: ID) -> bool {
+    world.has_flags(thing) && world.has_flag(thing, dirt_flag(world))
+}
+
+/// Is there water the actor can wash things in, either in its location or in a thing
+/// it can see?
+pub fn has_water(world: &World, actor: ID) -> bool {
+    let here = phys::loc(world, actor);
+
+    (world.has_flags(here) && world.has_flag(here, HasWater))
+        || phys::visible(world, actor)
+            .iter()
+            .any(|id| *id != actor && world.has_flags(*id) && world.has_flag(*id, HasWater))
+}
+
+/// The player washes the thing, if there's water to hand.
+pub fn wash(world: &mut World, pid: PlayerID, thing: ThingID) -> Result<(), String> {
+    let (pid, thing) = (pid.id(), thing.id());
+
+    if !has_water(world, pid) {
+        return Err("There's no water here.".into());
+    }
+
+    let event = Event::Wash(pid, thing);
+
+    if rule::allows(world, &event) {
+        let was_dirty = is_dirty(world, thing);
+        world.unset_flag(thing, dirt_flag(world));
+
+        let text = format!("$You wash {}.", washed_name(world, pid, thing));
+        if was_dirty {
+            visual::act(&format!("{} Much cleaner.", text));
+        } else {
+            visual::act(&text);
+        }
+
+        rule::fire_event(world, &event);
+    }
+
+    Ok(())
+}
+
+/// The actor has picked up the thing; if contamination is enabled and the actor is
+/// dirty, the thing gets dirty too.
+pub fn contaminate(world: &mut World, actor: ID, thing: ID) {
+    if !world.contamination
+        || is_dirty(world, thing)
+        || world.has_flag(thing, NeverDirty)
+    {
+        return;
+    }
+
+    if let Some(source) = dirt_source(world, actor) {
+        world.set_flag(thing, dirt_flag(world));
+        let text = format!(
+            "The dirt from {} gets all over {}.",
+            washed_name(world, actor, source),
+            visual::the_name(world, thing)
+        );
+        visual::act(&text);
+    }
+}
+
+/// Returns the dirty part of the actor, i.e., the actor itself or one of its body
+/// parts, if any.
+fn dirt_source(world: &World, actor: ID) -> Option<ID> {
+    if is_dirty(world, actor) {
+        return Some(actor);
+    }
+
+    phys::scenery(world, actor)
+        .into_iter()
+        .find(|id| is_dirty(world, *id))
+}
+
+/// The name of the thing being washed: "$you" for the player, "$your hands" for one of
+/// the player's body parts, and "the note" otherwise.
+fn washed_name(world: &World, pid: ID, thing: ID) -> String {
+    if thing == pid {
+        "$you".into()
+    } else if phys::loc(world, thing) == pid && world.has_flag(thing, Scenery) {
+        format!("$your {}", world.things[&thing].name)
+    } else {
+        visual::the_name(world, thing)
+    }
+}
@@ -0,0 +1,109 @@
+//! The Water System
+//!
+//! Rooms flagged DeepWater are over the player's head.  A player who can swim, or who is
+//! carrying something flagged Boat, stays afloat there; anyone else can only tread water
+//! for a few turns before drowning.  The countdown starts on the first turn in deep
+//! water, and is forgotten as soon as the player is back on dry land or afloat.  Rules
+//! on the Drown event can rescue the player, or add to the tragedy.
+//!
+//! Swimming is hard work: a player entering deep water without a boat leaves behind
+//! anything flagged Heavy, unless it's Essential.  Things dropped in deep water sink,
+//! unless they're Boats; Essential things turn up somewhere they can be recovered.
+//! Scenarios opt in by flagging rooms and things.
+
+use crate::entity::ID;
+use crate::entity::PlayerID;
+use crate::phys;
+use crate::rule;
+use crate::types::sentence;
+use crate::types::Capability;
+use crate::types::Event;
+use crate::types::Flag::*;
+use crate::visual;
+use crate::world::World;
+
+/// The name of the player variable that counts the turns spent out of their depth.
+pub const TREADING: &str = "treading";
+
+/// The Water system.  Counts down the turns until the player drowns, if they are out
+/// of their depth.
+pub fn system(world: &mut World) {
+    let pid = world.pid;
+    let here = phys::loc(world, pid);
+
+    if !is_deep(world, here) || is_afloat(world, pid) || world.has_flag(pid, Dead) {
+        world.set_var(pid, TREADING, 0);
+        return;
+    }
+
+    let turns = world.var(pid, TREADING) + 1;
+    world.set_var(pid, TREADING, turns);
+
+    if turns == 1 {
+        visual::info("The water is over $your head!");
+    } else if turns as usize <= world.drown_turns {
+        visual::info("$You $are struggling to stay afloat.");
+    } else {
+        world.set_var(pid, TREADING, 0);
+        drown(world, pid);
+    }
+}
+
+/// Is the room deep water?
+pub fn is_deep(world: &World, room: ID) -> bool {
+    world.has_flags(room) && world.has_flag(room, DeepWater)
+}
+
+/// Can the actor stay afloat in deep water, i.e., can it swim or is it carrying a boat?
+pub fn is_afloat(world: &World, actor: ID) -> bool {
+    phys::can(world, actor, Capability::Swim) || has_boat(world, actor)
+}
+
+/// Is the actor carrying a boat?
+fn has_boat(world: &World, actor: ID) -> bool {
+    phys::contents(world, actor)
+        .iter()
+        .any(|id| world.has_flags(*id) && world.has_flag(*id, Boat))
+}
+
+/// The player sets out into deep water, leaving anything heavy behind in their current
+/// location.  With a boat, the player can take everything along.
+pub fn set_out(world: &mut World, pid: PlayerID) {
+    let pid = pid.id();
+
+    if has_boat(world, pid) {
+        return;
+    }
+
+    let here = phys::loc(world, pid);
+
+    for id in phys::droppable(world, pid) {
+        if world.has_flag(id, Heavy) && !world.has_flag(id, Essential) {
+            phys::move_thing(world, id, here);
+            let text = format!("{} is too heavy to swim with; $you $leave it behind", visual::the_name(world, id));
+            visual::act(&sentence(&text));
+        }
+    }
+}
+
+/// The thing has been dropped; if it's in deep water, it sinks out of sight, unless
+/// it floats.
+pub fn sink(world: &mut World, thing: ID) {
+    let here = phys::loc(world, thing);
+
+    if !is_deep(world, here) || world.has_flag(thing, Boat) {
+        return;
+    }
+
+    let text = format!("{} sinks out of sight", visual::the_name(world, thing));
+    visual::act(&sentence(&text));
+    phys::destroy(world, thing);
+}
+
+/// The player drowns, unless a rule intervenes.
+fn drown(world: &mut World, pid: ID) {
+    if rule::allows(world, &Event::Drown(pid)) {
+        phys::kill(world, pid, "$You $have drowned.", Some("drown"));
+        rule::fire_event(world, &Event::Drown(pid));
+    }
+}
@@ -1,19 +1,59 @@
 //! The game world
+use crate::command::ParserExtension;
 use crate::player_control::CommandHandler;
+use crate::entity::button_component::*;
+use crate::entity::chapter_component::*;
+use crate::entity::climb_component::*;
+use crate::entity::consumable_component::*;
+#[cfg(feature = "deadlines")]
+use crate::entity::deadline_component::*;
+#[cfg(feature = "effects")]
+use crate::entity::effect_component::*;
 use crate::entity::flag_set_component::*;
 use crate::entity::inventory_component::*;
 use crate::entity::location_component::*;
+use crate::entity::lock_component::*;
+#[cfg(feature = "machines")]
+use crate::entity::machine_component::*;
+use crate::entity::platform_component::*;
+use crate::entity::plate_component::*;
+use crate::entity::portal_component::*;
 use crate::entity::player_component::*;
 use crate::entity::prose_component::*;
+use crate::entity::random_table_component::*;
 use crate::entity::room_component::*;
 use crate::entity::rule_component::*;
 use crate::entity::tag_component::*;
 use crate::entity::thing_component::*;
+use crate::entity::topic_component::*;
+use crate::entity::var_component::*;
+use crate::entity::verb_component::*;
+use crate::conmark::Narration;
 use crate::entity::ID;
+use crate::entity::PlayerID;
+use crate::entity::RoomID;
+use crate::entity::RuleID;
+use crate::entity::ThingID;
+use crate::autosave;
+use crate::dark;
+#[cfg(feature = "deadlines")]
+use crate::deadline;
+#[cfg(feature = "effects")]
+use crate::effect;
+use crate::help;
+use crate::help::HelpPage;
+use crate::phys;
+use crate::phys::NounIndex;
+use crate::phys::ScopeCache;
+use crate::rng::Rng;
 use crate::types::*;
+use crate::visual;
+use std::cell::RefCell;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 
 pub const LIMBO: ID = 0;
 
@@ -38,12 +78,144 @@ pub struct World {
     // The game clock
     pub clock: Time,
 
+    // The random number generator
+    pub rng: Rng,
+
+    // The player's score
+    pub score: i32,
+
+    // Does this scenario keep score?
+    pub scored: bool,
+
+    // Should the status line be displayed before each prompt?
+    pub status_line: bool,
+
+    // The current chapter of the story, if any.
+    pub chapter: Option<ID>,
+
+    // The entity the player is currently talking to, if any.
+    pub interlocutor: Option<ID>,
+
+    // The narration setting: person and tense
+    pub narration: Narration,
+
+    // The hook applied to each block of prose before it is displayed, if any.
+    pub output_hook: Option<OutputHook>,
+
+    // The scenario's hook for placing essential things that would be lost, if any.
+    pub recovery_hook: Option<RecoveryHook>,
+
+    // The initial locations of the essential things, where they turn up by default.
+    pub origins: HashMap<ID, ID>,
+
+    // Debugging: if true, the player sees script output from everywhere.
+    pub omniscient: bool,
+
+    // The condition under which the player has won the game, if any.
+    pub win_condition: Option<RulePredicate>,
+
+    // Has the player won the game?
+    pub won: bool,
+
+    // The session metrics.
+    pub metrics: Metrics,
+
+    // The log of recent commands, events, and rule firings.
+    pub log: EventLog,
+
+    // The number of turns a script has elapsed this turn, to be fast-forwarded.
+    pub elapsing: usize,
+
+    // If true, the rules and turn systems run for elapsed turns.
+    pub fast_forward: bool,
+
+    // If true, "go to <room>" goes all the way, rather than a step at a time.
+    pub go_to_all_the_way: bool,
+
+    // For each entity's prose variants, by prose type: the number of views so far,
+    // and the index of the text shown last.
+    pub prose_views: HashMap<(ID, ProseType), (usize, usize)>,
+
+    // The rest of the route being walked by "go to <room>", a step per turn.
+    pub walking: VecDeque<(Dir, ID)>,
+
+    // The players whose inventories have changed since their InventoryChanged events
+    // were last fired.
+    pub inventory_changes: BTreeSet<ID>,
+
+    // Has the player confirmed the command being executed?
+    pub confirmed: bool,
+
+    // The question awaiting the player's answer, if any.
+    pub prompt: Option<Prompt>,
+
+    // The player's answer to the most recent question, for "{answer}" in prose.
+    pub answer: Option<String>,
+
+    // The optional systems that run each turn.
+    pub systems: BTreeSet<TurnSystem>,
+
+    // Cached scope queries; see phys::ScopeCache.
+    pub scope_cache: RefCell<ScopeCache>,
+
+    // The things in each container by noun; see phys::named().
+    pub noun_index: NounIndex,
+
+    // The number of turns between autosaves, or 0 for none.
+    pub autosave_turns: usize,
+
+    // The scenario's title, which identifies it in the records file.
+    pub title: String,
+
+    // How winning runs are ranked in the records file.
+    pub ranking: Ranking,
+
+    // The flag that marks things as dirty, if not the standard Dirty flag.
+    pub dirt_flag: Option<Flag>,
+
+    // Do dirty actors dirty the things they pick up?
+    pub contamination: bool,
+
+    // Must the player be holding a thing to read it, or do they pick it up first?
+    pub read_policy: HoldPolicy,
+
+    // Are implicit actions, e.g., taking a thing before putting it somewhere, enabled?
+    pub implicit_actions: bool,
+
+    // The number of turns the player can tread water in deep water before drowning.
+    #[cfg(feature = "water")]
+    pub drown_turns: Time,
+
     //--------------------------------------------------------------------------------------------
     // Entity Components
     /// Tag Components: Identifiers for the entities.  This is a BTreeMap so that we can
     /// easily reference entities in order of creation.
     pub tags: BTreeMap<ID, TagComponent>,
 
+    /// Button Components: Things that move platforms.
+    pub buttons: HashMap<ID, ButtonComponent>,
+
+    /// Chapter Components: The chapters of the story.  We use BTreeMap so that
+    /// chapters are entered in order of definition.
+    pub chapters: BTreeMap<ID, ChapterComponent>,
+
+    /// Climb Components: Things the player can climb.
+    pub climbables: HashMap<ID, ClimbComponent>,
+
+    /// Consumable Components: Things with a limited number of charges.
+    pub consumables: HashMap<ID, ConsumableComponent>,
+
+    /// Effect Components: The status effects the player can be under.  We use BTreeMap
+    /// so that effects are listed and ticked in order of definition.
+    #[cfg(feature = "effects")]
+    pub effects: BTreeMap<ID, EffectComponent>,
+
+    /// Deadline Components: The turn limits and timed threats the player can be under.
+    /// We use BTreeMap so that deadlines are listed and counted down in order of
+    /// definition.
+    #[cfg(feature = "deadlines")]
+    pub deadlines: BTreeMap<ID, DeadlineComponent>,
+
     /// FlagSets, used for storing arbitrary data about the entity.  Flags include "engine"
     /// flags and custom flags defined by the scenario.
     pub flag_sets: HashMap<ID, FlagSetComponent>,
@@ -55,6 +227,25 @@ pub struct World {
     /// Location Components: Where entities are located.
     pub locations: HashMap<ID, LocationComponent>,
 
+    /// Lock Components: The keys that fit lockable things.
+    pub locks: HashMap<ID, LockComponent>,
+
+    /// Machine Components: Things that can be switched on and off.  We use BTreeMap so
+    /// that machines run in order of definition.
+    #[cfg(feature = "machines")]
+    pub machines: BTreeMap<ID, MachineComponent>,
+
+    /// Platform Components: Rooms that move between stops.  We use BTreeMap so that
+    /// platforms are handled in order of definition.
+    pub platforms: BTreeMap<ID, PlatformComponent>,
+
+    /// Plate Components: Rooms and containers that are pressed by the weight on them.
+    /// We use BTreeMap so that plates are checked in order of definition.
+    pub plates: BTreeMap<ID, PlateComponent>,
+
+    /// Portal Components: Things the player can enter to go somewhere else.
+    pub portals: HashMap<ID, PortalComponent>,
+
     /// Prose Components: contains all the different kinds of prose an entity can have.
     pub proses: HashMap<ID, ProseComponent>,
 
@@ -67,6 +258,19 @@ pub struct World {
     /// Thing Components: Information about things that the player can interact with.
     pub things: HashMap<ID, ThingComponent>,
 
+    /// Random Table Components: Tables of random events.  We use BTreeMap to ensure that
+    /// tables are rolled in order of definition.
+    pub random_tables: BTreeMap<ID, RandomTableComponent>,
+
+    /// Topic Components: Topics the player can ask entities about.
+    pub topics: HashMap<ID, TopicComponent>,
+
+    /// Var Components: Named integer variables attached to entities.
+    pub vars: HashMap<ID, VarComponent>,
+
+    /// Verb Components: Verb handlers attached to particular things.
+    pub verb_handlers: HashMap<ID, VerbComponent>,
+
     /// Rule Components: Rules that can fire.  We use BTreeMap to ensure that rules fire
     /// in order of definition.
     pub rules: BTreeMap<ID, RuleComponent>,
@@ -76,11 +280,56 @@ pub struct World {
 
     pub command_handlers: Vec<CommandHandler>,
 
+    // The scenario's parser extensions, in the order they are applied
+    pub parser_extensions: Vec<&'static dyn ParserExtension>,
+
     // The valid verbs
     pub verbs: HashSet<String>,
 
+    // The names of the scenario's custom directions; see Dir::Custom.
+    pub directions: BTreeSet<&'static str>,
+
+    // The verbs the player can use in the dark; see dark::permits().
+    pub dark_verbs: HashSet<String>,
+
+    // The responses to other verbs in the dark, by verb.
+    pub dark_responses: HashMap<String, String>,
+
+    // The percentage chance that the player stumbles when moving in the dark.
+    pub dark_risk: usize,
+
     // Mapping from verb synonyms to verbs
     pub synonyms: HashMap<String, String>,
+
+    // Capabilities required to use particular verbs
+    pub verb_requirements: HashMap<String, Requirement>,
+
+    // Help pages, by command or topic
+    pub help: BTreeMap<String, HelpPage>,
+
+    // Default responses for verbs applied to things that don't handle them
+    pub default_responses: HashMap<String, String>,
+
+    // The escalating prose for errors the player gets repeatedly, by error message.
+    pub repeat_errors: HashMap<String, Vec<String>>,
+
+    // The epitaphs shown when the player dies, by cause of death.
+    pub epitaphs: HashMap<String, String>,
+
+    // Where the player last died, if the player has died.
+    pub death_room: Option<ID>,
+
+    // What the player forfeits on being revived.
+    pub revive_forfeit: Forfeit,
+
+    // The points the player loses on being revived.
+    pub revive_penalty: i32,
+
+    // Verbs that don't prefer carried things when resolving nouns
+    pub verb_preferences: HashMap<String, Preference>,
+
+    // The recipes for combining things
+    pub recipes: Vec<Recipe>,
 }
 
 impl World {
@@ -94,18 +343,87 @@ impl World {
             tag_map: HashMap::new(),
             pid: 0,
             clock: 0,
+            rng: Rng::default(),
+            score: 0,
+            scored: false,
+            status_line: false,
+            chapter: None,
+            interlocutor: None,
+            narration: Narration::default(),
+            output_hook: None,
+            recovery_hook: None,
+            origins: HashMap::new(),
+            omniscient: false,
+            win_condition: None,
+            won: false,
+            metrics: Metrics::default(),
+            log: EventLog::default(),
+            elapsing: 0,
+            fast_forward: true,
+            go_to_all_the_way: false,
+            prose_views: HashMap::new(),
+            walking: VecDeque::new(),
+            inventory_changes: BTreeSet::new(),
+            confirmed: false,
+            prompt: None,
+            answer: None,
+            systems: BTreeSet::new(),
+            scope_cache: RefCell::new(ScopeCache::default()),
+            noun_index: NounIndex::new(),
+            autosave_turns: autosave::AUTOSAVE_TURNS,
+            title: "Untitled".into(),
+            ranking: Ranking::FewestTurns,
+            dirt_flag: None,
+            contamination: false,
+            read_policy: HoldPolicy::Strict,
+            implicit_actions: false,
+            #[cfg(feature = "water")]
+            drown_turns: 3,
             tags: BTreeMap::new(),
+            buttons: HashMap::new(),
+            chapters: BTreeMap::new(),
+            climbables: HashMap::new(),
+            consumables: HashMap::new(),
+            #[cfg(feature = "effects")]
+            effects: BTreeMap::new(),
+            #[cfg(feature = "deadlines")]
+            deadlines: BTreeMap::new(),
             flag_sets: HashMap::new(),
             inventories: HashMap::new(),
             locations: HashMap::new(),
+            locks: HashMap::new(),
+            #[cfg(feature = "machines")]
+            machines: BTreeMap::new(),
+            platforms: BTreeMap::new(),
+            plates: BTreeMap::new(),
+            portals: HashMap::new(),
             proses: HashMap::new(),
             players: HashMap::new(),
             rooms: HashMap::new(),
             things: HashMap::new(),
+            random_tables: BTreeMap::new(),
+            topics: HashMap::new(),
+            vars: HashMap::new(),
+            verb_handlers: HashMap::new(),
             rules: BTreeMap::new(),
             command_handlers: Vec::new(),
+            parser_extensions: Vec::new(),
             verbs: HashSet::new(),
+            directions: BTreeSet::new(),
+            dark_verbs: dark::DARK_VERBS.iter().map(|verb| verb.to_string()).collect(),
+            dark_responses: HashMap::new(),
+            dark_risk: 0,
             synonyms: HashMap::new(),
+            verb_requirements: HashMap::new(),
+            help: BTreeMap::new(),
+            default_responses: HashMap::new(),
+            repeat_errors: HashMap::new(),
+            epitaphs: HashMap::new(),
+            death_room: None,
+            revive_forfeit: Forfeit::Nothing,
+            revive_penalty: 0,
+            verb_preferences: HashMap::new(),
+            recipes: Vec::new(),
         };
 
         // NEXT, add the standard verbs and synonyms
@@ -124,6 +442,15 @@ impl World {
         world.add_verb("west");
         world.add_syn("west", "w");
 
+        world.add_verb("up");
+        world.add_syn("up", "u");
+
+        world.add_verb("down");
+        world.add_syn("down", "d");
+
+        world.add_verb("in");
+        world.add_verb("out");
+
         world.add_verb("help");
         world.add_verb("look");
 
@@ -133,16 +460,56 @@ impl World {
 
         world.add_verb("examine");
         world.add_syn("examine", "x");
+        world.add_verb("search");
 
         world.add_verb("get");
         world.add_syn("get", "take");
+        world.verb_preferences.insert("get".into(), Preference::Here);
 
         world.add_verb("drop");
 
         world.add_verb("read");
-
+        world.add_verb("disarm");
+        world.add_verb("lock");
+        world.add_verb("unlock");
+        world.add_verb("dial");
+        world.add_verb("set");
+        world.add_verb("enter");
+        #[cfg(feature = "machines")]
+        world.add_verb("switch");
+        world.add_verb("put");
+        world.add_verb("combine");
+        world.add_verb("attach");
+        world.add_verb("mix");
+        world.add_verb("push");
+        world.add_verb("pull");
+        world.add_verb("turn");
+        world.add_verb("touch");
+        world.add_verb("shake");
+        world.add_verb("climb");
+        world.add_verb("feel");
+        #[cfg(feature = "water")]
+        world.add_verb("swim");
+        world.add_verb("wash");
+        world.add_syn("wash", "clean");
+
+        world.add_verb("talk");
+        world.add_verb("ask");
+        world.add_verb("topics");
+        world.add_verb("use");
+
+        world.add_verb("chapter");
+        world.add_verb("status");
+        #[cfg(feature = "deadlines")]
+        world.add_verb("time");
+        world.add_verb("records");
+        world.add_verb("note");
+        world.add_verb("journal");
+        world.add_verb("erase");
+        world.add_verb("accessible");
         world.add_verb("restart");
         world.add_verb("undo");
+        world.add_verb("restore");
         world.add_verb("quit");
         world.add_syn("quit", "exit");
         world.add_syn("quit", "bye");
@@ -150,11 +517,26 @@ impl World {
         // NEXT, add debugging-only verbs
         world.add_verb("list");
         world.add_verb("dump");
-
-        // NEXT, add custom verbs
-        // TODO: Should be part of scenario, once the scenario can define
-        // custom command handlers.
-        world.add_verb("wash");
+        world.add_verb("omniscient");
+        world.add_verb("log");
+
+        // NEXT, add the help pages for the built-in commands.
+        help::add_builtin_pages(&mut world);
+
+        // NEXT, add the standard default responses, so that common verbs always
+        // produce something sensible.
+        world.set_default_response("push", "It doesn't budge.");
+        world.set_default_response("pull", "Nothing happens.");
+        world.set_default_response("eat", "That's plainly inedible.");
+        world.set_default_response("drink", "$You $can't drink that.");
+        world.set_default_response("open", "It doesn't open.");
+        world.set_default_response("close", "It doesn't close.");
+        world.set_default_response("turn", "It doesn't turn.");
+        world.set_default_response("touch", "$You $feel nothing unexpected.");
+        world.set_default_response("shake", "Nothing rattles.");
+        world.set_default_response("smell", "Nothing out of the ordinary.");
+        world.set_default_response("use", "$You $can't see how to use that.");
+        world.add_syn("push", "press");
 
         world
     }
@@ -223,6 +605,26 @@ impl World {
             && self.things.get(&id).is_some()
     }
 
+    /// Returns the entity's typed ID, if it's a player.
+    pub fn player_id(&self, id: ID) -> Option<PlayerID> {
+        if self.is_player(id) { Some(PlayerID::new(id)) } else { None }
+    }
+
+    /// Returns the entity's typed ID, if it's a room.
+    pub fn room_id(&self, id: ID) -> Option<RoomID> {
+        if self.is_room(id) { Some(RoomID::new(id)) } else { None }
+    }
+
+    /// Returns the entity's typed ID, if it's a thing.
+    pub fn thing_id(&self, id: ID) -> Option<ThingID> {
+        if self.is_thing(id) { Some(ThingID::new(id)) } else { None }
+    }
+
+    /// Returns the entity's typed ID, if it's a rule or named script.
+    pub fn rule_id(&self, id: ID) -> Option<RuleID> {
+        if self.rules.contains_key(&id) { Some(RuleID::new(id)) } else { None }
+    }
+
     /// Is this entity a room where the player can go?
     pub fn is_room(&self, id: ID) -> bool {
         self.rooms.get(&id).is_some() && self.has_inventory(id) && self.has_flags(id)
@@ -233,6 +635,11 @@ impl World {
         self.things.get(&id).is_some() && self.has_location(id) && self.has_flags(id)
     }
 
+    /// Can the player talk to this entity?
+    pub fn is_talker(&self, id: ID) -> bool {
+        self.topics.get(&id).is_some() && self.is_thing(id)
+    }
+
     /// Is this entity a rule?
     pub fn is_rule(&self, id: ID) -> bool {
         self.rules.get(&id).is_some() && self.has_flags(id)
@@ -255,6 +662,12 @@ impl World {
         }
     }
 
+    /// Adds an entry to the event log, stamped with the current time.
+    pub fn log(&mut self, text: &str) {
+        let clock = self.clock;
+        self.log.add(clock, text);
+    }
+
     /// Looks up an entity's ID in the tag map.  Panics if there is none.
     pub fn lookup(&self, tag: &str) -> ID {
         *self.tag_map.get(tag)
@@ -278,6 +691,16 @@ impl World {
         self.synonyms.insert(verb.to_string(), canon.to_string());
     }
 
+    /// Sets the default response for a verb applied to a thing that doesn't handle it,
+    /// adding the verb if need be.
+    pub fn set_default_response(&mut self, verb: &str, text: &str) {
+        if !self.verbs.contains(verb) {
+            self.add_verb(verb);
+        }
+
+        self.default_responses.insert(verb.into(), text.trim().into());
+    }
+
     //--------------------------------------------------------------------------------------------
     // Flags
 
@@ -297,6 +720,7 @@ impl World {
 
         // Consider adding as_flags() to Entity
         fc.set(flag);
+        self.scope_cache.get_mut().clear();
     }
 
     /// Clear the flag from the entity
@@ -307,6 +731,88 @@ impl World {
 
         // Consider adding as_flags() to Entity
         fc.unset(flag);
+        self.scope_cache.get_mut().clear();
+    }
+
+    //--------------------------------------------------------------------------------------------
+    // Variables
+
+    /// Gets the value of the entity's variable, or 0 if it has never been set.
+    pub fn var(&self, id: ID, name: &str) -> i64 {
+        self.vars.get(&id).map(|varc| varc.get(name)).unwrap_or(0)
+    }
+
+    /// Sets the value of the entity's variable.
+    pub fn set_var(&mut self, id: ID, name: &str, value: i64) {
+        self.vars.entry(id).or_insert_with(VarComponent::new).set(name, value);
+    }
+
+    //--------------------------------------------------------------------------------------------
+    // Validation
+
+    /// Checks the world's structural invariants, returning a description of the first
+    /// violation found.  The game logic should never violate them.
+    pub fn validate(&self) -> Result<(), String> {
+        // FIRST, LIMBO must exist.
+        if !self.has_inventory(LIMBO) {
+            return Err("LIMBO has no inventory".into());
+        }
+
+        // NEXT, every located entity must be in its location's inventory, and
+        // nothing can contain itself.
+        for (id, locc) in &self.locations {
+            if locc.id() == *id {
+                return Err(format!("[{}] {} is located in itself", id, self.tag(*id)));
+            }
+
+            match self.inventories.get(&locc.id()) {
+                Some(inv) if inv.has(*id) => {}
+                _ => {
+                    return Err(format!("[{}] {} is not in the inventory of its location [{}]",
+                        id, self.tag(*id), locc.id()));
+                }
+            }
+        }
+
+        // NEXT, everything in an inventory must be located there, and so can be in
+        // only one inventory.
+        for (owner, inv) in &self.inventories {
+            for id in inv.iter() {
+                match self.locations.get(id) {
+                    Some(locc) if locc.id() == *owner => {}
+                    _ => {
+                        return Err(format!("[{}] {} is in the inventory of [{}] {}, but isn't located there",
+                            id, self.tag(*id), owner, self.tag(*owner)));
+                    }
+                }
+            }
+        }
+
+        // NEXT, cached scope queries and the noun index must be up to date.
+        phys::check_scope_cache(self)?;
+        phys::check_noun_index(self)?;
+
+        // NEXT, the player must be in a room.
+        let here = self.locations[&self.pid].id();
+        if !self.is_room(here) {
+            return Err(format!("The player is in [{}] {}, which isn't a room", here, self.tag(here)));
+        }
+
+        // NEXT, rules can only refer to existing entities.
+        for (id, rulec) in &self.rules {
+            let mut ids = rulec.event.entities();
+            ids.extend(rulec.chapter);
+
+            if let Some(bad) = ids.iter().find(|id| !self.tags.contains_key(id)) {
+                return Err(format!("Rule [{}] {} refers to unknown entity [{}]", id, self.tag(*id), bad));
+            }
+
+            if let Some(bad) = rulec.script.tags().iter().find(|tag| !self.tag_map.contains_key(**tag)) {
+                return Err(format!("Rule [{}] {} refers to unknown entity {}", id, self.tag(*id), bad));
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -315,6 +821,18 @@ pub trait WorldQuery {
     // Gets the value of the turn clock
     fn clock(&self) -> usize;
 
+    // Gets the player's score
+    fn score(&self) -> i32;
+
+    // Returns the tag of the current chapter, if any.
+    fn chapter(&self) -> Option<String>;
+
+    // Returns the tagged thing's name with an indefinite article, e.g., "a note".
+    fn a_name(&self, tag: &str) -> String;
+
+    // Returns the tagged thing's name with the definite article, e.g., "the note".
+    fn the_name(&self, tag: &str) -> String;
+
     // Returns true if the given flag is set on the tagged entity, and false
     // otherwise.
     fn has(&self, tag: &str, flag: Flag) -> bool;
@@ -322,9 +840,49 @@ pub trait WorldQuery {
     // Returns the tag of the tagged entity's location.
     fn loc(&self, tag: &str) -> String;
 
+    // Returns the number of times the player has entered the tagged room, counting the
+    // start of the game for the starting room; e.g., a room's prose hook can tell
+    // the player they've passed this way before.
+    fn visits(&self, room: &str) -> usize;
+
     // Returns true if the tagged owner owns the tagged thing, and
     // false otherwise
     fn owns(&self, owner: &str, thing: &str) -> bool;
+
+    // Returns true if the tagged actor has the capability, directly or
+    // by virtue of something it's carrying.
+    fn can(&self, actor: &str, capability: Capability) -> bool;
+
+    // Returns the value of the tagged entity's variable, or 0 if it has never
+    // been set.
+    fn var(&self, tag: &str, name: &str) -> i64;
+
+    // Returns the session metrics, e.g., the number of turns taken.
+    fn metrics(&self) -> &Metrics;
+
+    // Returns true if the player is carrying the tagged thing, directly or in a
+    // container, and false otherwise.
+    fn carrying(&self, thing: &str) -> bool;
+
+    // Returns true if the player is carrying anything with the given flag, directly or
+    // in a container, and false otherwise.
+    fn carrying_any_with(&self, flag: Flag) -> bool;
+
+    // Returns true if the tagged pressure plate is pressed, and false otherwise.
+    fn pressed(&self, plate: &str) -> bool;
+
+    // Returns true if the player is under the tagged status effect, and false otherwise.
+    #[cfg(feature = "effects")]
+    fn has_effect(&self, effect: &str) -> bool;
+
+    // Returns the number of turns left before the tagged deadline runs out, or None if
+    // it isn't running.
+    #[cfg(feature = "deadlines")]
+    fn turns_left(&self, deadline: &str) -> Option<Time>;
+
+    // Returns true if the player has confirmed the command being executed; see
+    // Handled::Confirm.
+    fn confirmed(&self) -> bool;
 }
 
 impl WorldQuery for World {
@@ -333,6 +891,26 @@ impl WorldQuery for World {
         self.clock
     }
 
+    // Gets the player's score
+    fn score(&self) -> i32 {
+        self.score
+    }
+
+    // Returns the tag of the current chapter, if any.
+    fn chapter(&self) -> Option<String> {
+        self.chapter.map(|id| self.tag(id))
+    }
+
+    // Returns the tagged thing's name with an indefinite article, e.g., "a note".
+    fn a_name(&self, tag: &str) -> String {
+        visual::a_name(self, self.lookup(tag))
+    }
+
+    // Returns the tagged thing's name with the definite article, e.g., "the note".
+    fn the_name(&self, tag: &str) -> String {
+        visual::the_name(self, self.lookup(tag))
+    }
+
     /// Is the flag set on the entity?
     fn has(&self, tag: &str, flag: Flag) -> bool {
         let id = self.lookup(tag);
@@ -345,7 +923,15 @@ impl WorldQuery for World {
     // Returns the tag of the tagged entity's location.
     fn loc(&self, tag: &str) -> String {
         let id = self.lookup(tag);
-        self.tag(self.locations[&id].id)
+        self.tag(self.locations[&id].id())
+    }
+
+    // Returns the number of times the player has entered the tagged room, counting the
+    // start of the game for the starting room.
+    fn visits(&self, room: &str) -> usize {
+        let id = self.lookup(room);
+        assert!(self.is_room(id), "Not a room: {}", room);
+        self.rooms[&id].visits
     }
 
     // Returns true if the tagged owner owns the tagged thing, and
@@ -360,4 +946,62 @@ impl WorldQuery for World {
         }
     }
 
+    // Returns true if the tagged actor has the capability, directly or
+    // by virtue of something it's carrying.
+    fn can(&self, actor: &str, capability: Capability) -> bool {
+        phys::can(self, self.lookup(actor), capability)
+    }
+
+    // Returns the value of the tagged entity's variable, or 0 if it has never
+    // been set.
+    fn var(&self, tag: &str, name: &str) -> i64 {
+        World::var(self, self.lookup(tag), name)
+    }
+
+    // Returns the session metrics, e.g., the number of turns taken.
+    fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    // Returns true if the player is carrying the tagged thing, directly or in a
+    // container, and false otherwise.
+    fn carrying(&self, thing: &str) -> bool {
+        let id = self.lookup(thing);
+        id != self.pid && phys::encloses(self, self.pid, id)
+    }
+
+    // Returns true if the player is carrying anything with the given flag, directly or
+    // in a container, and false otherwise.
+    fn carrying_any_with(&self, flag: Flag) -> bool {
+        self.things.keys().any(|id| {
+            *id != self.pid
+                && self.has_flags(*id)
+                && self.has_flag(*id, flag)
+                && phys::encloses(self, self.pid, *id)
+        })
+    }
+
+    // Returns true if the tagged pressure plate is pressed, and false otherwise.
+    fn pressed(&self, plate: &str) -> bool {
+        self.plates.get(&self.lookup(plate)).map_or(false, |platec| platec.pressed)
+    }
+
+    // Returns true if the player is under the tagged status effect, and false otherwise.
+    #[cfg(feature = "effects")]
+    fn has_effect(&self, effect: &str) -> bool {
+        effect::is_active(self, self.pid, self.lookup(effect))
+    }
+
+    // Returns the number of turns left before the tagged deadline runs out, or None if
+    // it isn't running.
+    #[cfg(feature = "deadlines")]
+    fn turns_left(&self, deadline: &str) -> Option<Time> {
+        deadline::turns_left(self, self.lookup(deadline))
+    }
+
+    // Returns true if the player has confirmed the command being executed; see
+    // Handled::Confirm.
+    fn confirmed(&self) -> bool {
+        self.confirmed
+    }
 }
@@ -4,18 +4,48 @@
 //! various aids.
 
 use std::collections::HashSet;
+use std::fmt;
+use std::rc::Rc;
+use crate::conmark::*;
 use crate::entity::ID;
+use crate::entity::PlayerID;
+use crate::entity::RoomID;
+use crate::entity::RuleID;
+use crate::entity::ThingID;
+use crate::consumable;
+use crate::entity::button_component::*;
+use crate::entity::chapter_component::*;
+use crate::entity::climb_component::*;
+use crate::entity::consumable_component::*;
+#[cfg(feature = "deadlines")]
+use crate::entity::deadline_component::*;
+#[cfg(feature = "effects")]
+use crate::entity::effect_component::*;
 use crate::entity::flag_set_component::*;
 use crate::entity::inventory_component::*;
-use crate::entity::location_component::*;
+use crate::entity::lock_component::*;
+#[cfg(feature = "machines")]
+use crate::entity::machine_component::*;
 use crate::entity::player_component::*;
 use crate::entity::prose_component::*;
+use crate::entity::random_table_component::*;
+use crate::entity::platform_component::*;
+use crate::entity::plate_component::*;
+use crate::entity::portal_component::*;
 use crate::entity::room_component::*;
 use crate::entity::rule_component::*;
 use crate::entity::thing_component::*;
+use crate::entity::topic_component::*;
+use crate::entity::verb_component::*;
+use crate::help::HelpPage;
 use crate::phys;
+use crate::platform;
+use crate::rng::Rng;
+use crate::script;
+use crate::command::ParserExtension;
 use crate::player_control::CommandHandler;
 use crate::types::*;
+use crate::world;
 use crate::world::World;
 
 //-----------------------------------------------------------------------------------------------
@@ -27,16 +57,135 @@ pub const LIMBO: &str = "LIMBO";
 /// The tag of the PLAYER entity.
 pub const PLAYER: &str = "PLAYER";
 
+/// The player's default weight, for pressure plates; most things weigh 1.
+pub const PLAYER_WEIGHT: u32 = 10;
+
 /// Events for which rules can be written.
 pub enum WBEvent<'a> {
-    /// The player gets (or tries to get) the tagged entity
+    /// The player gets (or tries to get) the tagged entity, into the player's hands or
+    /// anything else
     GetThing(&'a str),
 
+    /// GetThingInto(thing, destination): The player gets (or tries to get) the tagged
+    /// entity into the tagged destination: the player, for the player's hands, or a
+    /// container, e.g., "put sword in sack" with the sword lying on the ground
+    GetThingInto(&'a str, &'a str),
+
+    /// PutThing(thing, container): The player puts (or tries to put) the tagged entity
+    /// into the tagged container
+    PutThing(&'a str, &'a str),
+
+    /// The player gets (or tries to get) any thing with the given flag, e.g., any
+    /// cursed thing
+    GetAnyWith(Flag),
+
     /// The player reads (or tries to read) the tagged entity
     ReadThing(&'a str),
 
     /// The player enters (or tries to enter) the tagged entity
     EnterRoom(&'a str),
+
+    /// The player detects (or is about to detect) the tagged trap
+    DetectTrap(&'a str),
+
+    /// The player disarms (or tries to disarm) the tagged trap
+    DisarmTrap(&'a str),
+
+    /// The player springs (or is about to spring) the tagged trap
+    SpringTrap(&'a str),
+
+    /// The story enters the tagged chapter
+    StartChapter(&'a str),
+
+    /// The player unlocks (or tries to unlock) the tagged thing
+    Unlock(&'a str),
+
+    /// The player locks (or tries to lock) the tagged thing
+    Lock(&'a str),
+
+    /// The player enters (or is about to enter) the tagged thing's combination
+    CorrectCode(&'a str),
+
+    /// The player enters the wrong combination on the tagged thing
+    WrongCode(&'a str),
+
+    /// The player switches on (or tries to switch on) the tagged machine
+    #[cfg(feature = "machines")]
+    SwitchOn(&'a str),
+
+    /// The player switches off (or tries to switch off) the tagged machine
+    #[cfg(feature = "machines")]
+    SwitchOff(&'a str),
+
+    /// The tagged machine is running; fired each turn
+    #[cfg(feature = "machines")]
+    Running(&'a str),
+
+    /// The player manipulates (or tries to manipulate) the tagged thing, e.g., by
+    /// pushing it.
+    Manipulate(&'a str, Manipulation),
+
+    /// The tagged consumable has used up its last charge
+    Exhausted(&'a str),
+
+    /// The player combines (or tries to combine) two things to make the tagged thing
+    Combine(&'a str),
+
+    /// The player sends (or tries to send) the tagged platform to the tagged stop
+    PlatformMove(&'a str, &'a str),
+
+    /// The player climbs (or tries to climb) the tagged thing, which leads nowhere
+    Climb(&'a str),
+
+    /// The player drowns (or is about to drown) in deep water
+    #[cfg(feature = "water")]
+    Drown,
+
+    /// The player washes (or tries to wash) the tagged thing
+    Wash(&'a str),
+
+    /// Something is added to or removed from what the player is carrying
+    InventoryChanged,
+
+    /// The player takes (or tries to take) any of the tagged owner's things in the
+    /// owner's presence
+    Steal(&'a str),
+
+    /// The load on the tagged pressure plate rises to its threshold
+    PlatePressed(&'a str),
+
+    /// The load on the tagged pressure plate falls below its threshold
+    PlateReleased(&'a str),
+
+    /// The player enters (or tries to enter) the tagged portal
+    EnterPortal(&'a str),
+
+    /// The player stumbles while trying to move about the tagged room in the dark
+    Stumble(&'a str),
+
+    /// The tagged deadline runs out
+    #[cfg(feature = "deadlines")]
+    TimeUp(&'a str),
+
+    /// The player searches (or tries to search) the tagged room
+    Search(&'a str),
+
+    /// The player is revived after a death, by any means
+    Revive,
+}
+
+/// A problem found when world-building is complete, e.g., a rule for a thing that was
+/// never defined; see WorldBuilder::try_world().
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildError {
+    /// The description of the problem, e.g., "Expected thing: [12] sword"
+    pub message: String,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
 }
 
 /// Expectations, to be checked when world-building is complete.
@@ -45,12 +194,54 @@ enum Is {
     /// The entity has readable prose
     Book(ID),
 
+    /// The entity is a chapter.
+    Chapter(ID),
+
+    /// The entity is a consumable thing.
+    Consumable(ID),
+
+    /// The entity is a deadline.
+    #[cfg(feature = "deadlines")]
+    Deadline(ID),
+
+    /// The entity is a status effect.
+    #[cfg(feature = "effects")]
+    Effect(ID),
+
+    /// The entity is a lockable thing.
+    Lockable(ID),
+
+    /// The entity is a machine.
+    #[cfg(feature = "machines")]
+    Machine(ID),
+
+    /// The entity has a name, i.e., it is a room or a thing.
+    Named(ID),
+
+    /// The entity is a platform with at least one stop.
+    Platform(ID),
+
+    /// The entity is a pressure plate.
+    Plate(ID),
+
+    /// The entity is a portal.
+    Portal(ID),
+
     /// The entity is the player
     Player(ID),
 
+    /// The entity is a random event table.
+    RandomTable(ID),
+
+    /// The entity is a rule or named script.
+    Rule(ID),
+
     /// The entity is a room.
     Room(ID),
 
+    /// Stop(platform, room): The room is one of the platform's stops.
+    Stop(ID, ID),
+
     /// The entity is a thing.
     Thing(ID),
 }
@@ -77,6 +268,9 @@ enum Is {
 pub struct WorldBuilder {
     world: World,
     expectations: HashSet<Is>,
+
+    // The distinct prose strings, so that entities with the same prose share it.
+    prose_table: HashSet<Rc<str>>,
 }
 
 impl WorldBuilder {
@@ -89,6 +283,7 @@ impl WorldBuilder {
         let mut this = Self {
             world: World::new(),
             expectations: HashSet::new(),
+            prose_table: HashSet::new(),
         };
 
         // NEXT, create LIMBO, the container for things which aren't anywhere else.
@@ -102,7 +297,10 @@ impl WorldBuilder {
         this.world.pid = pid;
 
         this.world.players.insert(pid, PlayerComponent::new());
-        this.world.things.insert(pid, ThingComponent::new("Yourself", "self"));
+        let mut thingc = ThingComponent::new("Yourself", "self");
+        thingc.aliases = vec!["me".into(), "myself".into()];
+        thingc.weight = PLAYER_WEIGHT;
+        this.world.things.insert(pid, thingc);
         this.add_inventory(pid);
         this.add_location(pid);
         this.add_flag(pid, Flag::Scenery);
@@ -129,6 +327,253 @@ impl WorldBuilder {
         self.world.command_handlers.push(CommandHandler::verb_visible(verb, hook));
     }
 
+    /// Adds an extension to the parser, e.g., command::Quotes.  Extensions transform the
+    /// command's words and may handle whole commands before the built-in and custom
+    /// commands; see command::ParserExtension.
+    pub fn parser_extension(&mut self, ext: &'static dyn ParserExtension) {
+        self.world.parser_extensions.push(ext);
+    }
+
+    /// Requires that the player have a capability to use the given verb.  If the player
+    /// lacks it, the command fails with the given prose.
+    pub fn verb_requires(&mut self, verb: &str, capability: Capability, text: &str) {
+        let req = Requirement::new(capability, text);
+        self.world.verb_requirements.insert(verb.into(), req);
+    }
+
+    /// Registers a turn system that the scenario needs but that the builder can't infer,
+    /// e.g., the Water system if rules flood a room with DeepWater during play.
+    /// Systems are otherwise registered as needed when the world is complete.
+    pub fn system(&mut self, system: TurnSystem) {
+        self.world.systems.insert(system);
+    }
+
+    /// Specifies which thing the verb prefers when a thing the player is carrying and a
+    /// thing here have the same noun.  Verbs prefer carried things unless told
+    /// otherwise; "get" prefers things here.
+    pub fn verb_prefers(&mut self, verb: &str, preference: Preference) {
+        self.world.verb_preferences.insert(verb.into(), preference);
+    }
+
+    /// Sets the response for a verb applied to a thing that doesn't handle it, e.g.,
+    /// "It doesn't budge." for "push".  The engine provides defaults for common verbs.
+    pub fn default_response(&mut self, verb: &str, text: &str) {
+        self.world.set_default_response(verb, text);
+    }
+
+    /// Specifies what to say when the player gets the same error message several times
+    /// in a row, e.g., for "$You $can't go that way.", "$You still $can't go that
+    /// way."  The texts are used in turn for the second and later times, the last one
+    /// repeating.  Without them, the engine varies the message itself.
+    pub fn repeat_error(&mut self, error: &str, texts: &[&str]) {
+        assert!(!texts.is_empty(), "No texts for repeated error: {}", error);
+        self.world.repeat_errors.insert(
+            error.into(),
+            texts.iter().map(|text| text.trim().to_string()).collect(),
+        );
+    }
+
+    /// Specifies what the player forfeits on being revived after a death, however the
+    /// player is revived: nothing, the default; the things the player was carrying,
+    /// left where the player died; or those things, lost.  See WBEvent::Revive.
+    pub fn revive_forfeit(&mut self, forfeit: Forfeit) {
+        self.world.revive_forfeit = forfeit;
+    }
+
+    /// Specifies the number of points the player loses on being revived after a death.
+    /// The default is 0.
+    pub fn revive_penalty(&mut self, points: i32) {
+        self.world.revive_penalty = points;
+    }
+
+    /// Sets the epitaph shown when the player dies of the given cause, e.g., "Here lies
+    /// a poor swimmer, drowned {deaths}."  "{deaths}" becomes the number of times the
+    /// player has died, e.g., "twice".  The causes are those given to
+    /// RuleBuilder::kill_as(), "drown" for drowning, and the tag of a deadline that runs
+    /// out.
+    pub fn epitaph(&mut self, cause: &str, text: &str) {
+        self.world.epitaphs.insert(cause.into(), text.trim().into());
+    }
+
+    /// Adds a help page for one of the scenario's commands, e.g., a verb added with
+    /// verb(), giving its usage, e.g., "jump <thing>", and what it does.  The player
+    /// reads it with "help <verb>".
+    pub fn help_command(&mut self, verb: &str, usage: &str, text: &str) {
+        let page = HelpPage {
+            usage: Some(usage.into()),
+            text: text.trim().into(),
+        };
+        self.world.help.insert(verb.into(), page);
+    }
+
+    /// Adds a help page on a topic, e.g., "magic", or replaces one of the built-in pages.
+    /// The player reads it with "help <topic>".
+    pub fn help_topic(&mut self, topic: &str, text: &str) {
+        let page = HelpPage {
+            usage: None,
+            text: text.trim().into(),
+        };
+        self.world.help.insert(topic.into(), page);
+    }
+
+    /// Adds a recipe: the player can combine the two tagged things, in either order, to
+    /// make the tagged output.  The inputs are moved to LIMBO, and the output, which
+    /// usually starts in LIMBO, is given to the player.
+    pub fn recipe(&mut self, output: &str, input1: &str, input2: &str) {
+        let output = self.world.alloc(output);
+        let input1 = self.world.alloc(input1);
+        let input2 = self.world.alloc(input2);
+        self.expect(Is::Thing(output));
+        self.expect(Is::Thing(input1));
+        self.expect(Is::Thing(input2));
+
+        self.world.recipes.push(Recipe {
+            inputs: (input1, input2),
+            output,
+        });
+    }
+
+    /// Seeds the random number generator, e.g., for reproducible testing.
+    pub fn seed(&mut self, seed: u64) {
+        self.world.rng = Rng::new(seed);
+    }
+
+    /// Specifies the narration's person and tense, e.g., "I took the note." rather than
+    /// "You take the note."  Prose can use narration placeholders such as "$You" and
+    /// "$are" so that it matches; see conmark::narrate().
+    pub fn narration(&mut self, person: Person, tense: Tense) {
+        self.world.narration = Narration { person, tense };
+    }
+
+    /// Specifies the scenario's title, which identifies it in the records of best
+    /// completions.
+    pub fn title(&mut self, title: &str) {
+        self.world.title = title.into();
+    }
+
+    /// Specifies how winning runs are ranked in the records of best completions,
+    /// e.g., by fewest turns or by highest score.
+    pub fn ranking(&mut self, ranking: Ranking) {
+        self.world.ranking = ranking;
+    }
+
+    /// Specifies the flag that marks things as dirty, and that washing clears, if the
+    /// scenario doesn't use the standard Dirty flag.
+    pub fn dirt_flag(&mut self, flag: Flag) {
+        self.world.dirt_flag = Some(flag);
+    }
+
+    /// Specifies that dirty actors dirty the things they pick up, e.g., a note picked
+    /// up with dirty hands.  Things flagged NeverDirty are exempt.
+    pub fn contamination(&mut self, flag: bool) {
+        self.world.contamination = flag;
+    }
+
+    /// Specifies whether the rules and turn systems run for each turn skipped by an
+    /// elapse action, with their output discarded, or whether the clock simply jumps
+    /// ahead.  They run by default.
+    pub fn fast_forward(&mut self, flag: bool) {
+        self.world.fast_forward = flag;
+    }
+
+    /// Defines a custom direction, e.g., "port" or "starboard" on a ship, with any
+    /// abbreviations, e.g., "p".  Returns the direction, for use in links: the player
+    /// goes that way with "<name>" or "go <name>", just like "north".
+    pub fn direction(&mut self, name: &'static str, abbreviations: &[&str]) -> Dir {
+        self.world.add_verb(name);
+        for abbrev in abbreviations {
+            self.world.add_syn(name, abbrev);
+        }
+        self.world.directions.insert(name);
+        Dir::Custom(name)
+    }
+
+    /// Specifies whether "go to <room>" takes the player all the way to the room, a turn
+    /// per step, or just the first step of the way.  The default is a step at a time.
+    pub fn go_to_all_the_way(&mut self, flag: bool) {
+        self.world.go_to_all_the_way = flag;
+    }
+
+    /// Allows the player to use the verb in a dark room, e.g., for one of the scenario's
+    /// own commands.  By default only movement, feeling around, and the game's own
+    /// commands work in the dark.
+    pub fn dark_verb(&mut self, verb: &str) {
+        self.world.dark_verbs.insert(verb.into());
+    }
+
+    /// Sets the response to a verb the player can't use in the dark, e.g., "$You
+    /// $fumble for it, but $find nothing." for "get".
+    pub fn dark_response(&mut self, verb: &str, text: &str) {
+        self.world.dark_responses.insert(verb.into(), text.trim().into());
+    }
+
+    /// Sets the percentage chance that the player stumbles, and gets nowhere, when moving
+    /// in the dark.  The default is 0.  See WBEvent::Stumble.
+    pub fn dark_risk(&mut self, percent: usize) {
+        assert!(percent <= 100, "Dark risk over 100%: {}", percent);
+        self.world.dark_risk = percent;
+    }
+
+    /// Specifies how many turns pass between autosaves; 0 turns autosaving off.  The
+    /// default is every 10 turns.
+    pub fn autosave(&mut self, turns: usize) {
+        self.world.autosave_turns = turns;
+    }
+
+    /// Specifies a hook that chooses where an Essential thing turns up if it would
+    /// otherwise be lost, e.g., dropped in deep water.  By default it turns up where it
+    /// started out.
+    pub fn recovery_hook(&mut self, hook: RecoveryHook) {
+        self.world.recovery_hook = Some(hook);
+    }
+
+    /// Specifies a hook that post-processes each block of prose before it is displayed,
+    /// e.g., to censor it or to slur the player's words.
+    pub fn output_hook(&mut self, hook: OutputHook) {
+        self.world.output_hook = Some(hook);
+    }
+
+    /// Specifies that the scenario keeps score, so that the score is included in the
+    /// status line.
+    pub fn scored(&mut self) {
+        self.world.scored = true;
+    }
+
+    /// Specifies whether the status line is displayed before each prompt by default.
+    /// The player can toggle it with "status on" and "status off".
+    pub fn status_line(&mut self, flag: bool) {
+        self.world.status_line = flag;
+    }
+
+    /// Specifies whether the player must be holding a portable thing to read it
+    /// (HoldPolicy::Strict, the default), or picks it up first (HoldPolicy::Lenient),
+    /// e.g., "(first taking the note)".
+    pub fn read_policy(&mut self, policy: HoldPolicy) {
+        self.world.read_policy = policy;
+    }
+
+    /// Specifies whether commands perform trivial prerequisites implicitly, e.g.,
+    /// "(first taking the rag)" when the player combines a rag lying on the ground, or
+    /// "(first unlocking the door with the key)" when the player walks through a locked
+    /// door.  Guards and traps still apply.  Disabled by default.
+    pub fn implicit_actions(&mut self, flag: bool) {
+        self.world.implicit_actions = flag;
+    }
+
+    /// Specifies the number of turns the player can tread water in rooms flagged
+    /// DeepWater before drowning, if they can't swim and have no boat.  The default
+    /// is 3.
+    #[cfg(feature = "water")]
+    pub fn drown_turns(&mut self, turns: Time) {
+        self.world.drown_turns = turns;
+    }
+
+    /// Specifies the condition under which the player wins the game.  It's checked at
+    /// the end of each turn.
+    pub fn win_when(&mut self, predicate: RulePredicate) {
+        self.world.win_condition = Some(predicate);
+    }
+
     /// Configures the player.
     pub fn player(&mut self) -> PlayerBuilder {
         PlayerBuilder {
@@ -153,11 +598,10 @@ impl WorldBuilder {
 
     /// Creates or configures a feature, i.e., a thing that's a part of its container:
     /// the player's hands, a pool of water, a big machine.  Features are things that
-    /// have their Scenery and Immovable flags set.
+    /// have their Scenery and Immovable flags set.  As part of their container they
+    /// weigh nothing of their own.
     pub fn feature(&mut self, tag: &str, name: &str, noun: &str) -> ThingBuilder {
-        self.thing(tag, name, noun)
-            .flag(Flag::Immovable)
-            .flag(Flag::Scenery)
+        self.thing(tag, name, noun).preset(Preset::Feature)
     }
 
     /// Creates or configures a thing.
@@ -189,6 +633,110 @@ impl WorldBuilder {
         }
     }
 
+    /// Creates and configures a named script: a sequence of actions that is triggered
+    /// by nothing, but that rules and command hooks can run by tag using `call`.
+    pub fn script(&mut self, tag: &str) -> RuleBuilder {
+        let id = self.world.alloc(tag);
+
+        self.world.rules.insert(id, RuleComponent::newx(Event::Call(id), &|_| true));
+        self.add_flag_set(id);
+
+        RuleBuilder {
+            wb: self,
+            tag: tag.to_string(),
+            id,
+        }
+    }
+
+    /// Creates and configures a chapter of the story.  Chapters are entered in order
+    /// of definition, each when its entry condition is met.  Use on() with
+    /// WBEvent::StartChapter to add scripts that run when the chapter begins.
+    pub fn chapter(&mut self, tag: &str, title: &str) -> ChapterBuilder {
+        let id = self.world.alloc(tag);
+
+        self.world.chapters.insert(id, ChapterComponent::new(title));
+
+        ChapterBuilder {
+            wb: self,
+            id,
+        }
+    }
+
+    /// Creates and configures a status effect the player can be under, e.g., "poisoned".
+    /// Rules apply and remove it; see RuleBuilder::apply_effect().
+    #[cfg(feature = "effects")]
+    pub fn effect(&mut self, tag: &str, name: &str) -> EffectBuilder {
+        let id = self.world.alloc(tag);
+
+        self.world.effects.insert(id, EffectComponent::new(name));
+
+        EffectBuilder {
+            wb: self,
+            id,
+        }
+    }
+
+    /// Creates and configures a deadline, which gives the player the given number of
+    /// turns once it's started, e.g., a lit fuse.  The name is how the player sees it,
+    /// as a phrase following "3 turns left", e.g., "before the fuse burns down".  Rules
+    /// start and stop it; see RuleBuilder::start_deadline().  When it runs out, the game
+    /// is over, unless a guard on WBEvent::TimeUp intervenes.
+    #[cfg(feature = "deadlines")]
+    pub fn deadline(&mut self, tag: &str, name: &str, turns: Time) -> DeadlineBuilder {
+        assert!(turns > 0, "Deadline with no turns: {}", tag);
+        let id = self.world.alloc(tag);
+
+        self.world.deadlines.insert(id, DeadlineComponent::new(name, turns));
+
+        DeadlineBuilder {
+            wb: self,
+            id,
+        }
+    }
+
+    /// Limits the whole game to the given number of turns: a deadline, tagged
+    /// "turn-limit", that's running from the start.
+    #[cfg(feature = "deadlines")]
+    pub fn turn_limit(&mut self, turns: Time) -> DeadlineBuilder {
+        self.deadline("turn-limit", "in the game", turns).started()
+    }
+
+    /// Creates and configures a table of random events.  Each turn, the table comes up
+    /// with the given percentage chance; if it does, one of its events is chosen by
+    /// weight.  Add events to the table using random_event().
+    pub fn random_table(&mut self, tag: &str, chance: usize) -> RandomTableBuilder {
+        let id = self.world.alloc(tag);
+
+        self.world.random_tables.insert(id, RandomTableComponent::new(chance));
+
+        RandomTableBuilder {
+            wb: self,
+            id,
+        }
+    }
+
+    /// Creates and configures a rule that is a random event in the tagged table.  The
+    /// weight is the event's chance of being chosen relative to the other events.
+    /// The rule's `when` predicate, if any, must be met for the event to be chosen.
+    pub fn random_event(&mut self, table_tag: &str, tag: &str, weight: usize) -> RuleBuilder {
+        let tid = self.world.alloc(table_tag);
+        self.expect(Is::RandomTable(tid));
+
+        let mut rulec = RuleComponent::new();
+        rulec.event = Event::Random(tid);
+        rulec.weight = weight;
+
+        let id = self.world.alloc(tag);
+        self.world.rules.insert(id, rulec);
+        self.add_flag_set(id);
+
+        RuleBuilder {
+            wb: self,
+            tag: tag.to_string(),
+            id,
+        }
+    }
+
     /// Creates and configures a guard that will determined whether a specific
     /// event can occur.  If the answer is no, then the guard can take some
     /// actions.
@@ -206,38 +754,247 @@ impl WorldBuilder {
     }
 
 
-    /// Completes world-building, after checking that all expectations are met.
+    /// Completes world-building, after checking that all expectations are met.  Panics
+    /// if any aren't, listing every problem; see try_world().
     pub fn world(self) -> World {
-        for expectation in self.expectations {
-            match expectation {
-                Is::Book(id) => {
-                    assert!(self.world.has_prose_type(id, ProseType::Book),
-                        "Expected book prose: [{}] {}",
-                        id, self.world.tag(id));
-                }
-                Is::Player(id) => {
-                    assert!(self.world.is_player(id),
-                        "Expected player: [{}] {}",
-                        id, self.world.tag(id));
-                }
-                Is::Room(id) => {
-                    assert!(self.world.is_room(id),
-                        "Expected room: [{}] {}",
-                        id, self.world.tag(id));
-                }
-                Is::Thing(id) => {
-                    assert!(self.world.is_thing(id),
-                        "Expected thing: [{}] {}",
-                        id, self.world.tag(id));
-                }
+        match self.try_world() {
+            Ok(world) => world,
+            Err(errors) => {
+                let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                panic!("{}", messages.join("\n"));
+            }
+        }
+    }
+
+    /// Completes world-building, after checking that all expectations are met.  If any
+    /// aren't, returns all of the problems, so that the author can fix them in one go.
+    pub fn try_world(self) -> Result<World, Vec<BuildError>> {
+        // FIRST, collect any unmet expectations.
+        let errors = self.errors();
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        // NEXT, dock the platforms at their initial stops.
+        let mut world = self.world;
+        let platforms: Vec<ID> = world.platforms.keys().cloned().collect();
+        for id in platforms {
+            platform::dock(&mut world, id);
+        }
+
+        // NEXT, the builder changes flags and nouns directly, so forget any scope
+        // queries made while building, and index the things' final nouns.
+        world.scope_cache.get_mut().clear();
+        phys::rebuild_noun_index(&mut world);
+
+        // NEXT, the things the player starts out with aren't changes, and plates
+        // start out pressed or not without firing events.
+        world.inventory_changes.clear();
+
+        let plates: Vec<ID> = world.plates.keys().cloned().collect();
+        for id in plates {
+            let pressed = phys::load(&world, id) >= world.plates[&id].threshold;
+            world.plates.get_mut(&id).unwrap().pressed = pressed;
+        }
+
+        // NEXT, remember where the essential things start out, so that they can turn
+        // up there if they would be lost.
+        let essential: Vec<ID> = world
+            .things
+            .keys()
+            .cloned()
+            .filter(|id| world.has_flags(*id) && world.has_flag(*id, Flag::Essential))
+            .collect();
+        for id in essential {
+            let origin = phys::loc(&world, id);
+            world.origins.insert(id, origin);
+        }
+
+        // NEXT, nothing has been moved yet, though placing things moved them.
+        for id in world.things.keys().cloned().collect::<Vec<_>>() {
+            if world.has_flags(id) {
+                world.unset_flag(id, Flag::Moved);
+            }
+        }
+
+        // NEXT, the player's first visit to the starting room is the start of the game.
+        let start = phys::loc(&world, world.pid);
+        if let Some(roomc) = world.rooms.get_mut(&start) {
+            roomc.visits = 1;
+        }
+
+        // NEXT, register the turn systems the scenario needs.
+        if !world.random_tables.is_empty() {
+            world.systems.insert(TurnSystem::RandomEvents);
+        }
+
+        #[cfg(feature = "machines")]
+        if !world.machines.is_empty() {
+            world.systems.insert(TurnSystem::Machines);
+        }
+
+        if world.rooms.keys().any(|id| world.has_flags(*id) && world.has_flag(*id, Flag::DeepWater)) {
+            world.systems.insert(TurnSystem::Water);
+        }
+
+        #[cfg(feature = "effects")]
+        if !world.effects.is_empty() {
+            world.systems.insert(TurnSystem::Effects);
+        }
+
+        #[cfg(feature = "deadlines")]
+        if !world.deadlines.is_empty() {
+            world.systems.insert(TurnSystem::Deadlines);
+        }
+
+        if !world.plates.is_empty() {
+            world.systems.insert(TurnSystem::Plates);
+        }
+
+        if !world.chapters.is_empty() || world.win_condition.is_some() {
+            world.systems.insert(TurnSystem::Story);
+        }
+
+        // NEXT, the systems the scenario needs must be compiled in.
+        if let Some(system) = world.systems.iter().find(|s| !s.is_enabled()) {
+            let message = format!("The scenario needs the {:?} system; enable its cargo feature", system);
+            return Err(vec![BuildError { message }]);
+        }
+
+        Ok(world)
+    }
+
+    /// Returns a human-readable summary of the world as built so far: the rooms and
+    /// their exits, the things and where they are, the rules and the events that trigger
+    /// them, and any expectations world() would find unmet.  Authors can print it, e.g.,
+    /// with the --report option, or compare it with a saved copy to review a scenario at
+    /// a glance.
+    pub fn report(&self) -> String {
+        let world = &self.world;
+        let by_tag = |ids: Vec<ID>| {
+            let mut ids = ids;
+            ids.sort_by_key(|id| world.tag(*id));
+            ids
+        };
+        let mut lines: Vec<String> = Vec::new();
+
+        // FIRST, the rooms and their exits.
+        lines.push("Rooms:".into());
+        for id in by_tag(world.rooms.keys().cloned().collect()) {
+            let roomc = &world.rooms[&id];
+            lines.push(format!("  {} \"{}\"", world.tag(id), roomc.name));
+
+            let mut links: Vec<_> = roomc.links.iter().collect();
+            links.sort_by_key(|(dir, _)| **dir);
+            for (dir, dest) in links {
+                let dest = match dest {
+                    LinkDest::Room(room) => world.tag(room.id()),
+                    LinkDest::DeadEnd(_) => "(dead end)".into(),
+                };
+                lines.push(format!("    {} -> {}", dir_name(*dir), dest));
             }
         }
-        self.world
+
+        // NEXT, the things and where they are.
+        lines.push("Things:".into());
+        for id in by_tag(world.things.keys().cloned().collect()) {
+            let place = match world.locations.get(&id) {
+                Some(locc) => world.tag(locc.id()),
+                None => "(nowhere)".into(),
+            };
+            lines.push(format!("  {} \"{}\" in {}", world.tag(id), world.things[&id].name, place));
+        }
+
+        // NEXT, the rules and their events.
+        lines.push("Rules:".into());
+        for id in by_tag(world.rules.keys().cloned().collect()) {
+            lines.push(format!("  {}: {}", world.tag(id), event_phrase(world, &world.rules[&id].event)));
+        }
+
+        // NEXT, any unmet expectations.
+        let errors = self.errors();
+
+        if errors.is_empty() {
+            lines.push("Unmet expectations: none".into());
+        } else {
+            lines.push("Unmet expectations:".into());
+            lines.extend(errors.iter().map(|error| format!("  {}", error)));
+        }
+
+        lines.join("\n") + "\n"
     }
 
     //-------------------------------------------------------------------------------------------
     // Utility methods
 
+    /// Returns the unmet expectations, sorted by message.
+    fn errors(&self) -> Vec<BuildError> {
+        let mut errors: Vec<BuildError> = self
+            .expectations
+            .iter()
+            .filter_map(|e| self.unmet(e))
+            .map(|message| BuildError { message })
+            .collect();
+        errors.sort_by(|a, b| a.message.cmp(&b.message));
+        errors
+    }
+
+    /// Checks an expectation, returning a message if it isn't met.
+    fn unmet(&self, expectation: &Is) -> Option<String> {
+        let world = &self.world;
+        let (met, what, id) = match *expectation {
+            Is::Book(id) => (world.has_prose_type(id, ProseType::Book), "book prose", id),
+            Is::Chapter(id) => (world.chapters.get(&id).is_some(), "chapter", id),
+            Is::Named(id) => (world.is_room(id) || world.is_thing(id), "room or thing", id),
+            Is::Platform(id) => {
+                let stops = world.platforms.get(&id).map_or(true, |p| p.stops.is_empty());
+                (world.is_room(id) && !stops, "platform with stops", id)
+            }
+            Is::Plate(id) => (world.plates.get(&id).is_some(), "pressure plate", id),
+            Is::Portal(id) => (world.is_thing(id) && world.portals.get(&id).is_some(), "portal", id),
+            Is::Player(id) => (world.is_player(id), "player", id),
+            Is::RandomTable(id) => (world.random_tables.get(&id).is_some(), "random table", id),
+            Is::Rule(id) => (world.rules.get(&id).is_some(), "rule or script", id),
+            Is::Room(id) => (world.is_room(id), "room", id),
+            Is::Stop(platform, room) => {
+                let met = world
+                    .platforms
+                    .get(&platform)
+                    .map_or(false, |p| p.stops.iter().any(|s| s.room == room));
+                if met {
+                    return None;
+                }
+                return Some(format!(
+                    "Expected stop of platform [{}] {}: [{}] {}",
+                    platform,
+                    world.tag(platform),
+                    room,
+                    world.tag(room)
+                ));
+            }
+            Is::Thing(id) => (world.is_thing(id), "thing", id),
+            #[cfg(feature = "machines")]
+            Is::Machine(id) => (world.is_thing(id) && world.machines.get(&id).is_some(), "machine", id),
+            #[cfg(feature = "deadlines")]
+            Is::Deadline(id) => (world.deadlines.get(&id).is_some(), "deadline", id),
+            #[cfg(feature = "effects")]
+            Is::Effect(id) => (world.effects.get(&id).is_some(), "status effect", id),
+            Is::Consumable(id) => {
+                (world.is_thing(id) && world.consumables.get(&id).is_some(), "consumable", id)
+            }
+            Is::Lockable(id) => {
+                (world.is_thing(id) && world.locks.get(&id).is_some(), "lockable thing", id)
+            }
+        };
+
+        if met {
+            None
+        } else {
+            Some(format!("Expected {}: [{}] {}", what, id, world.tag(id)))
+        }
+    }
+
     /// Adds an expectation for later checking.
     fn expect(&mut self, expectation: Is) {
         self.expectations.insert(expectation);
@@ -246,9 +1003,7 @@ impl WorldBuilder {
     /// Adds a location to an entity if it doesn't have one.  The entity will initially
     /// be in LIMBO.
     fn add_location(&mut self, id: ID) {
-        if self.world.locations.get(&id).is_none() {
-            self.world.locations.insert(id, LocationComponent::new());
-        }
+        phys::add_location(&mut self.world, id);
     }
 
     /// Sets the location of the thing to the entity with the given tag, creating
@@ -262,7 +1017,7 @@ impl WorldBuilder {
         self.add_location(thing);
 
         // NEXT, put the thing in the location.
-        phys::put_in(&mut self.world, thing, loc);
+        phys::move_thing(&mut self.world, thing, loc);
     }
 
     /// Adds an inventory to an entity if it doesn't have one.
@@ -298,10 +1053,32 @@ impl WorldBuilder {
     fn add_prose(&mut self, id: ID, prose_type: ProseType, text: &str) {
         self.add_prose_component(id);
 
-        let prose = Prose::Prose(text.trim().into());
+        let prose = Prose::Prose(self.intern(text.trim()));
+        self.world.proses.get_mut(&id).unwrap().types.insert(prose_type, prose);
+    }
+
+    /// Adds alternate prose strings of a given type to an entity's prose component,
+    /// creating the component if necessary; one is shown on each view.
+    fn add_prose_variants(&mut self, id: ID, prose_type: ProseType, texts: &[&str], variation: Variation) {
+        self.add_prose_component(id);
+
+        let texts = texts.iter().map(|text| self.intern(text.trim())).collect();
+        let prose = Prose::Variants(ProseVariants::new(texts, variation));
         self.world.proses.get_mut(&id).unwrap().types.insert(prose_type, prose);
     }
 
+    /// Returns the shared copy of the prose string, adding it to the prose table if
+    /// it's new.
+    fn intern(&mut self, text: &str) -> Rc<str> {
+        if let Some(shared) = self.prose_table.get(text) {
+            return shared.clone();
+        }
+
+        let shared: Rc<str> = text.into();
+        self.prose_table.insert(shared.clone());
+        shared
+    }
+
     /// Adds a prose hook of a given type to an entity's prose component,
     /// creating the component if necessary.
     fn add_prose_hook(&mut self, id: ID, prose_type: ProseType, hook: EntityProseHook) {
@@ -318,23 +1095,207 @@ impl WorldBuilder {
         let tag: String = match evt {
             WBEvent::GetThing(thing_tag) => {
                 let tid = self.world.alloc(thing_tag);
-                rulec.event = Event::GetThing(self.world.pid, tid);
+                rulec.event = Event::GetThing(self.world.pid, tid, world::LIMBO);
                 self.expect(Is::Thing(tid));
                 format!("{}-get-{}", kind, thing_tag)
             }
-            WBEvent::ReadThing(thing_tag) => {
+            WBEvent::GetThingInto(thing_tag, dest_tag) => {
                 let tid = self.world.alloc(thing_tag);
-                rulec.event = Event::ReadThing(self.world.pid, tid);
+                let did = self.world.alloc(dest_tag);
+                rulec.event = Event::GetThing(self.world.pid, tid, did);
                 self.expect(Is::Thing(tid));
-                self.expect(Is::Book(tid));
-                format!("{}-read-{}", kind, thing_tag)
+                self.expect(Is::Thing(did));
+                format!("{}-get-{}-into-{}", kind, thing_tag, dest_tag)
             }
-            WBEvent::EnterRoom(room_tag) => {
+            WBEvent::PutThing(thing_tag, container_tag) => {
+                let tid = self.world.alloc(thing_tag);
+                let cid = self.world.alloc(container_tag);
+                rulec.event = Event::PutThing(self.world.pid, tid, cid);
+                self.expect(Is::Thing(tid));
+                self.expect(Is::Thing(cid));
+                format!("{}-put-{}-in-{}", kind, thing_tag, container_tag)
+            }
+            WBEvent::GetAnyWith(flag) => {
+                rulec.event = Event::GetThing(self.world.pid, world::LIMBO, world::LIMBO);
+                rulec.class = Some(*flag);
+                format!("{}-get-any-{:?}", kind, flag)
+            }
+            WBEvent::ReadThing(thing_tag) => {
+                let tid = self.world.alloc(thing_tag);
+                rulec.event = Event::ReadThing(self.world.pid, tid);
+                self.expect(Is::Thing(tid));
+                self.expect(Is::Book(tid));
+                format!("{}-read-{}", kind, thing_tag)
+            }
+            WBEvent::EnterRoom(room_tag) => {
                 let rid = self.world.alloc(room_tag);
                 rulec.event = Event::EnterRoom(self.world.pid, rid);
                 self.expect(Is::Room(rid));
                 format!("{}-enter-{}", kind, room_tag)
             }
+            WBEvent::DetectTrap(trap_tag) => {
+                let tid = self.world.alloc(trap_tag);
+                rulec.event = Event::DetectTrap(self.world.pid, tid);
+                self.expect(Is::Thing(tid));
+                format!("{}-detect-{}", kind, trap_tag)
+            }
+            WBEvent::DisarmTrap(trap_tag) => {
+                let tid = self.world.alloc(trap_tag);
+                rulec.event = Event::DisarmTrap(self.world.pid, tid);
+                self.expect(Is::Thing(tid));
+                format!("{}-disarm-{}", kind, trap_tag)
+            }
+            WBEvent::SpringTrap(trap_tag) => {
+                let tid = self.world.alloc(trap_tag);
+                rulec.event = Event::SpringTrap(self.world.pid, tid);
+                self.expect(Is::Thing(tid));
+                format!("{}-spring-{}", kind, trap_tag)
+            }
+            WBEvent::StartChapter(chapter_tag) => {
+                let cid = self.world.alloc(chapter_tag);
+                rulec.event = Event::StartChapter(cid);
+                self.expect(Is::Chapter(cid));
+                format!("{}-start-{}", kind, chapter_tag)
+            }
+            WBEvent::Unlock(thing_tag) => {
+                let tid = self.world.alloc(thing_tag);
+                rulec.event = Event::Unlock(self.world.pid, tid);
+                self.expect(Is::Lockable(tid));
+                format!("{}-unlock-{}", kind, thing_tag)
+            }
+            WBEvent::Lock(thing_tag) => {
+                let tid = self.world.alloc(thing_tag);
+                rulec.event = Event::Lock(self.world.pid, tid);
+                self.expect(Is::Lockable(tid));
+                format!("{}-lock-{}", kind, thing_tag)
+            }
+            WBEvent::CorrectCode(thing_tag) => {
+                let tid = self.world.alloc(thing_tag);
+                rulec.event = Event::CorrectCode(self.world.pid, tid);
+                self.expect(Is::Lockable(tid));
+                format!("{}-code-{}", kind, thing_tag)
+            }
+            WBEvent::WrongCode(thing_tag) => {
+                let tid = self.world.alloc(thing_tag);
+                rulec.event = Event::WrongCode(self.world.pid, tid);
+                self.expect(Is::Lockable(tid));
+                format!("{}-wrong-code-{}", kind, thing_tag)
+            }
+            #[cfg(feature = "machines")]
+            WBEvent::SwitchOn(thing_tag) => {
+                let tid = self.world.alloc(thing_tag);
+                rulec.event = Event::SwitchOn(self.world.pid, tid);
+                self.expect(Is::Machine(tid));
+                format!("{}-switch-on-{}", kind, thing_tag)
+            }
+            #[cfg(feature = "machines")]
+            WBEvent::SwitchOff(thing_tag) => {
+                let tid = self.world.alloc(thing_tag);
+                rulec.event = Event::SwitchOff(self.world.pid, tid);
+                self.expect(Is::Machine(tid));
+                format!("{}-switch-off-{}", kind, thing_tag)
+            }
+            #[cfg(feature = "machines")]
+            WBEvent::Running(thing_tag) => {
+                let tid = self.world.alloc(thing_tag);
+                rulec.event = Event::Running(tid);
+                self.expect(Is::Machine(tid));
+                format!("{}-running-{}", kind, thing_tag)
+            }
+            WBEvent::Manipulate(thing_tag, how) => {
+                let tid = self.world.alloc(thing_tag);
+                rulec.event = Event::Manipulate(self.world.pid, tid, *how);
+                self.expect(Is::Thing(tid));
+                format!("{}-{}-{}", kind, how.verb(), thing_tag)
+            }
+            WBEvent::Exhausted(thing_tag) => {
+                let tid = self.world.alloc(thing_tag);
+                rulec.event = Event::Exhausted(tid);
+                self.expect(Is::Consumable(tid));
+                format!("{}-exhausted-{}", kind, thing_tag)
+            }
+            WBEvent::Combine(thing_tag) => {
+                let tid = self.world.alloc(thing_tag);
+                rulec.event = Event::Combine(self.world.pid, tid);
+                self.expect(Is::Thing(tid));
+                format!("{}-combine-{}", kind, thing_tag)
+            }
+            WBEvent::PlatformMove(platform_tag, stop_tag) => {
+                let pid = self.world.alloc(platform_tag);
+                let sid = self.world.alloc(stop_tag);
+                rulec.event = Event::PlatformMove(self.world.pid, pid, sid);
+                self.expect(Is::Platform(pid));
+                self.expect(Is::Stop(pid, sid));
+                format!("{}-move-{}-{}", kind, platform_tag, stop_tag)
+            }
+            WBEvent::Climb(thing_tag) => {
+                let tid = self.world.alloc(thing_tag);
+                rulec.event = Event::Climb(self.world.pid, tid);
+                self.expect(Is::Thing(tid));
+                format!("{}-climb-{}", kind, thing_tag)
+            }
+            #[cfg(feature = "water")]
+            WBEvent::Drown => {
+                rulec.event = Event::Drown(self.world.pid);
+                format!("{}-drown", kind)
+            }
+            WBEvent::Wash(thing_tag) => {
+                let tid = self.world.alloc(thing_tag);
+                rulec.event = Event::Wash(self.world.pid, tid);
+                self.expect(Is::Thing(tid));
+                format!("{}-wash-{}", kind, thing_tag)
+            }
+            WBEvent::InventoryChanged => {
+                rulec.event = Event::InventoryChanged(self.world.pid);
+                format!("{}-inventory-changed", kind)
+            }
+            WBEvent::Steal(owner_tag) => {
+                let oid = self.world.alloc(owner_tag);
+                rulec.event = Event::Steal(self.world.pid, oid, world::LIMBO);
+                self.expect(Is::Named(oid));
+                format!("{}-steal-from-{}", kind, owner_tag)
+            }
+            WBEvent::PlatePressed(plate_tag) => {
+                let id = self.world.alloc(plate_tag);
+                rulec.event = Event::PlatePressed(id);
+                self.expect(Is::Plate(id));
+                format!("{}-pressed-{}", kind, plate_tag)
+            }
+            WBEvent::PlateReleased(plate_tag) => {
+                let id = self.world.alloc(plate_tag);
+                rulec.event = Event::PlateReleased(id);
+                self.expect(Is::Plate(id));
+                format!("{}-released-{}", kind, plate_tag)
+            }
+            WBEvent::EnterPortal(portal_tag) => {
+                let id = self.world.alloc(portal_tag);
+                rulec.event = Event::EnterPortal(self.world.pid, id);
+                self.expect(Is::Portal(id));
+                format!("{}-enter-portal-{}", kind, portal_tag)
+            }
+            WBEvent::Stumble(room_tag) => {
+                let rid = self.world.alloc(room_tag);
+                rulec.event = Event::Stumble(self.world.pid, rid);
+                self.expect(Is::Room(rid));
+                format!("{}-stumble-{}", kind, room_tag)
+            }
+            #[cfg(feature = "deadlines")]
+            WBEvent::TimeUp(deadline_tag) => {
+                let id = self.world.alloc(deadline_tag);
+                rulec.event = Event::TimeUp(self.world.pid, id);
+                self.expect(Is::Deadline(id));
+                format!("{}-time-up-{}", kind, deadline_tag)
+            }
+            WBEvent::Search(room_tag) => {
+                let rid = self.world.alloc(room_tag);
+                rulec.event = Event::Search(self.world.pid, rid);
+                self.expect(Is::Room(rid));
+                format!("{}-search-{}", kind, room_tag)
+            }
+            WBEvent::Revive => {
+                rulec.event = Event::Revive(self.world.pid);
+                format!("{}-revive", kind)
+            }
         };
 
         let id = self.world.alloc(&tag);
@@ -358,6 +1319,11 @@ pub struct PlayerBuilder<'a> {
 }
 
 impl<'a> PlayerBuilder<'a> {
+    /// Returns the player's ID.
+    pub fn id(&self) -> PlayerID {
+        PlayerID::new(self.wb.world.pid)
+    }
+
     /// Sets the player's initial location given the location's tag
     pub fn location(self, loc_tag: &str) -> PlayerBuilder<'a> {
         self.wb.set_location(self.wb.world.pid, loc_tag);
@@ -385,6 +1351,30 @@ impl<'a> PlayerBuilder<'a> {
         self.wb.add_flag(self.wb.world.pid, flag);
         self
     }
+
+    /// Sets several flags on the player at once.
+    pub fn flags(self, flags: &[Flag]) -> PlayerBuilder<'a> {
+        for flag in flags {
+            self.wb.add_flag(self.wb.world.pid, *flag);
+        }
+        self
+    }
+
+    /// Specifies the player's own weight, for pressure plates.  Defaults to
+    /// PLAYER_WEIGHT.
+    pub fn weight(self, weight: u32) -> PlayerBuilder<'a> {
+        self.wb.world.things.get_mut(&self.wb.world.pid).unwrap().weight = weight;
+        self
+    }
+
+    /// Creates a body-part feature attached to the player, e.g., hands or pockets, and
+    /// returns its builder.  Like any feature, it is immovable scenery; its scenery
+    /// prose is included when the player examines themselves, and it can be examined
+    /// and given verbs like any other thing, e.g., "wash hands".
+    pub fn feature(self, tag: &str, name: &str, noun: &str) -> ThingBuilder<'a> {
+        let wb = self.wb;
+        wb.feature(tag, name, noun).location(PLAYER)
+    }
 }
 
 /// # RoomBuilder -- A tool for creating and configuring room entities.
@@ -395,12 +1385,25 @@ pub struct RoomBuilder<'a> {
 }
 
 impl<'a> RoomBuilder<'a> {
+    /// Returns the room's ID.
+    pub fn id(&self) -> RoomID {
+        RoomID::new(self.id)
+    }
+
     /// Adds descriptive prose to the room.
     pub fn prose(self, text: &str) -> RoomBuilder<'a> {
         self.wb.add_prose(self.id, ProseType::Room, text);
         self
     }
 
+    /// Adds alternate descriptive prose to the room, so that it doesn't read identically
+    /// on every visit.  The first string is shown on the first view, and the others are
+    /// chosen on later views according to the variation.
+    pub fn prose_variants(self, texts: &[&str], variation: Variation) -> RoomBuilder<'a> {
+        self.wb.add_prose_variants(self.id, ProseType::Room, texts, variation);
+        self
+    }
+
     /// Adds a prose hook to the room, to produce descriptive prose
     /// on demand.
     pub fn prose_hook(self, hook: EntityProseHook) -> RoomBuilder<'a> {
@@ -414,6 +1417,14 @@ impl<'a> RoomBuilder<'a> {
         self
     }
 
+    /// Sets several flags on the room at once, e.g., Dark and DeepWater.
+    pub fn flags(self, flags: &[Flag]) -> RoomBuilder<'a> {
+        for flag in flags {
+            self.wb.add_flag(self.id, *flag);
+        }
+        self
+    }
+
     /// Creates a link from this room to another room given the direction and
     /// the other room's tag.
     pub fn link(self, dir: Dir, room_tag: &str) -> RoomBuilder<'a> {
@@ -421,18 +1432,76 @@ impl<'a> RoomBuilder<'a> {
         let dest = self.wb.world.alloc(room_tag);
         self.wb.expect(Is::Room(dest));
 
-        let link = LinkDest::Room(dest);
+        let link = LinkDest::Room(RoomID::new(dest));
         self.wb.world.rooms.get_mut(&self.id).unwrap().links.insert(dir, link);
 
         self
     }
 
+    /// Requires that the player have a capability to follow the link in the given
+    /// direction.  If the player lacks it, the move fails with the given prose.
+    pub fn requires(self, dir: Dir, capability: Capability, text: &str) -> RoomBuilder<'a> {
+        let req = Requirement::new(capability, text);
+        self.wb.world.rooms.get_mut(&self.id).unwrap().requirements.insert(dir, req);
+        self
+    }
+
+    /// Guards the link in the given direction with the tagged trap, a thing that
+    /// should be flagged Trapped.  Following the link springs the trap, unless it's
+    /// been disarmed.
+    pub fn trap(self, dir: Dir, trap_tag: &str) -> RoomBuilder<'a> {
+        let trap = self.wb.world.alloc(trap_tag);
+        self.wb.expect(Is::Thing(trap));
+        self.wb.world.rooms.get_mut(&self.id).unwrap().traps.insert(dir, trap);
+        self
+    }
+
+    /// Guards the link in the given direction with the tagged door, a lockable thing.
+    /// The player can't follow the link while the door is locked.
+    pub fn door(self, dir: Dir, door_tag: &str) -> RoomBuilder<'a> {
+        let door = self.wb.world.alloc(door_tag);
+        self.wb.expect(Is::Lockable(door));
+        self.wb.world.rooms.get_mut(&self.id).unwrap().doors.insert(dir, door);
+        self
+    }
+
+    /// Makes the room a platform that moves between stops, e.g., an elevator or a
+    /// ferry.  The player leaves it in the given direction.  Add its stops with
+    /// `stop()`; it starts at the first.
+    pub fn platform(self, exit: Dir) -> RoomBuilder<'a> {
+        self.wb.world.platforms.insert(self.id, PlatformComponent::new(exit));
+        self.wb.expect(Is::Platform(self.id));
+        self
+    }
+
+    /// Adds a stop to the platform: the tagged room, which the player leaves in the
+    /// given direction to board the platform while it's there.
+    pub fn stop(self, room_tag: &str, entry: Dir) -> RoomBuilder<'a> {
+        let room = self.wb.world.alloc(room_tag);
+        self.wb.expect(Is::Room(room));
+
+        self.wb.world.platforms
+            .get_mut(&self.id)
+            .expect("Not a platform; call platform() first")
+            .stops
+            .push(Stop { room, entry });
+        self
+    }
+
     /// Adds a dead end in the given direction.
     pub fn dead_end(self, dir: Dir, text: &str) -> RoomBuilder<'a> {
         let dead_end = LinkDest::DeadEnd(text.into());
         self.wb.world.rooms.get_mut(&self.id).unwrap().links.insert(dir, dead_end);
         self
     }
+
+    /// Makes the room's floor a pressure plate, pressed while the weight in the room,
+    /// including the player's, is at least the threshold.  See WBEvent::PlatePressed
+    /// and WBEvent::PlateReleased.
+    pub fn pressure_plate(self, threshold: u32) -> RoomBuilder<'a> {
+        self.wb.world.plates.insert(self.id, PlateComponent::new(threshold));
+        self
+    }
 }
 
 /// # ThingBuilder -- A tool for creating and configuring thing entities.
@@ -443,17 +1512,122 @@ pub struct ThingBuilder<'a> {
 }
 
 impl<'a> ThingBuilder<'a> {
+    /// Returns the thing's ID.
+    pub fn id(&self) -> ThingID {
+        ThingID::new(self.id)
+    }
+
     /// Sets the thing's initial location given the location's tag.
     pub fn location(self, loc: &str) -> ThingBuilder<'a> {
         self.wb.set_location(self.id, loc);
         self
     }
+
+    /// Adds an additional noun by which the thing can be referred to.
+    pub fn alias(self, noun: &str) -> ThingBuilder<'a> {
+        self.wb.world.things.get_mut(&self.id).unwrap().aliases.push(noun.into());
+        self
+    }
+
+    /// Adds an adjective that sets the thing apart from others with the same noun, e.g.,
+    /// "red" for one of several keys.  The player can say "get red key", and the
+    /// adjective is added to the thing's name when things with the same name are
+    /// listed together.
+    pub fn adjective(self, word: &str) -> ThingBuilder<'a> {
+        self.wb.world.things.get_mut(&self.id).unwrap().adjectives.push(word.into());
+        self
+    }
+
+    /// Specifies the thing's listing priority: things with lower priorities are listed
+    /// first, and are preferred when several things have the same noun.  Defaults to 0.
+    pub fn priority(self, priority: i32) -> ThingBuilder<'a> {
+        self.wb.world.things.get_mut(&self.id).unwrap().priority = priority;
+        self
+    }
+
+    /// Specifies the room or NPC the thing belongs to.  Taking the thing in the owner's
+    /// presence fires a Steal event; see WBEvent::Steal.
+    pub fn owner(self, owner: &str) -> ThingBuilder<'a> {
+        let oid = self.wb.world.alloc(owner);
+        self.wb.expect(Is::Named(oid));
+        self.wb.world.things.get_mut(&self.id).unwrap().owner = Some(oid);
+        self
+    }
+
+    /// Specifies the thing's own weight, for pressure plates.  Defaults to 1.
+    pub fn weight(self, weight: u32) -> ThingBuilder<'a> {
+        self.wb.world.things.get_mut(&self.id).unwrap().weight = weight;
+        self
+    }
+
+    /// Makes the thing a portal to the tagged thing, and that thing a portal back to
+    /// this one, e.g., a pair of magic mirrors.  Entering either portal takes the
+    /// player to the room the other is in.  See WBEvent::EnterPortal.
+    pub fn portal(self, other_tag: &str) -> ThingBuilder<'a> {
+        let other = self.wb.world.alloc(other_tag);
+        self.wb.expect(Is::Thing(other));
+        self.wb.world.portals.insert(self.id, PortalComponent::new(Some(other)));
+        self.wb.world.portals.insert(other, PortalComponent::new(Some(self.id)));
+        self
+    }
+
+    /// Makes the thing a one-way portal to the tagged thing: entering it takes the
+    /// player to the room the other thing is in, but there's no way back.
+    pub fn portal_one_way(self, other_tag: &str) -> ThingBuilder<'a> {
+        let other = self.wb.world.alloc(other_tag);
+        self.wb.expect(Is::Thing(other));
+        self.wb.world.portals.insert(self.id, PortalComponent::new(Some(other)));
+        self.wb.world.portals.entry(other).or_insert_with(|| PortalComponent::new(None));
+        self
+    }
+
+    /// Makes the thing a container, e.g., a sack, that the player can put things in and
+    /// get them out of.  See WBEvent::PutThing and WBEvent::GetThingInto.
+    pub fn container(self) -> ThingBuilder<'a> {
+        self.wb.add_inventory(self.id);
+        self
+    }
+
+    /// Makes the thing a pressure plate, e.g., a scale pan or a pedestal, pressed while
+    /// the weight of the things on it is at least the threshold.  See
+    /// WBEvent::PlatePressed and WBEvent::PlateReleased.
+    pub fn pressure_plate(self, threshold: u32) -> ThingBuilder<'a> {
+        self.wb.add_inventory(self.id);
+        self.wb.world.plates.insert(self.id, PlateComponent::new(threshold));
+        self
+    }
+
+    /// Specifies the article to use with the thing's name, e.g., "an" for "hour".
+    pub fn article(self, article: &str) -> ThingBuilder<'a> {
+        self.wb.world.things.get_mut(&self.id).unwrap().article = Article::Custom(article.into());
+        self
+    }
+
+    /// Specifies that the thing is a mass noun, e.g., "some water".
+    pub fn mass(self) -> ThingBuilder<'a> {
+        self.article("some")
+    }
+
+    /// Specifies that the thing's name is a proper noun, and takes no article,
+    /// e.g., "Excalibur".
+    pub fn proper(self) -> ThingBuilder<'a> {
+        self.wb.world.things.get_mut(&self.id).unwrap().article = Article::Proper;
+        self
+    }
+
     /// Adds descriptive prose to the thing.
     pub fn on_examine(self, text: &str) -> ThingBuilder<'a> {
         self.wb.add_prose(self.id, ProseType::Thing, text);
         self
     }
 
+    /// Adds alternate descriptive prose to the thing, chosen on each examination
+    /// according to the variation; see RoomBuilder::prose_variants().
+    pub fn on_examine_variants(self, texts: &[&str], variation: Variation) -> ThingBuilder<'a> {
+        self.wb.add_prose_variants(self.id, ProseType::Thing, texts, variation);
+        self
+    }
+
     /// Adds a prose hook to the thing, to produce descriptive prose
     /// on demand.
     pub fn on_examine_hook(self, hook: EntityProseHook) -> ThingBuilder<'a> {
@@ -487,11 +1661,417 @@ impl<'a> ThingBuilder<'a> {
         self
     }
 
+    /// Gives the thing a plural name and noun, e.g., "gold coins" and "coins", so that
+    /// identical copies of it are listed together and the player can refer to several
+    /// at once, e.g., "get two coins".  See copies().
+    pub fn plural(self, name: &str, noun: &str) -> ThingBuilder<'a> {
+        self.wb.world.things.get_mut(&self.id).unwrap().plural = Some((name.into(), noun.into()));
+        self
+    }
+
+    /// Adds the given number of identical copies of the thing, as configured so far, in
+    /// the same place, tagged "<tag>-2", "<tag>-3", etc.  The copies get the thing's
+    /// name, nouns, prose, and flags, but none of its other components, e.g., a
+    /// machine's.
+    pub fn copies(self, count: usize) -> ThingBuilder<'a> {
+        let loc = phys::loc(&self.wb.world, self.id);
+        let thingc = self.wb.world.things[&self.id].clone();
+        let flags = self.wb.world.flag_sets[&self.id].clone();
+        let prose = self.wb.world.proses.get(&self.id).cloned();
+
+        for i in 2..count + 2 {
+            let id = self.wb.world.alloc(&format!("{}-{}", self.tag, i));
+            self.wb.world.things.insert(id, thingc.clone());
+            self.wb.world.flag_sets.insert(id, flags.clone());
+            if let Some(prose) = &prose {
+                self.wb.world.proses.insert(id, prose.clone());
+            }
+            self.wb.add_location(id);
+            phys::move_thing(&mut self.wb.world, id, loc);
+        }
+
+        self
+    }
+
+    /// Conceals the thing in its room: it isn't listed, and the player can't see or
+    /// handle it until searching the room reveals it.  See WBEvent::Search.
+    pub fn concealed(self) -> ThingBuilder<'a> {
+        self.wb.add_flag(self.id, Flag::Concealed);
+        self
+    }
+
+    /// Adds initial prose to the thing, which describes it in its room until it's first
+    /// moved, in place of the usual listing, e.g., "A gleaming sword is stuck in a stump
+    /// here."
+    pub fn initial(self, text: &str) -> ThingBuilder<'a> {
+        self.wb.add_prose(self.id, ProseType::Initial, text);
+        self
+    }
+
+    /// Makes the thing visible from adjacent rooms, e.g., a tower or a mountain, and
+    /// gives it the prose to display when it's examined from there.
+    pub fn distant(self, text: &str) -> ThingBuilder<'a> {
+        self.wb.add_flag(self.id, Flag::Distant);
+        self.wb.add_prose(self.id, ProseType::Distant, text);
+        self
+    }
+
+    /// Makes the thing climbable.  What happens when the player climbs it is up to
+    /// rules on the Climb event.
+    pub fn climbable(self) -> ThingBuilder<'a> {
+        self.wb.world.climbables.entry(self.id).or_insert_with(ClimbComponent::new);
+        self.wb.add_flag(self.id, Flag::Climbable);
+        self
+    }
+
+    /// Makes the thing climbable, leading in the given direction from its room, e.g.,
+    /// Up for a ladder.  Climbing it is just like following the room's link.
+    pub fn climbs(self, dir: Dir) -> ThingBuilder<'a> {
+        self.wb.world.climbables
+            .entry(self.id)
+            .or_insert_with(ClimbComponent::new)
+            .dir = Some(dir);
+        self.wb.add_flag(self.id, Flag::Climbable);
+        self
+    }
+
+    /// Requires that the player have a capability to climb the thing, e.g., a rope.
+    /// If the player lacks it, the climb fails with the given prose.
+    pub fn climb_requires(self, capability: Capability, text: &str) -> ThingBuilder<'a> {
+        self.wb.world.climbables
+            .entry(self.id)
+            .or_insert_with(ClimbComponent::new)
+            .requirement = Some(Requirement::new(capability, text));
+        self.wb.add_flag(self.id, Flag::Climbable);
+        self
+    }
+
+    /// Adds a conversation topic to the thing: the player can talk to the thing and
+    /// ask it about the topic, receiving the given prose as a response.
+    pub fn topic(self, name: &str, text: &str) -> ThingBuilder<'a> {
+        self.topic_when(name, &|_| true, text)
+    }
+
+    /// Adds a conversation topic to the thing that can only be asked about when the
+    /// predicate is true, e.g., once the player has learned of it.
+    pub fn topic_when(self, name: &str, predicate: RulePredicate, text: &str) -> ThingBuilder<'a> {
+        let topic = Topic {
+            name: name.into(),
+            predicate,
+            prose: text.trim().into(),
+        };
+
+        self.wb.world.topics
+            .entry(self.id)
+            .or_insert_with(TopicComponent::new)
+            .topics
+            .push(topic);
+        self
+    }
+
+    /// Makes the thing lockable with the tagged key.  Call it once for each key that
+    /// fits; things flagged MasterKey fit as well.
+    pub fn lockable_with(self, key_tag: &str) -> ThingBuilder<'a> {
+        let key = self.wb.world.alloc(key_tag);
+        self.wb.expect(Is::Thing(key));
+        self.wb.world.locks
+            .entry(self.id)
+            .or_insert_with(LockComponent::new)
+            .keys
+            .insert(key);
+        self
+    }
+
+    /// Makes the thing lockable, and initially locked.  Give it keys with
+    /// `lockable_with()`.
+    pub fn locked(self) -> ThingBuilder<'a> {
+        self.wb.world.locks.entry(self.id).or_insert_with(LockComponent::new);
+        self.wb.add_flag(self.id, Flag::Locked);
+        self
+    }
+
+    /// Gives the thing a combination, e.g., a safe or a keypad.  The thing is lockable;
+    /// entering the combination with "dial", "turn", "set", or "enter" unlocks it.
+    pub fn combination(self, code: &str) -> ThingBuilder<'a> {
+        self.wb.world.locks
+            .entry(self.id)
+            .or_insert_with(LockComponent::new)
+            .combination = Some(code.into());
+        self
+    }
+
+    /// Makes the thing a machine, which the player can switch on and off.  Rules on its
+    /// Running event give it behavior while it runs.
+    #[cfg(feature = "machines")]
+    pub fn machine(self) -> ThingBuilder<'a> {
+        self.wb.world.machines.entry(self.id).or_insert_with(MachineComponent::new);
+        self
+    }
+
+    /// Makes the thing a machine that runs only while the tagged battery is in its
+    /// inventory.  The player can put the battery in the machine.
+    #[cfg(feature = "machines")]
+    pub fn powered_by(self, battery_tag: &str) -> ThingBuilder<'a> {
+        let battery = self.wb.world.alloc(battery_tag);
+        self.wb.expect(Is::Thing(battery));
+        self.wb.add_inventory(self.id);
+        self.wb.world.machines
+            .entry(self.id)
+            .or_insert_with(MachineComponent::new)
+            .power = Power::Battery(battery);
+        self
+    }
+
+    /// Makes the thing a machine that runs only in rooms flagged Powered.
+    #[cfg(feature = "machines")]
+    pub fn needs_powered_room(self) -> ThingBuilder<'a> {
+        self.wb.world.machines
+            .entry(self.id)
+            .or_insert_with(MachineComponent::new)
+            .power = Power::Room;
+        self
+    }
+
+    /// Makes the thing a machine, and gives it an ambient line that's added to the
+    /// description of its room while it's running, e.g., "The generator hums here."
+    #[cfg(feature = "machines")]
+    pub fn ambient(self, text: &str) -> ThingBuilder<'a> {
+        self.wb.world.machines
+            .entry(self.id)
+            .or_insert_with(MachineComponent::new)
+            .ambient = Some(text.trim().into());
+        self
+    }
+
+    /// Makes the thing a consumable with the given number of charges.  Scripts use a
+    /// charge with `consume()`; a consumable machine uses one each turn it runs.
+    pub fn charges(self, count: i64) -> ThingBuilder<'a> {
+        self.wb.world.consumables.entry(self.id).or_insert_with(ConsumableComponent::new);
+        self.wb.world.set_var(self.id, consumable::CHARGES, count);
+        self
+    }
+
+    /// Makes the thing a consumable, and gives it a warning that's displayed each time
+    /// a charge is used while no more than the given number remain, e.g., "The lamp
+    /// flickers."
+    pub fn low_charge(self, count: i64, warning: &str) -> ThingBuilder<'a> {
+        let consumablec = self.wb.world.consumables
+            .entry(self.id)
+            .or_insert_with(ConsumableComponent::new);
+        consumablec.low = count;
+        consumablec.warning = Some(warning.trim().into());
+        self
+    }
+
+    /// Makes the thing a consumable, and gives it the prose added to its description
+    /// once its charges have run out.
+    pub fn on_exhausted(self, text: &str) -> ThingBuilder<'a> {
+        self.wb.world.consumables
+            .entry(self.id)
+            .or_insert_with(ConsumableComponent::new)
+            .exhausted = text.trim().into();
+        self
+    }
+
+    /// Sets the initial value of one of the thing's variables.
+    pub fn var(self, name: &str, value: i64) -> ThingBuilder<'a> {
+        self.wb.world.set_var(self.id, name, value);
+        self
+    }
+
+    /// Makes the thing a button that sends the tagged platform on to its next stop
+    /// when the player pushes or presses it, e.g., a ferry's bell.
+    pub fn moves_platform(self, platform_tag: &str) -> ThingBuilder<'a> {
+        let platform = self.wb.world.alloc(platform_tag);
+        self.wb.expect(Is::Platform(platform));
+        self.wb.world.buttons.insert(self.id, ButtonComponent::new(platform, None));
+        self
+    }
+
+    /// Makes the thing a button that sends the tagged platform to the tagged stop
+    /// when the player pushes or presses it, e.g., an elevator's call button.
+    pub fn calls_platform(self, platform_tag: &str, stop_tag: &str) -> ThingBuilder<'a> {
+        let platform = self.wb.world.alloc(platform_tag);
+        let stop = self.wb.world.alloc(stop_tag);
+        self.wb.expect(Is::Platform(platform));
+        self.wb.expect(Is::Stop(platform, stop));
+        self.wb.world.buttons.insert(self.id, ButtonComponent::new(platform, Some(stop)));
+        self
+    }
+
+    /// Adds a handler for "<verb> <noun>" commands, where the noun refers to this thing.
+    /// It's tried before the generic command handlers; it can return Handled::Pass to
+    /// fall through to them.
+    pub fn on_verb(self, verb: &str, hook: CommandHook) -> ThingBuilder<'a> {
+        if !self.wb.world.verbs.contains(verb) {
+            self.wb.world.add_verb(verb);
+        }
+
+        self.wb.world.verb_handlers
+            .entry(self.id)
+            .or_insert_with(VerbComponent::new)
+            .handlers
+            .insert(verb.into(), hook);
+        self
+    }
+
+    /// Adds a handler for "use <noun>" commands, where the noun refers to this thing.
+    pub fn on_use(self, hook: CommandHook) -> ThingBuilder<'a> {
+        self.on_verb("use", hook)
+    }
+
     /// Sets a flag on the thing.
     pub fn flag(self, flag: Flag) -> ThingBuilder<'a> {
         self.wb.add_flag(self.id, flag);
         self
     }
+
+    /// Sets several flags on the thing at once.
+    pub fn flags(self, flags: &[Flag]) -> ThingBuilder<'a> {
+        for flag in flags {
+            self.wb.add_flag(self.id, *flag);
+        }
+        self
+    }
+
+    /// Configures the thing as a standard kind of thing, e.g., a container or a lamp,
+    /// with all of the flags and components it needs.  See Preset.
+    pub fn preset(self, preset: Preset) -> ThingBuilder<'a> {
+        match preset {
+            Preset::Feature => self.flags(&[Flag::Immovable, Flag::Scenery]).weight(0),
+            Preset::Fixture => self.flag(Flag::Immovable).weight(0),
+            Preset::Container => self.container(),
+            Preset::LightSource => self.flag(Flag::LightSource),
+            #[cfg(feature = "machines")]
+            Preset::Lamp => self.machine().flag(Flag::LightSource),
+        }
+    }
+}
+
+/// # ChapterBuilder -- A tool for configuring chapters.
+pub struct ChapterBuilder<'a> {
+    wb: &'a mut WorldBuilder,
+    id: ID,
+}
+
+impl<'a> ChapterBuilder<'a> {
+    /// Specifies the chapter's entry condition.  If omitted, the chapter begins as
+    /// soon as the previous chapter has begun.
+    pub fn when(self, predicate: RulePredicate) -> ChapterBuilder<'a> {
+        self.wb.world.chapters.get_mut(&self.id).unwrap().predicate = predicate;
+        self
+    }
+
+    /// Adds a goal to the chapter: text describing it, and a predicate that's true
+    /// once it's been achieved.
+    pub fn goal(self, text: &str, predicate: RulePredicate) -> ChapterBuilder<'a> {
+        let goal = Goal {
+            text: text.into(),
+            predicate,
+        };
+        self.wb.world.chapters.get_mut(&self.id).unwrap().goals.push(goal);
+        self
+    }
+}
+
+/// # EffectBuilder -- A tool for configuring status effects.
+#[cfg(feature = "effects")]
+pub struct EffectBuilder<'a> {
+    wb: &'a mut WorldBuilder,
+    id: ID,
+}
+
+#[cfg(feature = "effects")]
+impl<'a> EffectBuilder<'a> {
+    /// Specifies what happens when the effect is applied while it's already active.
+    /// Defaults to Stacking::Refresh.
+    pub fn stacking(self, stacking: Stacking) -> EffectBuilder<'a> {
+        self.wb.world.effects.get_mut(&self.id).unwrap().stacking = stacking;
+        self
+    }
+
+    /// Specifies the tagged rule or named script to run each turn the effect is active,
+    /// once per stack.  The player is the script's subject.
+    pub fn tick(self, script_tag: &str) -> EffectBuilder<'a> {
+        let script = self.wb.world.alloc(script_tag);
+        self.wb.expect(Is::Rule(script));
+        self.wb.world.effects.get_mut(&self.id).unwrap().tick = Some(RuleID::new(script));
+        self
+    }
+
+    /// Specifies the text shown when the effect wears off or is removed.
+    pub fn on_expire(self, text: &str) -> EffectBuilder<'a> {
+        self.wb.world.effects.get_mut(&self.id).unwrap().expire = Some(text.trim().into());
+        self
+    }
+}
+
+/// # DeadlineBuilder -- A tool for configuring deadlines.
+#[cfg(feature = "deadlines")]
+pub struct DeadlineBuilder<'a> {
+    wb: &'a mut WorldBuilder,
+    id: ID,
+}
+
+#[cfg(feature = "deadlines")]
+impl<'a> DeadlineBuilder<'a> {
+    /// Starts the deadline at the beginning of the game, rather than when a rule starts
+    /// it.
+    pub fn started(self) -> DeadlineBuilder<'a> {
+        let deadlinec = self.wb.world.deadlines.get_mut(&self.id).unwrap();
+        deadlinec.left = Some(deadlinec.turns);
+        self
+    }
+
+    /// Warns the player every so many turns while the deadline is running, e.g., "The
+    /// fuse fizzes; {turns} to go."
+    pub fn warn_every(self, turns: Time, text: &str) -> DeadlineBuilder<'a> {
+        assert!(turns > 0, "Warning every 0 turns: {}", self.wb.world.tag(self.id));
+        self.wb.world.deadlines.get_mut(&self.id).unwrap().every =
+            Some((turns, text.trim().into()));
+        self
+    }
+
+    /// Warns the player when the given number of turns is left, instead of any periodic
+    /// warning.
+    pub fn warn_at(self, turns: Time, text: &str) -> DeadlineBuilder<'a> {
+        self.wb
+            .world
+            .deadlines
+            .get_mut(&self.id)
+            .unwrap()
+            .warnings
+            .insert(turns, text.trim().into());
+        self
+    }
+
+    /// Specifies the text shown when the deadline runs out.  Defaults to "$You $have run
+    /// out of time."
+    pub fn on_expire(self, text: &str) -> DeadlineBuilder<'a> {
+        self.wb.world.deadlines.get_mut(&self.id).unwrap().expire = Some(text.trim().into());
+        self
+    }
+}
+
+/// # RandomTableBuilder -- A tool for configuring random event tables.
+pub struct RandomTableBuilder<'a> {
+    wb: &'a mut WorldBuilder,
+    id: ID,
+}
+
+impl<'a> RandomTableBuilder<'a> {
+    /// Limits the table to rooms with the given flag, e.g., User("FOREST").
+    pub fn region(self, flag: Flag) -> RandomTableBuilder<'a> {
+        self.wb.world.random_tables.get_mut(&self.id).unwrap().region = Some(flag);
+        self
+    }
+
+    /// Specifies the number of turns after an event fires before the table can
+    /// come up again.
+    pub fn cooldown(self, turns: Time) -> RandomTableBuilder<'a> {
+        self.wb.world.random_tables.get_mut(&self.id).unwrap().cooldown = turns;
+        self
+    }
 }
 
 /// # RuleBuilder -- A tool for creating and configuring rules.
@@ -502,6 +2082,11 @@ pub struct RuleBuilder<'a> {
 }
 
 impl<'a> RuleBuilder<'a> {
+    /// Returns the rule's ID.
+    pub fn id(&self) -> RuleID {
+        RuleID::new(self.id)
+    }
+
     /// Specifies the predicate for normal rules.  If omitted, the rule fires every time it
     /// is triggered.
     pub fn when(self, predicate: RulePredicate) -> RuleBuilder<'a> {
@@ -520,6 +2105,29 @@ impl<'a> RuleBuilder<'a> {
         self
     }
 
+    /// Specifies that the rule can only fire during the tagged chapter.
+    pub fn in_chapter(self, chapter_tag: &str) -> RuleBuilder<'a> {
+        let cid = self.wb.world.alloc(chapter_tag);
+        self.wb.expect(Is::Chapter(cid));
+        self.wb.world.rules.get_mut(&self.id).unwrap().chapter = Some(cid);
+        self
+    }
+
+    /// Specifies how often the rule can fire: once in the game, like once_only(), or
+    /// once per visit to a room, e.g., for an EnterRoom rule that shouldn't repeat
+    /// while the player stays put.
+    pub fn once_per(self, once: Once) -> RuleBuilder<'a> {
+        match once {
+            Once::Game => self.once_only(),
+            Once::Visit => {
+                let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+                assert!(!rulec.is_guard, "Cannot set 'once_per' on allow() rule: {}", self.tag);
+                rulec.once_per_visit = true;
+                self
+            }
+        }
+    }
+
     /// Specifies that the rule should execute no more than once.
     pub fn once_only(self) -> RuleBuilder<'a> {
         let rulec = &self.wb.world.rules[&self.id];
@@ -528,6 +2136,38 @@ impl<'a> RuleBuilder<'a> {
         self
     }
 
+    /// Specifies that once the rule fires, it can't fire again for the given number of
+    /// turns, e.g., so that an ambient message doesn't appear on every turn.
+    pub fn cooldown(self, turns: Time) -> RuleBuilder<'a> {
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        assert!(!rulec.is_guard, "Cannot set 'cooldown' on allow() rule: {}", self.tag);
+        rulec.cooldown = turns;
+        self
+    }
+
+    /// Makes an EnterRoom guard send the player to the tagged room instead of keeping
+    /// the player out, e.g., a trapdoor that drops the player into the cellar.  The
+    /// guard's script is executed first, and then the player enters the other room,
+    /// subject to its own guards.
+    pub fn redirect(self, room_tag: &str) -> RuleBuilder<'a> {
+        let rid = self.wb.world.alloc(room_tag);
+        self.wb.expect(Is::Room(rid));
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        assert!(rulec.is_guard, "Cannot set 'redirect' on normal rule: {}", self.tag);
+        assert!(
+            matches!(rulec.event, Event::EnterRoom(..)),
+            "Cannot set 'redirect' on a guard for anything but EnterRoom: {}",
+            self.tag
+        );
+        assert!(
+            !matches!(rulec.event, Event::EnterRoom(_, room) if room == rid),
+            "Cannot 'redirect' a guard to the room it guards: {}",
+            self.tag
+        );
+        rulec.redirect = Some(rid);
+        self
+    }
+
     /// Specifies text to print when the rule fires.
     pub fn print(self, text: &str) -> RuleBuilder<'a> {
         let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
@@ -536,6 +2176,117 @@ impl<'a> RuleBuilder<'a> {
         self
     }
 
+    /// Specifies text to print when the rule fires, provided that the player is in
+    /// the tagged room.
+    pub fn print_in(self, room_tag: &str, text: &str) -> RuleBuilder<'a> {
+        let rid = self.wb.world.alloc(room_tag);
+        self.wb.expect(Is::Room(rid));
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.print_to(Audience::Room(room_tag.into()), text);
+
+        self
+    }
+
+    /// Specifies text to print when the rule fires, provided that the player is in
+    /// a room with the given flag.
+    pub fn print_region(self, flag: Flag, text: &str) -> RuleBuilder<'a> {
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.print_to(Audience::Region(flag), text);
+
+        self
+    }
+
+    /// Plays a cutscene when the rule fires: the pages are displayed one at a time,
+    /// with a "[more]" prompt between them, and nothing else is displayed for the
+    /// remainder of the turn.  Actions added after the cutscene (e.g., `put_in`)
+    /// take effect when it ends.
+    pub fn cutscene(self, pages: &[&str]) -> RuleBuilder<'a> {
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.cutscene(pages);
+        self
+    }
+
+    /// Clears the tagged thing's owner when the rule fires, e.g., when the player pays
+    /// for it, so that taking it is no longer theft.
+    pub fn disown(self, thing: &str) -> RuleBuilder<'a> {
+        let id = self.wb.world.alloc(thing);
+        self.wb.expect(Is::Thing(id));
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.disown(thing);
+        self
+    }
+
+    /// Cuts to later in the scenario when the rule fires: prints the transition text,
+    /// e.g., "Later that day...", and skips the given number of turns.  See
+    /// WorldBuilder::fast_forward().
+    pub fn elapse(self, turns: usize, text: &str) -> RuleBuilder<'a> {
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.elapse(turns, text);
+        self
+    }
+
+    /// Applies the tagged status effect to the player when the rule fires, for the
+    /// given number of turns or, if None, until removed.
+    #[cfg(feature = "effects")]
+    pub fn apply_effect(self, effect: &str, turns: Option<Time>) -> RuleBuilder<'a> {
+        let id = self.wb.world.alloc(effect);
+        self.wb.expect(Is::Effect(id));
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.apply_effect(effect, turns);
+        self
+    }
+
+    /// Removes the tagged status effect from the player when the rule fires.
+    #[cfg(feature = "effects")]
+    pub fn remove_effect(self, effect: &str) -> RuleBuilder<'a> {
+        let id = self.wb.world.alloc(effect);
+        self.wb.expect(Is::Effect(id));
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.remove_effect(effect);
+        self
+    }
+
+    /// Starts the tagged deadline when the rule fires, or starts it over if it's
+    /// running.
+    #[cfg(feature = "deadlines")]
+    pub fn start_deadline(self, deadline: &str) -> RuleBuilder<'a> {
+        let id = self.wb.world.alloc(deadline);
+        self.wb.expect(Is::Deadline(id));
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.start_deadline(deadline);
+        self
+    }
+
+    /// Stops the tagged deadline when the rule fires, e.g., when the player cuts the
+    /// fuse.
+    #[cfg(feature = "deadlines")]
+    pub fn stop_deadline(self, deadline: &str) -> RuleBuilder<'a> {
+        let id = self.wb.world.alloc(deadline);
+        self.wb.expect(Is::Deadline(id));
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.stop_deadline(deadline);
+        self
+    }
+
+    /// Makes a sound in the tagged room when the rule fires.  The player hears it if
+    /// they are within `loudness` links of the room; e.g., a loudness of 1 carries
+    /// into adjacent rooms.
+    pub fn sound(self, room_tag: &str, loudness: usize, sound: &str) -> RuleBuilder<'a> {
+        let rid = self.wb.world.alloc(room_tag);
+        self.wb.expect(Is::Room(rid));
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.sound(room_tag, loudness, sound);
+
+        self
+    }
+
     /// Sets a flag on the entity.
     pub fn set_flag(self, tag: &str, flag: Flag) -> RuleBuilder<'a> {
         // FIRST, get the entity on which we'll be adding the flag, and
@@ -562,6 +2313,139 @@ impl<'a> RuleBuilder<'a> {
         self
     }
 
+    /// Gives the tagged room or thing a new name, e.g., after the clearing burns.
+    pub fn rename(self, tag: &str, name: &str) -> RuleBuilder<'a> {
+        let id = self.wb.world.alloc(tag);
+        self.wb.expect(Is::Named(id));
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.rename(tag, name);
+        self
+    }
+
+    /// Replaces the tagged entity's prose of the given type, so that rules can
+    /// permanently alter descriptions without the need for prose hooks.
+    pub fn set_prose(self, tag: &str, prose_type: ProseType, text: &str) -> RuleBuilder<'a> {
+        self.wb.world.alloc(tag);
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.set_prose(tag, prose_type, text);
+        self
+    }
+
+    /// Identifies the tagged thing, giving it a new name and nouns; e.g., the "strange
+    /// key" becomes the "vault key".  The first noun is the thing's primary noun; the
+    /// rest are aliases.  From then on the parser knows the thing only by its new nouns.
+    pub fn identify(self, thing: &str, name: &str, nouns: &[&str]) -> RuleBuilder<'a> {
+        let id = self.wb.world.alloc(thing);
+        self.wb.expect(Is::Thing(id));
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.identify(thing, name, nouns);
+        self
+    }
+
+    /// Adds points to the player's score when the rule fires.  The points can be
+    /// negative.
+    pub fn score(self, points: i32) -> RuleBuilder<'a> {
+        self.wb.world.scored = true;
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.score(points);
+        self
+    }
+
+    /// Locks the tagged lockable thing, without need of a key.
+    pub fn lock(self, thing: &str) -> RuleBuilder<'a> {
+        let id = self.wb.world.alloc(thing);
+        self.wb.expect(Is::Lockable(id));
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.lock(thing);
+        self
+    }
+
+    /// Unlocks the tagged lockable thing, without need of a key.
+    pub fn unlock(self, thing: &str) -> RuleBuilder<'a> {
+        let id = self.wb.world.alloc(thing);
+        self.wb.expect(Is::Lockable(id));
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.unlock(thing);
+        self
+    }
+
+    /// Uses one of the tagged consumable's charges.
+    pub fn consume(self, thing: &str) -> RuleBuilder<'a> {
+        let id = self.wb.world.alloc(thing);
+        self.wb.expect(Is::Consumable(id));
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.consume(thing);
+        self
+    }
+
+    /// Adds a link from the tagged room to the tagged destination room in the given
+    /// direction, e.g., a passage revealed when the bookcase slides aside.  Links are
+    /// one-way; add the reverse link as well if the player should be able to return.
+    pub fn link(self, room: &str, dir: Dir, dest: &str) -> RuleBuilder<'a> {
+        let id = self.wb.world.alloc(room);
+        self.wb.expect(Is::Room(id));
+        let id = self.wb.world.alloc(dest);
+        self.wb.expect(Is::Room(id));
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.link(room, dir, dest);
+        self
+    }
+
+    /// Removes the tagged room's link in the given direction.
+    pub fn unlink(self, room: &str, dir: Dir) -> RuleBuilder<'a> {
+        let id = self.wb.world.alloc(room);
+        self.wb.expect(Is::Room(id));
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.unlink(room, dir);
+        self
+    }
+
+    /// Moves a thing (or the player) into the tagged entity's inventory.
+    pub fn put_in(self, thing: &str, inv: &str) -> RuleBuilder<'a> {
+        let id = self.wb.world.alloc(thing);
+        self.wb.add_location(id);
+        let loc = self.wb.world.alloc(inv);
+        self.wb.add_inventory(loc);
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.put_in(thing, inv);
+        self
+    }
+
+    /// Runs the script of the tagged rule or named script, as though its actions were
+    /// part of this rule.  Its event and predicate are ignored.
+    pub fn call(self, script: &str) -> RuleBuilder<'a> {
+        let id = self.wb.world.alloc(script);
+        self.wb.expect(Is::Rule(id));
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.call(script);
+        self
+    }
+
+    /// Asks the player a question when the rule fires.  Each answer is paired with the
+    /// tag of the rule or named script to run when the player gives it; the answer "*"
+    /// accepts any text, which the script's prose can show as "{answer}".  Input that
+    /// answers nothing is taken as a command, and the question is dropped.
+    pub fn prompt(self, question: &str, answers: &[(&str, &str)]) -> RuleBuilder<'a> {
+        for (_, script) in answers {
+            let id = self.wb.world.alloc(script);
+            self.wb.expect(Is::Rule(id));
+        }
+
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.prompt(question, answers);
+        self
+    }
+
     /// Moves a thing to LIMBO
     pub fn forget(self, thing: &str) -> RuleBuilder<'a> {
         // FIRST, get the entity which we'll be forgetting.
@@ -574,6 +2458,22 @@ impl<'a> RuleBuilder<'a> {
         self
     }
 
+    /// Adds an entry to the player's quest journal when the rule fires, e.g., to
+    /// record a plot point.  The player reads the journal with "read journal".
+    pub fn journal_entry(self, text: &str) -> RuleBuilder<'a> {
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.journal_entry(text);
+        self
+    }
+
+    /// Moves the subject of the triggering event to LIMBO, e.g., the thing that was
+    /// gotten.
+    pub fn forget_subject(self) -> RuleBuilder<'a> {
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.forget(script::SUBJECT);
+        self
+    }
+
     /// Kills the tagged entity, i.e., sets the Dead flag.
     /// TODO: At present, really presumes that the entity is the player.
     /// Eventually, we might have NPCs, monsters, etc.  But the script
@@ -586,6 +2486,22 @@ impl<'a> RuleBuilder<'a> {
         self
     }
 
+    /// Kills the tagged entity, like kill(), announcing the death with the given message
+    /// instead of the usual "$You $have died.", and showing the epitaph for the given
+    /// cause of death; see WorldBuilder::epitaph().  Either can be omitted.
+    pub fn kill_as(
+        self,
+        tag: &str,
+        message: Option<&str>,
+        cause: Option<&str>,
+    ) -> RuleBuilder<'a> {
+        let id = self.wb.world.lookup(tag);
+        self.wb.expect(Is::Player(id));
+        let rulec = &mut self.wb.world.rules.get_mut(&self.id).unwrap();
+        rulec.script.kill_as(tag, message, cause);
+        self
+    }
+
     /// Revives the tagged entity, i.e., clears the Dead flag.
     /// TODO: At present, really presumes that the entity is the player.
     /// Eventually, we might have NPCs, monsters, etc.  But the script
@@ -598,3 +2514,59 @@ impl<'a> RuleBuilder<'a> {
         self
     }
 }
+
+/// Returns the direction's name for the world builder report, e.g., "north".
+fn dir_name(dir: Dir) -> String {
+    match dir {
+        Dir::Custom(name) => name.into(),
+        _ => format!("{:?}", dir).to_lowercase(),
+    }
+}
+
+/// Describes the event for the world builder report by kind and the tags of the
+/// entities it refers to, e.g., "GetThing(PLAYER, sword, LIMBO)".
+fn event_phrase(world: &World, event: &Event) -> String {
+    let text = format!("{:?}", event);
+    let kind = text.split('(').next().unwrap_or_default();
+    let tags: Vec<String> = event.entities().iter().map(|id| world.tag(*id)).collect();
+
+    if tags.is_empty() {
+        kind.into()
+    } else {
+        format!("{}({})", kind, tags.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario;
+
+    #[test]
+    fn test_report() {
+        let report = scenario::builder().report();
+        assert!(report.contains("  clearing \"A Dreary Clearing\"\n    north -> (dead end)\n"));
+        assert!(report.contains("  sword \"sword\" in hilltop\n"));
+        assert!(report.contains("  on-get-sword: GetThing(PLAYER, sword, LIMBO)\n"));
+        assert!(report.ends_with("Unmet expectations: none\n"));
+
+        let mut wb = WorldBuilder::new();
+        wb.on(&WBEvent::GetThing("ghost")).print("Boo!");
+        assert!(wb.report().contains("Unmet expectations:\n  Expected thing: ["));
+    }
+
+    #[test]
+    fn test_try_world() {
+        assert!(scenario::builder().try_world().is_ok());
+
+        // Every unmet expectation is reported, not just the first.
+        let mut wb = WorldBuilder::new();
+        wb.on(&WBEvent::GetThing("ghost")).print("Boo!");
+        wb.on(&WBEvent::EnterRoom("attic")).print("Creak.");
+
+        let errors = wb.try_world().err().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].message.starts_with("Expected room: ["));
+        assert!(errors[1].message.starts_with("Expected thing: ["));
+    }
+}